@@ -0,0 +1,96 @@
+// IEEE 754-2008 decimal64 and decimal128 in the binary integer decimal (BID)
+// encoding.
+//
+// A finite decimal float is (-1)^s * C * 10^q with an integer coefficient C of
+// at most 16 or 34 digits. After the sign, the combination field holds the
+// biased exponent and the coefficient. If its two highest bits are not 11, the
+// exponent comes first and the coefficient takes all bits after it. Otherwise
+// the coefficient has the implied high bits 100, the exponent starts two bits
+// later, and a coefficient above the largest one of the format is not
+// canonical and has the value zero. This is always the case in decimal128. If
+// the four highest bits are all set, the value is infinite or NaN.
+
+/// The number of digits of a decimal64 coefficient.
+pub(crate) const DECIMAL64_DIGITS: u32 = 16;
+
+/// The exponent of a decimal64 with a biased exponent of zero.
+pub(crate) const DECIMAL64_MIN_EXPONENT: i32 = -398;
+
+/// The number of digits of a decimal128 coefficient.
+pub(crate) const DECIMAL128_DIGITS: u32 = 34;
+
+/// The exponent of a decimal128 with a biased exponent of zero.
+pub(crate) const DECIMAL128_MIN_EXPONENT: i32 = -6176;
+
+const DECIMAL64_EXPONENT_BITS: u32 = 10;
+const DECIMAL128_EXPONENT_BITS: u32 = 14;
+
+/// A finite decimal float, as its sign, coefficient and exponent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct DecimalParts {
+    pub sign: bool,
+    pub coefficient: u128,
+    pub exponent: i32,
+}
+
+#[inline]
+pub(crate) const fn is_nonfinite_decimal64(bits: u64) -> bool {
+    (bits >> 59) & 0xf == 0xf
+}
+
+#[inline]
+pub(crate) const fn is_nan_decimal64(bits: u64) -> bool {
+    (bits >> 58) & 0x1f == 0x1f
+}
+
+#[inline]
+pub(crate) const fn is_nonfinite_decimal128(bits: u128) -> bool {
+    (bits >> 123) & 0xf == 0xf
+}
+
+#[inline]
+pub(crate) const fn is_nan_decimal128(bits: u128) -> bool {
+    (bits >> 122) & 0x1f == 0x1f
+}
+
+/// Decodes a finite decimal64.
+#[inline]
+pub(crate) const fn decimal64_parts(bits: u64) -> DecimalParts {
+    let (biased_exponent, coefficient) = if (bits >> 61) & 3 != 3 {
+        (bits >> 53, bits & ((1 << 53) - 1))
+    } else {
+        (bits >> 51, (0b100 << 51) | (bits & ((1 << 51) - 1)))
+    };
+    let biased_exponent = biased_exponent & ((1 << DECIMAL64_EXPONENT_BITS) - 1);
+    let coefficient = coefficient as u128;
+    DecimalParts {
+        sign: bits >> 63 != 0,
+        coefficient: if coefficient < 10u128.pow(DECIMAL64_DIGITS) {
+            coefficient
+        } else {
+            0
+        },
+        exponent: biased_exponent as i32 + DECIMAL64_MIN_EXPONENT,
+    }
+}
+
+/// Decodes a finite decimal128.
+#[inline]
+pub(crate) const fn decimal128_parts(bits: u128) -> DecimalParts {
+    // The coefficients with the implied high bits are all too large.
+    let (biased_exponent, coefficient) = if (bits >> 125) & 3 != 3 {
+        (bits >> 113, bits & ((1 << 113) - 1))
+    } else {
+        (bits >> 111, 0)
+    };
+    let biased_exponent = biased_exponent & ((1 << DECIMAL128_EXPONENT_BITS) - 1);
+    DecimalParts {
+        sign: bits >> 127 != 0,
+        coefficient: if coefficient < 10u128.pow(DECIMAL128_DIGITS) {
+            coefficient
+        } else {
+            0
+        },
+        exponent: biased_exponent as i32 + DECIMAL128_MIN_EXPONENT,
+    }
+}
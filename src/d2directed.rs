@@ -0,0 +1,104 @@
+// Shortest digits of a double that lie on one side of its exact value.
+//
+// Ryū's `d2d` picks the shortest decimal in the rounding interval [vm, vp]
+// around the value v. Here the interval is cut at v instead, to [vm, v] or
+// [v, vp], so the result still parses back to the same double but is never
+// above (or below) it. The search is the same digit removal loop, with the
+// integers just outside the cut interval as bounds: the candidate at each
+// length is the truncation (or the ceiling) of v, and a digit can be removed
+// as long as the shorter candidate is still strictly past the excluded bound.
+
+use crate::d2s::{d2d_interval, decimal_length17, DecimalInterval64, FloatingDecimal64};
+use crate::d2s_intrinsics::div10;
+use crate::raw::mantissa::write_mantissa_long;
+
+/// Shortest decimal in the rounding interval of the double that is at most
+/// its exact value.
+#[inline]
+pub const fn d2d_down(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
+    let DecimalInterval64 {
+        mut vr,
+        mut vm,
+        e10,
+        vm_is_trailing_zeros,
+        accept_bounds,
+        ..
+    } = d2d_interval(ieee_mantissa, ieee_exponent, true);
+
+    // vm becomes the largest integer that is not in the interval. This is one
+    // below the lower bound when that bound is exact and accepted.
+    vm -= (accept_bounds && vm_is_trailing_zeros) as u64;
+    let mut removed = 0i32;
+    loop {
+        let vr_div10 = div10(vr);
+        let vm_div10 = div10(vm);
+        if vr_div10 <= vm_div10 {
+            break;
+        }
+        vr = vr_div10;
+        vm = vm_div10;
+        removed += 1;
+    }
+
+    FloatingDecimal64 {
+        exponent: e10 + removed,
+        mantissa: vr,
+    }
+}
+
+/// Shortest decimal in the rounding interval of the double that is at least
+/// its exact value.
+#[inline]
+pub const fn d2d_up(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
+    let DecimalInterval64 {
+        vr,
+        mut vp,
+        e10,
+        vr_is_trailing_zeros,
+        ..
+    } = d2d_interval(ieee_mantissa, ieee_exponent, true);
+
+    // vm is the largest integer below the exact value. Truncating it and
+    // adding one gives the ceiling of the value at every length.
+    let mut vm = vr - vr_is_trailing_zeros as u64;
+    let mut removed = 0i32;
+    loop {
+        let vp_div10 = div10(vp);
+        let vm_div10 = div10(vm);
+        if vp_div10 <= vm_div10 {
+            break;
+        }
+        vp = vp_div10;
+        vm = vm_div10;
+        removed += 1;
+    }
+
+    FloatingDecimal64 {
+        exponent: e10 + removed,
+        mantissa: vm + 1,
+    }
+}
+
+/// Number of decimal digits of a directed mantissa. Half of the rounding
+/// interval can need one more digit than all of it, so this is up to 18.
+#[inline]
+pub const fn decimal_length18(v: u64) -> u32 {
+    if v >= 100000000000000000 {
+        18
+    } else {
+        decimal_length17(v)
+    }
+}
+
+/// Writes the digits of a directed mantissa, ending at `result`.
+/// [`write_mantissa_long`] takes up to 17 digits, so the last digit of an
+/// 18-digit mantissa is written first.
+#[inline]
+pub const unsafe fn write_mantissa18(output: u64, result: *mut u8) {
+    if output >= 100000000000000000 {
+        *result.sub(1) = b'0' + (output % 10) as u8;
+        write_mantissa_long(output / 10, result.sub(1));
+    } else {
+        write_mantissa_long(output, result);
+    }
+}
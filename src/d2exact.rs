@@ -0,0 +1,183 @@
+// All digits of the exact decimal value of a double.
+//
+// A finite double is m2 * 2^e2 with an odd m2, so its integer part has at most
+// 309 digits, and its fraction, an odd multiple of 2^-k, exactly k digits
+// after the decimal point. Neither needs a larger bignum than the one for
+// fixed precision. The integer part is split into 19-digit chunks by repeated
+// division by 10^19, which gives the lowest chunk first, so they are all kept.
+// The fraction is multiplied by 10^19 to move its next 19 digits above the
+// binary point, which gives the highest chunk first, so it is streamed.
+
+use core::ptr;
+
+use crate::d2fixed::{div_small, f64_parts, mul_small, shl, Big, LIMBS};
+use crate::d2s::{DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS};
+use crate::q2s::decimal_length39;
+use crate::raw::mantissa::{write_mantissa, write_mantissa_long};
+
+/// The number of digits in a chunk.
+pub(crate) const CHUNK_DIGITS: usize = 19;
+
+const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+// 2^1024 has 309 digits.
+const INTEGER_CHUNKS: usize = 17;
+
+/// The digits of a double, chunk by chunk.
+pub(crate) struct ExactDigits {
+    // The 19-digit chunks of the integer part, the lowest one first, of which
+    // the first `integer_chunks` remain to be written.
+    integer: [u64; INTEGER_CHUNKS],
+    integer_chunks: usize,
+    integer_length: usize,
+
+    // The fraction, as a multiple of 2^-fraction_bits, and the number of its
+    // digits that remain to be written.
+    fraction: Big,
+    fraction_bits: u32,
+    fraction_digits: usize,
+}
+
+impl ExactDigits {
+    /// The digits of the magnitude of `f`, which must be finite.
+    #[inline]
+    pub(crate) const fn new(f: f64) -> Self {
+        let mut digits = ExactDigits {
+            integer: [0; INTEGER_CHUNKS],
+            integer_chunks: 0,
+            integer_length: 0,
+            fraction: [0; LIMBS],
+            fraction_bits: 0,
+            fraction_digits: 0,
+        };
+        let bits = f.to_bits();
+        let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+        let ieee_exponent =
+            (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+        let (mut m2, mut e2) = f64_parts(ieee_mantissa, ieee_exponent);
+        if m2 == 0 {
+            return digits;
+        }
+        let zeros = m2.trailing_zeros();
+        m2 >>= zeros;
+        e2 += zeros as i32;
+
+        let mut integer = [0u64; LIMBS];
+        if e2 >= 0 {
+            integer[0] = m2;
+            integer = shl(integer, e2 as u32);
+        } else {
+            let bits = -e2 as u32;
+            if bits < 64 {
+                integer[0] = m2 >> bits;
+                digits.fraction[0] = m2 & ((1 << bits) - 1);
+            } else {
+                digits.fraction[0] = m2;
+            }
+            digits.fraction_bits = bits;
+            digits.fraction_digits = bits as usize;
+        }
+
+        while !is_zero(&integer) {
+            digits.integer[digits.integer_chunks] = div_small(&mut integer, CHUNK);
+            digits.integer_chunks += 1;
+        }
+        if digits.integer_chunks > 0 {
+            let highest = digits.integer[digits.integer_chunks - 1];
+            digits.integer_length = (digits.integer_chunks - 1) * CHUNK_DIGITS
+                + decimal_length39(highest as u128) as usize;
+        }
+        digits
+    }
+
+    /// The number of digits of the integer part, which is zero if it is zero.
+    #[inline]
+    pub(crate) const fn integer_length(&self) -> usize {
+        self.integer_length
+    }
+
+    /// The number of digits after the decimal point, without trailing zeros.
+    #[inline]
+    pub(crate) const fn fraction_length(&self) -> usize {
+        self.fraction_bits as usize
+    }
+
+    /// Writes the next chunk of the integer part to the start of `result`,
+    /// returning its length, which is zero after the last one. Only the first
+    /// chunk may be shorter than 19 digits.
+    #[inline]
+    pub(crate) const unsafe fn next_integer(&mut self, result: *mut u8) -> usize {
+        if self.integer_chunks == 0 {
+            return 0;
+        }
+        self.integer_chunks -= 1;
+        let chunk = self.integer[self.integer_chunks];
+        // Only the highest chunk leaves less than 19 digits for the others.
+        let length = if (self.integer_chunks + 1) * CHUNK_DIGITS >= self.integer_length {
+            self.integer_length - self.integer_chunks * CHUNK_DIGITS
+        } else {
+            CHUNK_DIGITS
+        };
+        write_padded(chunk, length, result);
+        length
+    }
+
+    /// Writes the next chunk of the fraction to the start of `result`,
+    /// returning its length, which is zero after the last one. Only the last
+    /// chunk may be shorter than 19 digits.
+    #[inline]
+    pub(crate) const unsafe fn next_fraction(&mut self, result: *mut u8) -> usize {
+        if self.fraction_digits == 0 {
+            return 0;
+        }
+        let length = if self.fraction_digits < CHUNK_DIGITS {
+            self.fraction_digits
+        } else {
+            CHUNK_DIGITS
+        };
+        self.fraction_digits -= length;
+        self.fraction = mul_small(self.fraction, 10u64.pow(length as u32));
+
+        // The chunk is below 10^19, so it fits in the 64 bits above the binary
+        // point, which are cleared.
+        let limb = (self.fraction_bits / 64) as usize;
+        let bits = self.fraction_bits % 64;
+        let mut chunk = self.fraction[limb] >> bits;
+        if bits != 0 && limb + 1 < LIMBS {
+            chunk |= self.fraction[limb + 1] << (64 - bits);
+        }
+        self.fraction[limb] &= (1u64 << bits).wrapping_sub(1);
+        let mut k = limb + 1;
+        while k < LIMBS {
+            self.fraction[k] = 0;
+            k += 1;
+        }
+
+        write_padded(chunk, length, result);
+        length
+    }
+}
+
+#[inline]
+pub(crate) const fn is_zero(x: &Big) -> bool {
+    let mut k = 0;
+    while k < LIMBS {
+        if x[k] != 0 {
+            return false;
+        }
+        k += 1;
+    }
+    true
+}
+
+// Writes `length` digits of `chunk`, with leading zeros.
+#[inline]
+const unsafe fn write_padded(chunk: u64, length: usize, result: *mut u8) {
+    let mut digits = [b'0'; CHUNK_DIGITS];
+    // `write_mantissa_long` takes up to 17 digits, so the last 8 are written
+    // first.
+    let end = digits.as_mut_ptr().add(CHUNK_DIGITS);
+    write_mantissa((chunk % 100_000_000) as u32, end);
+    write_mantissa_long(chunk / 100_000_000, end.sub(8));
+    ptr::copy_nonoverlapping(digits.as_ptr().add(CHUNK_DIGITS - length), result, length);
+}
@@ -0,0 +1,121 @@
+// Shortest digits of an f64 that round-trip when narrowed to f32.
+//
+// The digits are chosen from the rounding interval of `x as f32`, exactly as
+// `f2s::f2d` does, so they have the same length as the output of `f2d` and
+// parse back to the same f32. Where several decimals of that length fall in
+// the interval, `f2d` picks the one closest to the f32 value, while `d2f`
+// picks the one closest to the original f64 value. Since these are at most a
+// few units in the last digit apart, the candidates are compared exactly with
+// 256-bit integers instead of rerunning the interval logic.
+
+use crate::d2fixed::{mul_pow5, shl};
+use crate::d2s::{DOUBLE_BIAS, DOUBLE_MANTISSA_BITS};
+use crate::f2s::{f2d, FloatingDecimal32, FLOAT_BIAS, FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+
+// Returns -1, 0 or 1 as d * 10^e10 is less than, equal to or greater than
+// m * 2^e2. Both sides must be within a factor of about 2^64 of each other.
+#[inline]
+const fn compare_decimal_binary(d: u64, e10: i32, m: u64, e2: i32) -> i32 {
+    // d * 5^e10 * 2^e10 <=> m * 2^e2
+    // Little-endian limbs of unsigned 256-bit integers.
+    let mut lhs = [d, 0, 0, 0];
+    let mut rhs = [m, 0, 0, 0];
+    if e10 >= 0 {
+        lhs = mul_pow5(lhs, e10 as u32);
+    } else {
+        rhs = mul_pow5(rhs, -e10 as u32);
+    }
+    if e10 >= e2 {
+        lhs = shl(lhs, (e10 - e2) as u32);
+    } else {
+        rhs = shl(rhs, (e2 - e10) as u32);
+    }
+
+    let mut k = 4;
+    while k > 0 {
+        k -= 1;
+        if lhs[k] != rhs[k] {
+            return if lhs[k] < rhs[k] { -1 } else { 1 };
+        }
+    }
+    0
+}
+
+// Same as `f2s::f2d` for the f32 nearest to `d`, but choosing the candidate
+// closest to `d` itself. Requires `d` to be positive and `d as f32` to be
+// finite and nonzero.
+#[inline]
+pub const fn d2f(d: f64) -> FloatingDecimal32 {
+    let f = d as f32;
+    let bits = f.to_bits();
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+    let mut v = f2d(ieee_mantissa, ieee_exponent);
+
+    // The rounding interval of `f`, in units of 2^e2 as in `f2d`.
+    let (e2, m2) = if ieee_exponent == 0 {
+        (
+            1 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32 - 2,
+            ieee_mantissa,
+        )
+    } else {
+        (
+            ieee_exponent as i32 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32 - 2,
+            (1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    let accept_bounds = (m2 & 1) == 0;
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    let mp = 4 * m2 as u64 + 2;
+    let mm = 4 * m2 as u64 - 1 - mm_shift;
+
+    // Twice `d` as m * 2^e2, to compare it with midpoints between candidates,
+    // i.e. (2 * k + 1) * 10^e10.
+    let bits = d.to_bits();
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> DOUBLE_MANTISSA_BITS) as i32;
+    let (e2_d, m2_d) = if ieee_exponent == 0 {
+        (
+            1 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32 + 1,
+            ieee_mantissa,
+        )
+    } else {
+        (
+            ieee_exponent - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32 + 1,
+            (1u64 << DOUBLE_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+
+    // Move up while the next candidate is in the interval and closer to `d`.
+    loop {
+        let next = v.mantissa as u64 + 1;
+        let in_interval = match compare_decimal_binary(next, v.exponent, mp, e2) {
+            -1 => true,
+            0 => accept_bounds,
+            _ => false,
+        };
+        if !in_interval
+            || compare_decimal_binary(2 * v.mantissa as u64 + 1, v.exponent, m2_d, e2_d) >= 0
+        {
+            break;
+        }
+        v.mantissa += 1;
+    }
+    // Move down while the previous candidate is in the interval and closer to
+    // `d`.
+    loop {
+        let previous = v.mantissa as u64 - 1;
+        let in_interval = match compare_decimal_binary(previous, v.exponent, mm, e2) {
+            1 => true,
+            0 => accept_bounds,
+            _ => false,
+        };
+        if !in_interval
+            || compare_decimal_binary(2 * v.mantissa as u64 - 1, v.exponent, m2_d, e2_d) <= 0
+        {
+            break;
+        }
+        v.mantissa -= 1;
+    }
+    v
+}
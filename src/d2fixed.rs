@@ -0,0 +1,441 @@
+// Correctly rounded digits of the exact binary value, for output with a
+// fixed precision instead of the shortest one.
+//
+// Ryū only ever produces the shortest digits, whose rounding is with respect
+// to the interval of values that parse back to the same float. Rounding to a
+// given number of digits has to look at the exact value instead: 0.125 is
+// "0.12" to two digits and "0.13" is wrong, even though "0.125" is its
+// shortest representation. The quotient and remainder of the exact value by
+// a power of ten are computed with a small fixed-size bignum, which is large
+// enough for any finite f64.
+//
+// Rounding the shortest digits themselves, the way a person rounds a printed
+// number, only needs integer arithmetic on the decimal mantissa.
+
+use crate::common::decimal_length9;
+use crate::d2s::{d2d, decimal_length17, FloatingDecimal64, DOUBLE_BIAS, DOUBLE_MANTISSA_BITS};
+use crate::f2s::{f2d, FLOAT_BIAS, FLOAT_MANTISSA_BITS};
+use crate::format::RoundingMode;
+
+/// The largest number of significant digits for an `f64`.
+pub const MAX_SIGNIFICANT_DIGITS_F64: u32 = 17;
+/// The largest number of significant digits for an `f32`.
+pub const MAX_SIGNIFICANT_DIGITS_F32: u32 = 9;
+
+// Little-endian limbs. The numerator is at most the f64 mantissa times
+// 2^971 times two, or the f64 mantissa times 5^340 for 17 digits of the
+// smallest subnormal, both well below 2^1152. The bounds of a tolerance
+// interval have about 110 more bits, which still fit, and so does a fraction
+// of 1074 bits times 10^19 for the exact digits.
+pub(crate) const LIMBS: usize = 18;
+pub(crate) type Big = [u64; LIMBS];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+// How the part of the exact value that was cut off compares to half a unit
+// in the last kept digit.
+pub(crate) enum Remainder {
+    Zero,
+    BelowHalf,
+    Half,
+    AboveHalf,
+}
+
+// The multiplications and left shifts take any number of limbs, so that
+// `d2f` can use them on four.
+#[inline]
+pub(crate) const fn mul_small<const N: usize>(mut x: [u64; N], factor: u64) -> [u64; N] {
+    let mut carry = 0u128;
+    let mut k = 0;
+    while k < N {
+        let t = x[k] as u128 * factor as u128 + carry;
+        x[k] = t as u64;
+        carry = t >> 64;
+        k += 1;
+    }
+    debug_assert!(carry == 0);
+    x
+}
+
+// Divides in place, returning the remainder.
+#[inline]
+pub(crate) const fn div_small(x: &mut Big, divisor: u64) -> u64 {
+    let mut remainder = 0u128;
+    let mut k = LIMBS;
+    while k > 0 {
+        k -= 1;
+        let t = (remainder << 64) | x[k] as u128;
+        x[k] = (t / divisor as u128) as u64;
+        remainder = t % divisor as u128;
+    }
+    remainder as u64
+}
+
+#[inline]
+pub(crate) const fn mul_pow5<const N: usize>(mut x: [u64; N], mut p: u32) -> [u64; N] {
+    // 5^27 is the largest power of 5 that fits in 64 bits.
+    while p >= 27 {
+        x = mul_small(x, 7450580596923828125);
+        p -= 27;
+    }
+    mul_small(x, 5u64.pow(p))
+}
+
+// Divides in place, returning whether the remainder is nonzero.
+#[inline]
+const fn div_pow5(x: &mut Big, mut p: u32) -> bool {
+    let mut inexact = false;
+    while p >= 27 {
+        inexact |= div_small(x, 7450580596923828125) != 0;
+        p -= 27;
+    }
+    inexact | (div_small(x, 5u64.pow(p)) != 0)
+}
+
+#[inline]
+pub(crate) const fn shl<const N: usize>(x: [u64; N], shift: u32) -> [u64; N] {
+    let limbs = (shift / 64) as usize;
+    let bits = shift % 64;
+    debug_assert!(limbs < N);
+    let mut result = [0u64; N];
+    let mut k = limbs;
+    while k < N {
+        result[k] = x[k - limbs] << bits;
+        if bits != 0 && k > limbs {
+            result[k] |= x[k - limbs - 1] >> (64 - bits);
+        }
+        k += 1;
+    }
+    result
+}
+
+#[inline]
+pub(crate) const fn add(mut x: Big, y: &Big) -> Big {
+    let mut carry = false;
+    let mut k = 0;
+    while k < LIMBS {
+        let (sum, c1) = x[k].overflowing_add(y[k]);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        x[k] = sum;
+        carry = c1 | c2;
+        k += 1;
+    }
+    debug_assert!(!carry);
+    x
+}
+
+// The difference of x and y, which must not be larger than x.
+#[inline]
+pub(crate) const fn sub(mut x: Big, y: &Big) -> Big {
+    let mut borrow = false;
+    let mut k = 0;
+    while k < LIMBS {
+        let (difference, b1) = x[k].overflowing_sub(y[k]);
+        let (difference, b2) = difference.overflowing_sub(borrow as u64);
+        x[k] = difference;
+        borrow = b1 | b2;
+        k += 1;
+    }
+    debug_assert!(!borrow);
+    x
+}
+
+// Shifts right in place, returning whether any nonzero bit was shifted out.
+#[inline]
+pub(crate) const fn shr(x: &mut Big, shift: u32) -> bool {
+    let limbs = (shift / 64) as usize;
+    let bits = shift % 64;
+    let mut inexact = false;
+    let mut k = 0;
+    while k < LIMBS {
+        if k < limbs {
+            inexact |= x[k] != 0;
+        } else if k == limbs && bits != 0 {
+            inexact |= x[k] << (64 - bits) != 0;
+        }
+        x[k] = if k + limbs < LIMBS {
+            let mut limb = x[k + limbs] >> bits;
+            if bits != 0 && k + limbs + 1 < LIMBS {
+                limb |= x[k + limbs + 1] << (64 - bits);
+            }
+            limb
+        } else {
+            0
+        };
+        k += 1;
+    }
+    inexact
+}
+
+// Returns floor(m2 * 2^e2 / 10^k) and how the remainder compares to half of
+// 10^k. The quotient must be less than 2^127.
+#[inline]
+pub(crate) const fn div_pow10(m2: u64, e2: i32, k: i32) -> (u128, Remainder) {
+    let mut x = [0u64; LIMBS];
+    x[0] = m2;
+    div_big_pow10(x, e2, k)
+}
+
+// Same as `div_pow10`, for a numerator that does not fit in 64 bits. It must
+// be small enough to be multiplied by 5^-k, or shifted left by e2 - k + 1.
+#[inline]
+pub(crate) const fn div_big_pow10(mut x: Big, e2: i32, k: i32) -> (u128, Remainder) {
+    // Twice the quotient, whose lowest bit tells whether the remainder is at
+    // least a half: 2 * x * 2^e2 / 10^k = x * 2^p2 * 5^p5.
+    let p2 = e2 - k + 1;
+    let p5 = -k;
+
+    let mut inexact = false;
+    if p5 > 0 {
+        x = mul_pow5(x, p5 as u32);
+    }
+    if p2 > 0 {
+        x = shl(x, p2 as u32);
+    } else if p2 < 0 {
+        if -p2 as u32 >= 64 * LIMBS as u32 {
+            let mut k = 0;
+            while k < LIMBS {
+                inexact |= x[k] != 0;
+                k += 1;
+            }
+            x = [0u64; LIMBS];
+        } else {
+            inexact |= shr(&mut x, -p2 as u32);
+        }
+    }
+    if p5 < 0 {
+        inexact |= div_pow5(&mut x, -p5 as u32);
+    }
+
+    let mut k = 2;
+    while k < LIMBS {
+        debug_assert!(x[k] == 0);
+        k += 1;
+    }
+    let remainder = match (x[0] & 1 != 0, inexact) {
+        (false, false) => Remainder::Zero,
+        (false, true) => Remainder::BelowHalf,
+        (true, false) => Remainder::Half,
+        (true, true) => Remainder::AboveHalf,
+    };
+    ((x[0] as u128 | (x[1] as u128) << 64) >> 1, remainder)
+}
+
+// Returns whether the magnitude of a number with the given sign rounds up
+// from the candidate below it, given whether that candidate is odd and how
+// the part that was cut off compares to half a unit.
+#[inline]
+pub(crate) const fn rounds_up(
+    odd: bool,
+    remainder: Remainder,
+    negative: bool,
+    mode: RoundingMode,
+) -> bool {
+    match (remainder, mode) {
+        (Remainder::Zero, _) => false,
+        (_, RoundingMode::Floor) => negative,
+        (_, RoundingMode::Ceiling) => !negative,
+        (_, RoundingMode::TowardZero) => false,
+        (_, RoundingMode::AwayFromZero) => true,
+        (Remainder::BelowHalf, _) => false,
+        (Remainder::AboveHalf, _) => true,
+        (Remainder::Half, RoundingMode::HalfEven) => odd,
+        (Remainder::Half, RoundingMode::HalfUp) => !negative,
+        (Remainder::Half, RoundingMode::HalfAwayFromZero) => true,
+    }
+}
+
+// Rounds the decimal mantissa * 10^exponent to a multiple of 10^target,
+// returning the mantissa for 10^target. Decimals that are already a multiple
+// are returned unchanged.
+#[inline]
+pub(crate) const fn round_decimal(
+    mantissa: u64,
+    exponent: i32,
+    target: i32,
+    negative: bool,
+    mode: RoundingMode,
+) -> (u64, i32) {
+    if exponent >= target {
+        return (mantissa, exponent);
+    }
+    let shift = (target - exponent) as u32;
+    let (q, remainder) = if shift > 19 {
+        // Any u64 is below half of 10^20.
+        (
+            0,
+            if mantissa == 0 {
+                Remainder::Zero
+            } else {
+                Remainder::BelowHalf
+            },
+        )
+    } else {
+        let divisor = 10u64.pow(shift);
+        let r = mantissa % divisor;
+        let half = divisor / 2;
+        let remainder = if r == 0 {
+            Remainder::Zero
+        } else if r < half {
+            Remainder::BelowHalf
+        } else if r == half {
+            Remainder::Half
+        } else {
+            Remainder::AboveHalf
+        };
+        (mantissa / divisor, remainder)
+    };
+    (
+        q + rounds_up(q & 1 != 0, remainder, negative, mode) as u64,
+        target,
+    )
+}
+
+// The value of a finite f64 as m2 * 2^e2.
+#[inline]
+pub(crate) const fn f64_parts(ieee_mantissa: u64, ieee_exponent: u32) -> (u64, i32) {
+    if ieee_exponent == 0 {
+        (ieee_mantissa, 1 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32)
+    } else {
+        (
+            (1u64 << DOUBLE_MANTISSA_BITS) | ieee_mantissa,
+            ieee_exponent as i32 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32,
+        )
+    }
+}
+
+// The value of a finite f32 as m2 * 2^e2.
+#[inline]
+pub(crate) const fn f32_parts(ieee_mantissa: u32, ieee_exponent: u32) -> (u64, i32) {
+    if ieee_exponent == 0 {
+        (
+            ieee_mantissa as u64,
+            1 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32,
+        )
+    } else {
+        (
+            ((1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa) as u64,
+            ieee_exponent as i32 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32,
+        )
+    }
+}
+
+// Rounds the nonzero value m2 * 2^e2 to `digits` significant digits, given
+// the number of digits `kk` of its integer part as an estimate that may be
+// one too large or too small, e.g. from its shortest representation. The
+// returned mantissa has exactly `digits` digits, including trailing zeros.
+#[inline]
+const fn round_significant(
+    m2: u64,
+    e2: i32,
+    kk: i32,
+    digits: u32,
+    negative: bool,
+    mode: RoundingMode,
+) -> (u64, i32) {
+    debug_assert!(m2 != 0);
+    debug_assert!(digits >= 1 && digits <= MAX_SIGNIFICANT_DIGITS_F64);
+    let min = 10u128.pow(digits - 1);
+    let max = 10u128.pow(digits);
+
+    let mut k = kk - digits as i32;
+    let (mut q, mut remainder) = div_pow10(m2, e2, k);
+    if q >= max {
+        k += 1;
+        (q, remainder) = div_pow10(m2, e2, k);
+    } else if q < min {
+        k -= 1;
+        (q, remainder) = div_pow10(m2, e2, k);
+    }
+    debug_assert!(min <= q && q < max);
+
+    q += rounds_up(q & 1 != 0, remainder, negative, mode) as u128;
+    if q == max {
+        // 9.995 -> 10.0
+        q = min;
+        k += 1;
+    }
+    (q as u64, k)
+}
+
+// The f64 `ieee_mantissa * 2^ieee_exponent` rounded to `digits` significant
+// digits. The value must be nonzero.
+#[inline]
+pub const fn d2fixed_significant(
+    ieee_mantissa: u64,
+    ieee_exponent: u32,
+    digits: u32,
+    negative: bool,
+    mode: RoundingMode,
+) -> FloatingDecimal64 {
+    let (m2, e2) = f64_parts(ieee_mantissa, ieee_exponent);
+    let v = d2d(ieee_mantissa, ieee_exponent);
+    let kk = decimal_length17(v.mantissa) as i32 + v.exponent;
+    let (mantissa, exponent) = round_significant(m2, e2, kk, digits, negative, mode);
+    FloatingDecimal64 { mantissa, exponent }
+}
+
+// Same as `d2fixed_significant`, for an f32. The mantissa is returned as a
+// u64, since it may have more digits than an f32 ever needs.
+#[inline]
+pub const fn f2fixed_significant(
+    ieee_mantissa: u32,
+    ieee_exponent: u32,
+    digits: u32,
+    negative: bool,
+    mode: RoundingMode,
+) -> FloatingDecimal64 {
+    let (m2, e2) = f32_parts(ieee_mantissa, ieee_exponent);
+    let v = f2d(ieee_mantissa, ieee_exponent);
+    let kk = decimal_length9(v.mantissa) as i32 + v.exponent;
+    let (mantissa, exponent) = round_significant(m2, e2, kk, digits, negative, mode);
+    FloatingDecimal64 { mantissa, exponent }
+}
+
+// The f64 `ieee_mantissa * 2^ieee_exponent` rounded to a multiple of
+// 10^-decimal_places, returned as the mantissa for 10^-decimal_places, which
+// must be less than 2^127. It is for values below 10^16 with up to 14
+// decimal places, or below 2^52 with up to 20.
+#[inline]
+pub const fn d2fixed(
+    ieee_mantissa: u64,
+    ieee_exponent: u32,
+    decimal_places: u32,
+    negative: bool,
+    mode: RoundingMode,
+) -> u128 {
+    let (m2, e2) = f64_parts(ieee_mantissa, ieee_exponent);
+    let (q, remainder) = div_pow10(m2, e2, -(decimal_places as i32));
+    q + rounds_up(q & 1 != 0, remainder, negative, mode) as u128
+}
+
+// Same as `d2fixed`, for an f32.
+#[inline]
+pub const fn f2fixed(
+    ieee_mantissa: u32,
+    ieee_exponent: u32,
+    decimal_places: u32,
+    negative: bool,
+    mode: RoundingMode,
+) -> u128 {
+    let (m2, e2) = f32_parts(ieee_mantissa, ieee_exponent);
+    let (q, remainder) = div_pow10(m2, e2, -(decimal_places as i32));
+    q + rounds_up(q & 1 != 0, remainder, negative, mode) as u128
+}
+
+// Removes the trailing zeros of a nonzero mantissa.
+#[inline]
+pub const fn strip_trailing_zeros(mut mantissa: u64, mut exponent: i32) -> (u64, i32) {
+    debug_assert!(mantissa != 0);
+    while mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    (mantissa, exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+
+    include!("../unittests/d2fixed_test.rs");
+}
@@ -0,0 +1,182 @@
+// Shortest digits of a float in any radix from 2 to 36.
+//
+// This is the search of `d2d_within` in `d2range` for a radix other than ten.
+// The rounding interval of the float is divided exactly by the largest power
+// of the radix that leaves it at least one unit wide, or the next smaller one
+// if no integer is strictly inside. The quotients then have at most 63 bits.
+// Digits are removed while a candidate with one digit less is still strictly
+// inside the interval, and of the last candidates, the one closest to the
+// float is chosen, as Ryū does in base ten.
+
+use crate::d2fixed::{div_small, mul_small, rounds_up, shl, shr, Remainder, LIMBS};
+use crate::d2range::{above, below};
+use crate::format::RoundingMode;
+
+// floor(log2(radix) * 2^32) for the radices 2 to 36, with which the floor of
+// log_radix(2^e) is exact for every |e| below 1200.
+const LOG2_RADIX: [u64; 35] = [
+    4294967296,
+    6807362105,
+    8589934592,
+    9972605231,
+    11102329401,
+    12057497578,
+    12884901888,
+    13614724211,
+    14267572527,
+    14858145664,
+    15397296697,
+    15893267570,
+    16352464874,
+    16779967337,
+    17179869184,
+    17555519226,
+    17909691507,
+    18244709746,
+    18562539823,
+    18864859684,
+    19153112960,
+    19428550662,
+    19692263993,
+    19945210462,
+    20188234866,
+    20422086317,
+    20647432170,
+    20864869498,
+    21074934633,
+    21278111131,
+    21474836480,
+    21665507770,
+    21850486522,
+    22030102809,
+    22204658803,
+];
+
+/// A float as `mantissa * radix^exponent`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FloatingRadix {
+    pub mantissa: u64,
+    pub exponent: i32,
+}
+
+// floor(log_radix(2^e)).
+#[inline]
+pub(crate) const fn floor_log_radix_pow2(e: i32, radix: u32) -> i32 {
+    ((e as i64) << 32).div_euclid(LOG2_RADIX[radix as usize - 2] as i64) as i32
+}
+
+// Returns floor(x * 2^e2 / radix^k) and how the remainder compares to half of
+// radix^k, as `div_pow10` does for radix 10. The quotient must be less than
+// 2^127.
+#[inline]
+const fn div_pow_radix(x: u64, e2: i32, radix: u32, k: i32) -> (u128, Remainder) {
+    // The largest power of the radix that fits in 64 bits, so that most
+    // steps multiply or divide by it.
+    let mut chunk = radix as u64;
+    let mut chunk_exponent = 1;
+    while chunk <= u64::MAX / radix as u64 {
+        chunk *= radix as u64;
+        chunk_exponent += 1;
+    }
+
+    // Twice the quotient, whose lowest bit tells whether the remainder is at
+    // least a half: 2 * x * 2^e2 / radix^k.
+    let mut n = [0u64; LIMBS];
+    n[0] = x;
+    let mut p = -k;
+    while p >= chunk_exponent {
+        n = mul_small(n, chunk);
+        p -= chunk_exponent;
+    }
+    while p > 0 {
+        n = mul_small(n, radix as u64);
+        p -= 1;
+    }
+
+    let mut inexact = false;
+    let p2 = e2 + 1;
+    if p2 > 0 {
+        n = shl(n, p2 as u32);
+    } else if p2 < 0 {
+        inexact |= shr(&mut n, -p2 as u32);
+    }
+
+    let mut p = k;
+    while p >= chunk_exponent {
+        inexact |= div_small(&mut n, chunk) != 0;
+        p -= chunk_exponent;
+    }
+    while p > 0 {
+        inexact |= div_small(&mut n, radix as u64) != 0;
+        p -= 1;
+    }
+
+    let mut i = 2;
+    while i < LIMBS {
+        debug_assert!(n[i] == 0);
+        i += 1;
+    }
+    let remainder = match (n[0] & 1 != 0, inexact) {
+        (false, false) => Remainder::Zero,
+        (false, true) => Remainder::BelowHalf,
+        (true, false) => Remainder::Half,
+        (true, true) => Remainder::AboveHalf,
+    };
+    ((n[0] as u128 | (n[1] as u128) << 64) >> 1, remainder)
+}
+
+/// The shortest digits in `radix` of the positive float m2 * 2^e2 that parse
+/// back to it, where `mm_shift` is 1 unless the float is a power of two whose
+/// predecessor is half as far below, as in `d2d`. Of the shortest ones, the
+/// digits closest to the float are returned.
+#[inline]
+pub const fn d2radix(m2: u64, e2: i32, mm_shift: u64, radix: u32) -> FloatingRadix {
+    debug_assert!(2 <= radix && radix <= 36);
+    let accept_bounds = m2 & 1 == 0;
+    let lower = 4 * m2 - 1 - mm_shift;
+    let upper = 4 * m2 + 2;
+
+    // The interval is at least 2^(e2 - 1) wide.
+    let mut k = floor_log_radix_pow2(e2 - 1, radix);
+    let (mut vm, mut vp) = loop {
+        let (q, remainder) = div_pow_radix(lower, e2 - 2, radix, k);
+        let vm = below(q, remainder, accept_bounds);
+        let (q, remainder) = div_pow_radix(upper, e2 - 2, radix, k);
+        let vp = above(q, remainder, accept_bounds);
+        if vm + 2 <= vp {
+            break (vm, vp);
+        }
+        k -= 1;
+    };
+
+    let radix64 = radix as u64;
+    loop {
+        let vm_div = vm / radix64;
+        let vp_div = vp.div_ceil(radix64);
+        if vm_div + 2 > vp_div {
+            break;
+        }
+        vm = vm_div;
+        vp = vp_div;
+        k += 1;
+    }
+
+    // The candidate closest to the float, which is between the bounds.
+    let (q, remainder) = div_pow_radix(m2, e2, radix, k);
+    let mut output =
+        q as u64 + rounds_up(q & 1 != 0, remainder, false, RoundingMode::HalfEven) as u64;
+    if output <= vm {
+        output = vm + 1;
+    } else if output >= vp {
+        output = vp - 1;
+    }
+    FloatingRadix {
+        mantissa: output,
+        exponent: k,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("../unittests/d2radix_test.rs");
+}
@@ -0,0 +1,204 @@
+// The shortest decimal inside an arbitrary interval of doubles.
+//
+// This is what Ryū's `d2d` does for the rounding interval of a single double,
+// for other intervals: one between two arbitrary doubles, or the rounding
+// interval widened by a tolerance. The bounds are divided exactly by the same
+// power of ten, chosen so that the quotient of the upper bound has 18 or 19
+// digits. In those units, the decimals in the interval are the integers
+// strictly between vm and vp, the integers just outside of it, and the same
+// digit removal loop as in `d2d` finds the shortest ones.
+
+use crate::d2fixed::{
+    add, div_big_pow10, div_pow10, f64_parts, mul_small, rounds_up, shl, sub, Remainder, LIMBS,
+};
+use crate::d2s::{d2d, decimal_length17, FloatingDecimal64, DOUBLE_MANTISSA_BITS};
+use crate::d2s_intrinsics::div10;
+use crate::format::{RoundingMode, Tolerance};
+
+// The largest integer below the bound with the quotient q and the given
+// remainder, or the largest one that is not above it if it is excluded.
+#[inline]
+pub(crate) const fn below(q: u128, remainder: Remainder, inclusive: bool) -> u64 {
+    let exact = matches!(remainder, Remainder::Zero);
+    q as u64 + (inclusive && !exact) as u64 - inclusive as u64
+}
+
+// The smallest integer above the bound with the quotient q and the given
+// remainder, or the smallest one that is not below it if it is excluded.
+#[inline]
+pub(crate) const fn above(q: u128, remainder: Remainder, inclusive: bool) -> u64 {
+    let exact = matches!(remainder, Remainder::Zero);
+    q as u64 + (inclusive || !exact) as u64
+}
+
+// Removes digits while there is an integer strictly between vm and vp that
+// ends in zero, returning the new bounds and the number of removed digits.
+#[inline]
+const fn remove_digits(mut vm: u64, mut vp: u64) -> (u64, u64, i32) {
+    debug_assert!(vm + 2 <= vp);
+    let mut removed = 0i32;
+    loop {
+        let vm_div10 = div10(vm);
+        let vp_div10 = div10(vp + 9);
+        if vm_div10 + 2 > vp_div10 {
+            break;
+        }
+        vm = vm_div10;
+        vp = vp_div10;
+        removed += 1;
+    }
+    (vm, vp, removed)
+}
+
+/// The shortest decimal between two doubles, which must be positive apart
+/// from a lower bound of zero when the interval is open. Of the shortest
+/// decimals with the largest exponent, the one in the middle is returned, and
+/// the even one of the two in the middle if there are an even number of them.
+/// Returns `None` if there is none with at most 17 digits.
+#[inline]
+pub const fn d2d_in_range(
+    lo_mantissa: u64,
+    lo_exponent: u32,
+    hi_mantissa: u64,
+    hi_exponent: u32,
+    inclusive: bool,
+) -> Option<FloatingDecimal64> {
+    // The shortest representation of hi has as many integer digits as hi,
+    // or one more, when it rounds up to a power of ten.
+    let v = d2d(hi_mantissa, hi_exponent);
+    let k = v.exponent + decimal_length17(v.mantissa) as i32 - 18;
+
+    let (m2, e2) = f64_parts(lo_mantissa, lo_exponent);
+    let (q, remainder) = div_pow10(m2, e2, k);
+    let vm = below(q, remainder, inclusive);
+    let (m2, e2) = f64_parts(hi_mantissa, hi_exponent);
+    let (q, remainder) = div_pow10(m2, e2, k);
+    let vp = above(q, remainder, inclusive);
+
+    if vm + 2 > vp {
+        return None;
+    }
+    let (vm, vp, removed) = remove_digits(vm, vp);
+
+    let sum = vm + vp;
+    let mut output = sum / 2;
+    if sum % 2 != 0 && output % 2 != 0 {
+        output += 1;
+    }
+    if output >= 100000000000000000 {
+        return None;
+    }
+    Some(FloatingDecimal64 {
+        exponent: k + removed,
+        mantissa: output,
+    })
+}
+
+/// The shortest decimal that is within the tolerance of a positive double,
+/// or parses back to it. Of the shortest decimals, the one closest to the
+/// double is returned. Returns `None` if zero is within the tolerance.
+#[inline]
+pub const fn d2d_within(
+    ieee_mantissa: u64,
+    ieee_exponent: u32,
+    tolerance: Tolerance,
+) -> Option<FloatingDecimal64> {
+    let (m2, e2) = f64_parts(ieee_mantissa, ieee_exponent);
+
+    // The upper bound is less than twice the value, so its quotient has at
+    // most 19 digits.
+    let v = d2d(ieee_mantissa, ieee_exponent);
+    let k = v.exponent + decimal_length17(v.mantissa) as i32 - 18;
+
+    // The rounding interval of the double, as in d2d.
+    let accept_bounds = m2 & 1 == 0;
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    let (q, remainder) = div_pow10(4 * m2 - 1 - mm_shift, e2 - 2, k);
+    let mut vm = below(q, remainder, accept_bounds);
+    let (q, remainder) = div_pow10(4 * m2 + 2, e2 - 2, k);
+    let mut vp = above(q, remainder, accept_bounds);
+
+    // Widened to the tolerance interval, [x - t, x + t] = [lo, hi] * 2^e,
+    // unless it is smaller than the rounding interval, which is at least
+    // 2^(e2 - 2) on each side.
+    let mut lo = [0u64; LIMBS];
+    let mut hi = [0u64; LIMBS];
+    let mut e = 0;
+    let mut widen = false;
+    match tolerance {
+        Tolerance::Relative(r) => {
+            if r >= 1.0 {
+                return None;
+            }
+            let (mr, er) = f64_parts(
+                r.to_bits() & ((1u64 << DOUBLE_MANTISSA_BITS) - 1),
+                (r.to_bits() >> DOUBLE_MANTISSA_BITS) as u32,
+            );
+            // x * r >= 2^(e2 - 2) needs r >= 2^-55, and then -er <= 107.
+            if mr != 0 && er >= -107 {
+                // x * (1 +- r) = x * (2^s +- mr) / 2^s
+                let s = -er;
+                let mut one = [0u64; LIMBS];
+                one[0] = 1;
+                one = shl(one, s as u32);
+                let mut r = [0u64; LIMBS];
+                r[0] = mr;
+                lo = mul_small(sub(one, &r), m2);
+                hi = mul_small(add(one, &r), m2);
+                e = e2 - s;
+                widen = true;
+            }
+        }
+        Tolerance::Absolute(t) => {
+            if t >= f64::from_bits((ieee_exponent as u64) << DOUBLE_MANTISSA_BITS | ieee_mantissa) {
+                return None;
+            }
+            let (mt, et) = f64_parts(
+                t.to_bits() & ((1u64 << DOUBLE_MANTISSA_BITS) - 1),
+                (t.to_bits() >> DOUBLE_MANTISSA_BITS) as u32,
+            );
+            // t < 2^(et + 53), and since t < x, et - e2 < 53.
+            if mt != 0 && et + 53 > e2 - 2 {
+                let mut x = [0u64; LIMBS];
+                x[0] = m2;
+                let mut t = [0u64; LIMBS];
+                t[0] = mt;
+                e = if e2 < et { e2 } else { et };
+                x = shl(x, (e2 - e) as u32);
+                t = shl(t, (et - e) as u32);
+                lo = sub(x, &t);
+                hi = add(x, &t);
+                widen = true;
+            }
+        }
+    }
+    if widen {
+        let (q, remainder) = div_big_pow10(lo, e, k);
+        let below = below(q, remainder, true);
+        if below < vm {
+            vm = below;
+        }
+        let (q, remainder) = div_big_pow10(hi, e, k);
+        let above = above(q, remainder, true);
+        if above > vp {
+            vp = above;
+        }
+    }
+
+    let (vm, vp, removed) = remove_digits(vm, vp);
+
+    // The candidate closest to the double, which is between the bounds.
+    let (q, remainder) = div_pow10(m2, e2, k + removed);
+    let mut output =
+        q as u64 + rounds_up(q & 1 != 0, remainder, false, RoundingMode::HalfEven) as u64;
+    if output <= vm {
+        output = vm + 1;
+    } else if output >= vp {
+        output = vp - 1;
+    }
+    debug_assert!(output < 100000000000000000);
+    Some(FloatingDecimal64 {
+        exponent: k + removed,
+        mantissa: output,
+    })
+}
@@ -88,8 +88,29 @@ pub struct FloatingDecimal64 {
     pub exponent: i32,
 }
 
+// The interval of decimals that parse back to a double, scaled by 10^-e10 and
+// truncated: vm, vr and vp are the lower bound, the exact value and the upper
+// bound. The trailing zeros flags are set when vm or vr are exact.
+pub(crate) struct DecimalInterval64 {
+    pub vr: u64,
+    pub vp: u64,
+    pub vm: u64,
+    pub e10: i32,
+    pub vm_is_trailing_zeros: bool,
+    pub vr_is_trailing_zeros: bool,
+    pub accept_bounds: bool,
+}
+
+/// Steps 1 to 3 of [`d2d`]. With `exact_bounds`, the trailing zeros flags
+/// are set whenever vm or vr are exact, as the directed conversions need;
+/// without it they are computed as upstream does, which is enough for the
+/// shortest output.
 #[inline]
-pub const fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
+pub(crate) const fn d2d_interval(
+    ieee_mantissa: u64,
+    ieee_exponent: u32,
+    exact_bounds: bool,
+) -> DecimalInterval64 {
     let (e2, m2) = if ieee_exponent == 0 {
         (
             // We subtract 2 so that the bounds computation has 2 additional bits.
@@ -114,9 +135,9 @@ pub const fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
     // uint64_t mm = mv - 1 - mm_shift;
 
     // Step 3: Convert to a decimal power base using 128-bit arithmetic.
-    let mut vr: u64;
+    let vr: u64;
     let mut vp: u64;
-    let mut vm: u64;
+    let vm: u64;
     let mut vp_uninit: MaybeUninit<u64> = MaybeUninit::uninit();
     let mut vm_uninit: MaybeUninit<u64> = MaybeUninit::uninit();
     let e10: i32;
@@ -147,9 +168,29 @@ pub const fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
         };
         vp = unsafe { vp_uninit.assume_init() };
         vm = unsafe { vm_uninit.assume_init() };
-        if q <= 21 {
+        if exact_bounds && q == 0 {
+            // Upstream leaves the flags unset here, which is safe for the
+            // shortest output but not for the directed conversions. With
+            // q == 0 all of mp, mv and mm are integers.
+            vr_is_trailing_zeros = true;
+            if accept_bounds {
+                vm_is_trailing_zeros = true;
+            } else {
+                vp -= 1;
+            }
+        } else if q <= 21 || (exact_bounds && q == 22) {
             // This should use q <= 22, but I think 21 is also safe. Smaller values
             // may still be safe, but it's more difficult to reason about them.
+            //
+            // The directed conversions use q <= 22, which is exact. As
+            // m2 < 2^53 < 5^23, mv = 4 * m2 is never a multiple of 5^23. mp
+            // and mm are below 2^55, and their odd part is below
+            // 2^54 < 3 * 5^23, so only 5^23 itself could divide it. The
+            // odd part of mm is 2^54 - 1 when mm_shift == 0, which is not
+            // 5^23. Otherwise it is 2 * m2 - 1 for mm and 2 * m2 + 1 for mp,
+            // which makes mm a multiple of 5^23 only for an odd m2 and mp
+            // only for an even one, exactly where their flags are not used.
+            // From q == 24 on, 5^q is above all of them.
             // Only one of mp, mv, and mm can be a multiple of 5, if any.
             let mv_mod5 = (mv as u32).wrapping_sub(5u32.wrapping_mul(div5(mv) as u32));
             if mv_mod5 == 0 {
@@ -195,7 +236,7 @@ pub const fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
             vr_is_trailing_zeros = true;
             if accept_bounds {
                 // mm = mv - 1 - mm_shift, so it has 1 trailing 0 bit iff mm_shift == 1.
-                vm_is_trailing_zeros = mm_shift == 1;
+                vm_is_trailing_zeros = (exact_bounds && q == 0) || mm_shift == 1;
             } else {
                 // mp = mv + 2, so it always has at least one trailing 0 bit.
                 vp -= 1;
@@ -210,6 +251,29 @@ pub const fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
         }
     }
 
+    DecimalInterval64 {
+        vr,
+        vp,
+        vm,
+        e10,
+        vm_is_trailing_zeros,
+        vr_is_trailing_zeros,
+        accept_bounds,
+    }
+}
+
+#[inline]
+pub const fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
+    let DecimalInterval64 {
+        mut vr,
+        mut vp,
+        mut vm,
+        e10,
+        mut vm_is_trailing_zeros,
+        mut vr_is_trailing_zeros,
+        accept_bounds,
+    } = d2d_interval(ieee_mantissa, ieee_exponent, false);
+
     // Step 4: Find the shortest decimal representation in the interval of valid
     // representations.
     let mut removed = 0i32;
@@ -25,7 +25,7 @@ use crate::f2s_intrinsics::{
 
 pub const FLOAT_MANTISSA_BITS: u32 = 23;
 pub const FLOAT_EXPONENT_BITS: u32 = 8;
-const FLOAT_BIAS: i32 = 127;
+pub const FLOAT_BIAS: i32 = 127;
 pub use crate::f2s_intrinsics::{FLOAT_POW5_BITCOUNT, FLOAT_POW5_INV_BITCOUNT};
 
 // A floating decimal representing m * 10^e.
@@ -38,16 +38,34 @@ pub struct FloatingDecimal32 {
 
 #[inline]
 pub const fn f2d(ieee_mantissa: u32, ieee_exponent: u32) -> FloatingDecimal32 {
+    f2d_layout(
+        ieee_mantissa,
+        ieee_exponent,
+        FLOAT_MANTISSA_BITS,
+        FLOAT_BIAS,
+    )
+}
+
+// Same as `f2d`, for any binary layout with an implicit leading bit that is
+// no wider than binary32, i.e. `mantissa_bits <= 23` and a binary exponent
+// range within the one of `f32`.
+#[inline]
+pub const fn f2d_layout(
+    ieee_mantissa: u32,
+    ieee_exponent: u32,
+    mantissa_bits: u32,
+    bias: i32,
+) -> FloatingDecimal32 {
     let (e2, m2) = if ieee_exponent == 0 {
         (
             // We subtract 2 so that the bounds computation has 2 additional bits.
-            1 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32 - 2,
+            1 - bias - mantissa_bits as i32 - 2,
             ieee_mantissa,
         )
     } else {
         (
-            ieee_exponent as i32 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32 - 2,
-            (1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa,
+            ieee_exponent as i32 - bias - mantissa_bits as i32 - 2,
+            (1u32 << mantissa_bits) | ieee_mantissa,
         )
     };
     let even = (m2 & 1) == 0;
@@ -0,0 +1,85 @@
+// Shortest digits of a binary fixed-point number.
+//
+// A fixed-point number with f fractional bits is m * 2^-f, and its neighbours
+// are 2^-f away on either side, so the decimals that round back to it are
+// those less than 2^(-f-1) away, or exactly that far away when m is even. As in
+// `d2radix`, the bounds of this interval are divided exactly by the largest
+// power of ten that leaves an integer strictly between them, digits are
+// removed while one with a digit less is still inside, and the candidate
+// closest to the number is chosen. Unlike for a float, m can have 64 bits, so
+// the bounds are kept in 128 bits.
+
+use crate::d2fixed::{div_big_pow10, rounds_up, Big, Remainder, LIMBS};
+use crate::d2radix::floor_log_radix_pow2;
+use crate::format::RoundingMode;
+use crate::q2s::FloatingDecimal128;
+
+#[inline]
+const fn big(x: u128) -> Big {
+    let mut n = [0u64; LIMBS];
+    n[0] = x as u64;
+    n[1] = (x >> 64) as u64;
+    n
+}
+
+// `below` and `above` of `d2range`, for quotients of more than 64 bits.
+#[inline]
+const fn below(q: u128, remainder: Remainder, inclusive: bool) -> u128 {
+    let exact = matches!(remainder, Remainder::Zero);
+    q + (inclusive && !exact) as u128 - inclusive as u128
+}
+
+#[inline]
+const fn above(q: u128, remainder: Remainder, inclusive: bool) -> u128 {
+    let exact = matches!(remainder, Remainder::Zero);
+    q + (inclusive || !exact) as u128
+}
+
+/// The shortest decimal that rounds to the positive fixed-point number
+/// m * 2^-frac_bits, with ties to even. Of the shortest ones, the one closest
+/// to the number is returned.
+#[inline]
+pub const fn fixed2d(m: u64, frac_bits: u32) -> FloatingDecimal128 {
+    debug_assert!(m != 0);
+    let accept_bounds = m & 1 == 0;
+    let e2 = -(frac_bits as i32) - 1;
+    let lower = big(2 * m as u128 - 1);
+    let upper = big(2 * m as u128 + 1);
+
+    // The interval is 2^-frac_bits wide.
+    let mut k = floor_log_radix_pow2(-(frac_bits as i32), 10);
+    let (mut vm, mut vp) = loop {
+        let (q, remainder) = div_big_pow10(lower, e2, k);
+        let vm = below(q, remainder, accept_bounds);
+        let (q, remainder) = div_big_pow10(upper, e2, k);
+        let vp = above(q, remainder, accept_bounds);
+        if vm + 2 <= vp {
+            break (vm, vp);
+        }
+        k -= 1;
+    };
+
+    loop {
+        let vm_div10 = vm / 10;
+        let vp_div10 = vp.div_ceil(10);
+        if vm_div10 + 2 > vp_div10 {
+            break;
+        }
+        vm = vm_div10;
+        vp = vp_div10;
+        k += 1;
+    }
+
+    // The candidate closest to the number, which is between the bounds.
+    let (q, remainder) = div_big_pow10(big(m as u128), -(frac_bits as i32), k);
+    let mut output = q + rounds_up(q & 1 != 0, remainder, false, RoundingMode::HalfEven) as u128;
+    if output <= vm {
+        output = vm + 1;
+    } else if output >= vp {
+        output = vp - 1;
+    }
+    FloatingDecimal128 {
+        mantissa: output,
+        exponent: k,
+    }
+}
@@ -3,11 +3,69 @@
 use core::mem::MaybeUninit;
 use core::{fmt, ops, ptr, slice, str};
 
+use crate::bid::{
+    is_nan_decimal128, is_nan_decimal64, is_nonfinite_decimal128, is_nonfinite_decimal64,
+};
+use crate::d2exact::{ExactDigits, CHUNK_DIGITS};
+use crate::d2fixed::{MAX_SIGNIFICANT_DIGITS_F32, MAX_SIGNIFICANT_DIGITS_F64};
+use crate::minifloat::Layout;
 use crate::raw::{self, FormattedMeta};
 
 /// The length of the buffer used to store the formatted text.
 pub const BUFFER_LEN: usize = 32;
 
+/// The length of the buffer used to store the formatted text of an IEEE 754
+/// binary128 (`f128`) number, which needs up to 36 significant digits and a
+/// four-digit exponent.
+pub const BUFFER_LEN_F128: usize = 44;
+
+/// The largest number of decimal places that the `Formatter` methods taking a
+/// number of decimal places accept, so that their output fits in
+/// [`BUFFER_LEN`] bytes.
+pub const MAX_DECIMAL_PLACES: usize = 14;
+
+/// The length of the buffer used to store the formatted text of a number that
+/// shares its number of decimal places with other numbers, which can pad a
+/// 16-digit integer part with up to 21 decimal places.
+pub const BUFFER_LEN_COLUMN: usize = 39;
+
+/// The largest number of values that
+/// [`Formatter::shared_decimal_places_f64`] and its siblings accept. They
+/// compare every pair of values, so they are meant for the few dozen numbers
+/// of an axis or a column rather than for large data sets.
+pub const MAX_SHARED_VALUES: usize = 256;
+
+/// The largest number of fraction digits that [`FractionDigits`] accepts.
+///
+/// This is the range of `Intl.NumberFormat` before ECMAScript 2023, which
+/// raised it to 100.
+pub const MAX_INTL_FRACTION_DIGITS: usize = 20;
+
+/// The length of the buffer used to store the formatted text of a number with
+/// [`FractionDigits`], which is never in exponent form: the 309 integer
+/// digits of the largest `f64` with [`MAX_INTL_FRACTION_DIGITS`] zeros after
+/// the decimal point.
+pub const BUFFER_LEN_FRACTION_DIGITS: usize = 331;
+
+/// The length of a buffer that holds the exact decimal value of any `f64`,
+/// which has up to 1074 digits after the decimal point.
+pub const BUFFER_LEN_EXACT: usize = 1077;
+
+/// The length of a buffer that holds the exact decimal value of any `f32`,
+/// which has up to 149 digits after the decimal point.
+pub const BUFFER_LEN_EXACT_F32: usize = 152;
+
+/// The length of the buffer used to store the formatted text of a number in
+/// a radix other than 10, which has up to 1074 digits after the point in
+/// radix 2.
+pub const BUFFER_LEN_RADIX: usize = 1077;
+
+/// The largest number of bytes that [`Formatted::write_notation`] adds to the
+/// text of a [`Formatted<N>`], so that a buffer of `N + NOTATION_EXTRA_LEN`
+/// bytes always holds it: two for the minus sign, five for " × 10", and two
+/// for each of the minus sign and four digits of the exponent.
+pub const NOTATION_EXTRA_LEN: usize = 17;
+
 #[derive(Debug, Clone, Copy)]
 /// Safe API for formatting floating point numbers to text.
 ///
@@ -29,9 +87,9 @@ pub struct Formatter;
 /// The formatted text of a floating point number.
 ///
 /// This implements `AsRef<str>` and `ops::Deref<Target = str>`.
-pub struct Formatted {
+pub struct Formatted<const N: usize = BUFFER_LEN> {
     /// The inner bytes, maybe initialized.
-    bytes: [MaybeUninit<u8>; BUFFER_LEN],
+    bytes: [MaybeUninit<u8>; N],
 
     /// The type of the formatted number, which indicates whether it is an
     /// integer, has a decimal point, or is in exponent form.
@@ -41,7 +99,240 @@ pub struct Formatted {
     initialized: usize,
 }
 
-impl fmt::Debug for Formatted {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Whether precision-limited output keeps the zeros at the end of its
+/// digits, as in "1.20" rather than "1.2" for three significant digits.
+pub enum TrailingZeros {
+    /// Keep them, so that the output shows the requested precision.
+    Keep,
+
+    /// Strip them, so that the output is as short as possible.
+    Strip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How precision-limited output rounds a number that lies between two
+/// candidates.
+pub enum RoundingMode {
+    /// Round to the nearest candidate, and ties to the one whose last digit
+    /// is even, as IEEE 754 does by default: 2.5 is "2" and 3.5 is "4".
+    HalfEven,
+
+    /// Round to the nearest candidate, and ties toward positive infinity,
+    /// as JavaScript's `Math.round`: 2.5 is "3" and -2.5 is "-2".
+    HalfUp,
+
+    /// Round to the nearest candidate, and ties away from zero, as
+    /// spreadsheets do: 2.5 is "3" and -2.5 is "-3".
+    HalfAwayFromZero,
+
+    /// Round toward negative infinity: 2.7 is "2" and -2.1 is "-3".
+    Floor,
+
+    /// Round toward positive infinity: 2.1 is "3" and -2.7 is "-2".
+    Ceiling,
+
+    /// Round toward zero, i.e. truncate: 2.7 is "2" and -2.7 is "-2".
+    TowardZero,
+
+    /// Round away from zero: 2.1 is "3" and -2.1 is "-3".
+    AwayFromZero,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How [`Formatted::write_notation`] typesets a number for publication.
+pub enum Notation {
+    /// Unicode text, with the minus sign U+2212, a multiplication sign and a
+    /// superscript exponent, as in "−1.23 × 10⁻⁷". Infinity is "∞".
+    Typographic,
+
+    /// LaTeX math mode, as in "-1.23 \times 10^{-7}". Infinity is `\infty`
+    /// and NaN is `\mathrm{NaN}`.
+    Latex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The characters of a Unicode numbering system, which
+/// [`Formatted::write_numbering`] writes in place of the ASCII digits,
+/// decimal point and minus sign.
+///
+/// The exponent marker `e` and the text of NaN and infinity are kept as they
+/// are.
+pub struct NumberingSystem {
+    zero: char,
+    decimal_separator: char,
+    minus_sign: char,
+}
+
+impl NumberingSystem {
+    /// The Arabic-Indic digits U+0660 to U+0669, with the Arabic decimal
+    /// separator U+066B, as in "١٢٣٫٤٥".
+    pub const ARABIC_INDIC: Self = Self::new('\u{660}', '\u{66b}', '-');
+    /// The ASCII digits, with "." and "-", which leave the text unchanged.
+    pub const ASCII: Self = Self::new('0', '.', '-');
+    /// The Bengali digits U+09E6 to U+09EF, with "." and "-".
+    pub const BENGALI: Self = Self::new('\u{9e6}', '.', '-');
+    /// The Devanagari digits U+0966 to U+096F, with "." and "-".
+    pub const DEVANAGARI: Self = Self::new('\u{966}', '.', '-');
+    /// The extended Arabic-Indic digits U+06F0 to U+06F9 used for Persian and
+    /// Urdu, with the Arabic decimal separator U+066B and the minus sign
+    /// U+2212.
+    pub const EXTENDED_ARABIC_INDIC: Self = Self::new('\u{6f0}', '\u{66b}', '\u{2212}');
+    /// The full-width digits U+FF10 to U+FF19, with the full-width full stop
+    /// U+FF0E and hyphen-minus U+FF0D, as in "１２３．４５".
+    pub const FULLWIDTH: Self = Self::new('\u{ff10}', '\u{ff0e}', '\u{ff0d}');
+    /// The Thai digits U+0E50 to U+0E59, with "." and "-".
+    pub const THAI: Self = Self::new('\u{e50}', '.', '-');
+
+    /// Creates a numbering system whose digits are `zero` and the nine
+    /// characters after it, as for every decimal digit set in Unicode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the digits are not all characters, if the decimal separator
+    /// or minus sign is one of the digits, an ASCII digit, `e`, `E` or `+`,
+    /// or if they are the same.
+    pub const fn new(zero: char, decimal_separator: char, minus_sign: char) -> Self {
+        let zero_code = zero as u32;
+        assert!(
+            zero_code + 9 < 0xd800 || 0xdfff < zero_code && zero_code + 9 <= 0x10ffff,
+            "the digits must all be characters"
+        );
+        let system = Self {
+            zero,
+            decimal_separator,
+            minus_sign,
+        };
+        assert!(
+            system.is_symbol(decimal_separator)
+                && system.is_symbol(minus_sign)
+                && decimal_separator != minus_sign,
+            "the decimal separator and minus sign must be distinct symbols"
+        );
+        system
+    }
+
+    // Whether `c` can be the decimal separator or minus sign.
+    const fn is_symbol(&self, c: char) -> bool {
+        !matches!(c, '0'..='9' | 'e' | 'E' | '+') && self.digit_value(c).is_none()
+    }
+
+    /// The digit with the value `digit`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digit` is not below 10.
+    pub const fn digit(&self, digit: u8) -> char {
+        assert!(digit < 10, "`digit` must be below 10");
+        match char::from_u32(self.zero as u32 + digit as u32) {
+            Some(c) => c,
+            None => unreachable!(),
+        }
+    }
+
+    /// The value of `c` if it is one of the digits.
+    pub const fn digit_value(&self, c: char) -> Option<u8> {
+        let value = (c as u32).wrapping_sub(self.zero as u32);
+        if value < 10 {
+            Some(value as u8)
+        } else {
+            None
+        }
+    }
+
+    /// The decimal separator.
+    pub const fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    /// The minus sign.
+    pub const fn minus_sign(&self) -> char {
+        self.minus_sign
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The numbers of digits that
+/// [`Formatter::format_f64_fraction_digits`] prints, as the
+/// `minimumIntegerDigits`, `minimumFractionDigits` and
+/// `maximumFractionDigits` options of JavaScript's `Intl.NumberFormat`.
+///
+/// The number is rounded to the maximum number of fraction digits, its
+/// trailing zeros past the minimum are removed, and its integer part is
+/// padded with zeros to the minimum number of integer digits.
+pub struct FractionDigits {
+    min_integer_digits: usize,
+    min_fraction_digits: usize,
+    max_fraction_digits: usize,
+}
+
+impl FractionDigits {
+    /// Between `min_fraction_digits` and `max_fraction_digits` digits after
+    /// the decimal point, and at least one integer digit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_fraction_digits` is more than `max_fraction_digits`,
+    /// or `max_fraction_digits` is more than [`MAX_INTL_FRACTION_DIGITS`].
+    pub const fn new(min_fraction_digits: usize, max_fraction_digits: usize) -> Self {
+        assert!(
+            min_fraction_digits <= max_fraction_digits,
+            "`min_fraction_digits` must be at most `max_fraction_digits`"
+        );
+        assert!(
+            max_fraction_digits <= MAX_INTL_FRACTION_DIGITS,
+            "`max_fraction_digits` must be at most 20"
+        );
+        Self {
+            min_integer_digits: 1,
+            min_fraction_digits,
+            max_fraction_digits,
+        }
+    }
+
+    /// Pads the integer part with zeros to at least `min_integer_digits`
+    /// digits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_integer_digits` is zero or more than 21.
+    pub const fn with_min_integer_digits(mut self, min_integer_digits: usize) -> Self {
+        assert!(
+            min_integer_digits >= 1 && min_integer_digits <= 21,
+            "`min_integer_digits` must be between 1 and 21"
+        );
+        self.min_integer_digits = min_integer_digits;
+        self
+    }
+
+    /// The minimum number of integer digits.
+    pub const fn min_integer_digits(&self) -> usize {
+        self.min_integer_digits
+    }
+
+    /// The minimum number of digits after the decimal point.
+    pub const fn min_fraction_digits(&self) -> usize {
+        self.min_fraction_digits
+    }
+
+    /// The maximum number of digits after the decimal point.
+    pub const fn max_fraction_digits(&self) -> usize {
+        self.max_fraction_digits
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How far lossy output may be from the exact value of a number.
+pub enum Tolerance {
+    /// At most this fraction of the magnitude of the number away, as in
+    /// `1e-6` for one part in a million.
+    Relative(f64),
+
+    /// At most this far away from the number.
+    Absolute(f64),
+}
+
+impl<const N: usize> fmt::Debug for Formatted<N> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Formatted")
@@ -52,14 +343,14 @@ impl fmt::Debug for Formatted {
     }
 }
 
-impl fmt::Display for Formatted {
+impl<const N: usize> fmt::Display for Formatted<N> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.as_str())
     }
 }
 
-impl AsRef<str> for Formatted {
+impl<const N: usize> AsRef<str> for Formatted<N> {
     /// Returns a reference to the string representation of the last formatted
     /// floating point number.
     ///
@@ -72,7 +363,7 @@ impl AsRef<str> for Formatted {
     }
 }
 
-impl ops::Deref for Formatted {
+impl<const N: usize> ops::Deref for Formatted<N> {
     type Target = str;
 
     /// Returns a reference to the string representation of the last formatted
@@ -87,7 +378,7 @@ impl ops::Deref for Formatted {
     }
 }
 
-impl Formatted {
+impl<const N: usize> Formatted<N> {
     #[inline]
     /// Returns the formatted text.
     pub const fn as_str(&self) -> &str {
@@ -205,6 +496,18 @@ impl Formatted {
             FormattedMeta::Decimal {
                 offset_decimal_point,
             } => {
+                // Integers such as those of `format_f64_radix` have no decimal
+                // point, which is added before the zeros.
+                if offset_decimal_point == self.initialized {
+                    if DECIMAL_PLACES == 0 {
+                        return self.as_str();
+                    }
+                    unsafe {
+                        *self.bytes.as_mut_ptr().add(self.initialized).cast::<u8>() = b'.';
+                    };
+                    self.initialized += 1;
+                }
+
                 let target_length = offset_decimal_point + DECIMAL_PLACES + 1;
 
                 let to_be_zeroed = target_length.checked_sub(self.initialized);
@@ -385,6 +688,10 @@ impl Formatted {
                             );
 
                             zeros.as_mut_ptr().write_bytes(b'0', zeros.len());
+                            // An integer without a decimal point gets one.
+                            if offset_decimal_point == self.initialized {
+                                *zeros.as_mut_ptr() = b'.';
+                            }
                         }
                     }
 
@@ -507,6 +814,271 @@ impl Formatted {
             }
         }
     }
+
+    #[inline]
+    /// The length in bytes of the text that
+    /// [`write_notation`](Self::write_notation) writes, which is at most `N`
+    /// + [`NOTATION_EXTRA_LEN`].
+    pub const fn notation_len(&self, notation: Notation) -> usize {
+        self.write_notation_prefix(notation, &mut [])
+    }
+
+    /// Writes the text typeset in `notation` to the start of `buffer` and
+    /// returns it, or returns `None` if `buffer` is shorter than
+    /// [`notation_len`](Self::notation_len).
+    ///
+    /// The exponent form is rewritten as a power of ten, with the digits as
+    /// they are. Unlike [`as_str`](Self::as_str), the text is not ASCII in the
+    /// [`Typographic`](Notation::Typographic) notation.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// # use ryuu::format::Notation;
+    /// # use ryuu::Formatter;
+    /// let mut buffer = [0u8; 32 + ryuu::format::NOTATION_EXTRA_LEN];
+    ///
+    /// let formatted = Formatter::format_f64(-1.23e-7);
+    /// assert_eq!(
+    ///     formatted.write_notation(Notation::Typographic, &mut buffer),
+    ///     Some("\u{2212}1.23 \u{d7} 10\u{207b}\u{2077}")
+    /// );
+    /// assert_eq!(
+    ///     formatted.write_notation(Notation::Latex, &mut buffer),
+    ///     Some("-1.23 \\times 10^{-7}")
+    /// );
+    ///
+    /// let formatted = Formatter::format_f64(-0.5);
+    /// assert_eq!(
+    ///     formatted.write_notation(Notation::Typographic, &mut buffer),
+    ///     Some("\u{2212}0.5")
+    /// );
+    ///
+    /// let formatted = Formatter::format_f64(f64::NEG_INFINITY);
+    /// assert_eq!(
+    ///     formatted.write_notation(Notation::Latex, &mut buffer),
+    ///     Some("-\\infty")
+    /// );
+    /// assert_eq!(
+    ///     formatted.write_notation(Notation::Latex, &mut [0u8; 6]),
+    ///     None
+    /// );
+    /// ```
+    pub const fn write_notation<'a>(
+        &self,
+        notation: Notation,
+        buffer: &'a mut [u8],
+    ) -> Option<&'a str> {
+        let len = self.notation_len(notation);
+        if buffer.len() < len {
+            return None;
+        }
+        self.write_notation_prefix(notation, buffer);
+        // SAFETY: Only ASCII bytes of the text are copied, and the others are
+        // replaced by whole UTF-8 sequences.
+        Some(unsafe { str::from_utf8_unchecked(slice::from_raw_parts(buffer.as_ptr(), len)) })
+    }
+
+    // Writes the text in `notation` to `buffer`, leaving out the pieces that
+    // do not fit, and returns the length of all of it.
+    const fn write_notation_prefix(&self, notation: Notation, buffer: &mut [u8]) -> usize {
+        const SUPERSCRIPT_DIGITS: [&str; 10] = [
+            "\u{2070}", "\u{b9}", "\u{b2}", "\u{b3}", "\u{2074}", "\u{2075}", "\u{2076}",
+            "\u{2077}", "\u{2078}", "\u{2079}",
+        ];
+
+        let bytes = self.as_bytes();
+        let mut offset = 0;
+        let (sign, mut rest) = bytes.split_at((bytes[0] == b'-') as usize);
+        if !sign.is_empty() {
+            let minus = match notation {
+                Notation::Typographic => "\u{2212}",
+                Notation::Latex => "-",
+            };
+            offset = put(buffer, offset, minus.as_bytes());
+        }
+
+        match self.meta {
+            FormattedMeta::Decimal { .. } => put(buffer, offset, rest),
+            FormattedMeta::Nonfinite => match (notation, rest) {
+                (Notation::Typographic, b"inf") => put(buffer, offset, "\u{221e}".as_bytes()),
+                (Notation::Typographic, _) => put(buffer, offset, rest),
+                (Notation::Latex, b"inf") => put(buffer, offset, b"\\infty"),
+                (Notation::Latex, _) => {
+                    offset = put(buffer, offset, b"\\mathrm{");
+                    offset = put(buffer, offset, rest);
+                    put(buffer, offset, b"}")
+                }
+            },
+            FormattedMeta::Exponent {
+                offset_exponent, ..
+            } => {
+                let (mantissa, exponent) = rest.split_at(offset_exponent - sign.len());
+                offset = put(buffer, offset, mantissa);
+                rest = exponent.split_at(1).1;
+                match notation {
+                    Notation::Typographic => {
+                        offset = put(buffer, offset, " \u{d7} 10".as_bytes());
+                        while let [c, tail @ ..] = rest {
+                            let superscript = if *c == b'-' {
+                                "\u{207b}"
+                            } else {
+                                SUPERSCRIPT_DIGITS[(*c - b'0') as usize]
+                            };
+                            offset = put(buffer, offset, superscript.as_bytes());
+                            rest = tail;
+                        }
+                        offset
+                    }
+                    Notation::Latex => {
+                        offset = put(buffer, offset, b" \\times 10^{");
+                        offset = put(buffer, offset, rest);
+                        put(buffer, offset, b"}")
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    /// The length in bytes of the text that
+    /// [`write_numbering`](Self::write_numbering) writes, which is at most
+    /// four times the length of [`as_str`](Self::as_str).
+    pub const fn numbering_len(&self, system: NumberingSystem) -> usize {
+        self.write_numbering_prefix(system, &mut [])
+    }
+
+    /// Writes the text with the digits, decimal point and minus sign of
+    /// `system` to the start of `buffer` and returns it, or returns `None` if
+    /// `buffer` is shorter than [`numbering_len`](Self::numbering_len).
+    ///
+    /// Only the minus sign of NaN and infinity is replaced. The text reads
+    /// back with [`parse::s2d_numbering`](crate::parse::s2d_numbering).
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// # use ryuu::format::NumberingSystem;
+    /// # use ryuu::Formatter;
+    /// let mut buffer = [0u8; 4 * 32];
+    ///
+    /// let formatted = Formatter::format_f64(123.45);
+    /// assert_eq!(
+    ///     formatted.write_numbering(NumberingSystem::ARABIC_INDIC, &mut buffer),
+    ///     Some("١٢٣٫٤٥")
+    /// );
+    /// assert_eq!(
+    ///     formatted.write_numbering(NumberingSystem::FULLWIDTH, &mut buffer),
+    ///     Some("１２３．４５")
+    /// );
+    ///
+    /// let formatted = Formatter::format_f64(-1e-7);
+    /// assert_eq!(
+    ///     formatted.write_numbering(NumberingSystem::DEVANAGARI, &mut buffer),
+    ///     Some("-१e-७")
+    /// );
+    /// ```
+    pub const fn write_numbering<'a>(
+        &self,
+        system: NumberingSystem,
+        buffer: &'a mut [u8],
+    ) -> Option<&'a str> {
+        let len = self.numbering_len(system);
+        if buffer.len() < len {
+            return None;
+        }
+        self.write_numbering_prefix(system, buffer);
+        // SAFETY: Each ASCII byte of the text is replaced by a whole UTF-8
+        // sequence.
+        Some(unsafe { str::from_utf8_unchecked(slice::from_raw_parts(buffer.as_ptr(), len)) })
+    }
+
+    // Writes the text in `system` to `buffer`, leaving out the characters
+    // that do not fit, and returns the length of all of it.
+    const fn write_numbering_prefix(&self, system: NumberingSystem, buffer: &mut [u8]) -> usize {
+        let nonfinite = matches!(self.meta, FormattedMeta::Nonfinite);
+        let bytes = self.as_bytes();
+        let mut offset = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = match bytes[i] {
+                b'-' => system.minus_sign,
+                b'.' if !nonfinite => system.decimal_separator,
+                digit @ b'0'..=b'9' if !nonfinite => system.digit(digit - b'0'),
+                other => other as char,
+            };
+            offset = put(buffer, offset, c.encode_utf8(&mut [0; 4]).as_bytes());
+            i += 1;
+        }
+        offset
+    }
+}
+
+impl<const N: usize> Formatted<N> {
+    // The text of a NaN or an infinity.
+    #[inline]
+    const fn nonfinite(text: &'static str) -> Self {
+        let mut bytes = [MaybeUninit::uninit(); N];
+        unsafe {
+            ptr::copy_nonoverlapping(text.as_ptr(), bytes.as_mut_ptr().cast::<u8>(), text.len());
+        }
+        Formatted {
+            bytes,
+            meta: FormattedMeta::Nonfinite,
+            initialized: text.len(),
+        }
+    }
+
+    // The same text in a buffer of `M` bytes, which must hold it.
+    #[inline]
+    const fn resize<const M: usize>(&self) -> Formatted<M> {
+        debug_assert!(self.initialized <= M);
+        let mut bytes = [MaybeUninit::uninit(); M];
+        unsafe {
+            ptr::copy_nonoverlapping(self.bytes.as_ptr(), bytes.as_mut_ptr(), self.initialized);
+        }
+        Formatted {
+            bytes,
+            meta: self.meta,
+            initialized: self.initialized,
+        }
+    }
+
+    // The offset of the decimal point, of the exponent of a mantissa without
+    // one, or of the end of a number without either, on which
+    // `AlignedColumn` lines the text up.
+    #[inline]
+    const fn alignment_offset(&self) -> usize {
+        match self.meta {
+            FormattedMeta::Decimal {
+                offset_decimal_point,
+            } => offset_decimal_point,
+            FormattedMeta::Exponent {
+                offset_decimal_point: Some(offset_decimal_point),
+                ..
+            } => offset_decimal_point,
+            FormattedMeta::Exponent {
+                offset_decimal_point: None,
+                offset_exponent,
+            } => offset_exponent,
+            FormattedMeta::Nonfinite => self.initialized,
+        }
+    }
+}
+
+// Copies `text` to `buffer` at `offset` if it fits, and returns the offset
+// after it.
+#[inline]
+pub(crate) const fn put(buffer: &mut [u8], offset: usize, text: &[u8]) -> usize {
+    let end = offset + text.len();
+    if end <= buffer.len() {
+        let mut i = 0;
+        while i < text.len() {
+            buffer[offset + i] = text[i];
+            i += 1;
+        }
+    }
+    end
 }
 
 impl Formatter {
@@ -533,23 +1105,7 @@ impl Formatter {
     /// Const version of [`format`](Self::format), specifically for `f64`.
     pub const fn format_f64(d: f64) -> Formatted {
         if is_nonfinite_f64(d) {
-            let nonfinite_formatted = format_nonfinite_f64(d);
-
-            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
-
-            unsafe {
-                ptr::copy_nonoverlapping(
-                    nonfinite_formatted.as_ptr(),
-                    bytes.as_mut_ptr().cast::<u8>(),
-                    nonfinite_formatted.len(),
-                );
-            };
-
-            Formatted {
-                bytes,
-                meta: FormattedMeta::Nonfinite,
-                initialized: nonfinite_formatted.len(),
-            }
+            Formatted::nonfinite(format_nonfinite_f64(d))
         } else {
             Self::format_finite_f64(d)
         }
@@ -559,23 +1115,7 @@ impl Formatter {
     /// Const version of [`format`](Self::format), specifically for `f32`.
     pub const fn format_f32(f: f32) -> Formatted {
         if is_nonfinite_f32(f) {
-            let nonfinite_formatted = format_nonfinite_f32(f);
-
-            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
-
-            unsafe {
-                ptr::copy_nonoverlapping(
-                    nonfinite_formatted.as_ptr(),
-                    bytes.as_mut_ptr().cast::<u8>(),
-                    nonfinite_formatted.len(),
-                );
-            };
-
-            Formatted {
-                bytes,
-                meta: FormattedMeta::Nonfinite,
-                initialized: nonfinite_formatted.len(),
-            }
+            Formatted::nonfinite(format_nonfinite_f32(f))
         } else {
             Self::format_finite_f32(f)
         }
@@ -628,55 +1168,2183 @@ impl Formatter {
             initialized: offset_full.initialized,
         }
     }
-}
-
-#[allow(private_bounds)]
-/// A floating point number, f32 or f64, that can be formatted to text.
-///
-/// This trait is sealed and cannot be implemented for types outside of the
-/// `ryu` crate.
-pub trait Float: Sealed {}
-
-impl Float for f32 {}
-impl Float for f64 {}
-
-trait Sealed: Copy {
-    fn format(self) -> Formatted;
-}
 
-impl Sealed for f32 {
     #[inline]
-    fn format(self) -> Formatted {
-        Formatter::format_f32(self)
+    /// Print an `f64` like [`format_f64`](Self::format_f64), but with the
+    /// sign, quiet bit and payload of NaN.
+    ///
+    /// A quiet NaN prints as `nan`, and a signaling NaN as `snan`, after a
+    /// `-` if its sign bit is set. Unless the NaN is the default quiet NaN,
+    /// the 52-bit trailing significand follows in hexadecimal between
+    /// parentheses, in the style of C99's `nan("...")`. The field includes the
+    /// quiet bit, `0x8000000000000`. Finite numbers and infinities print as in
+    /// [`format_f64`](Self::format_f64). The output parses back to the exact
+    /// bits with [`s2d_bits`].
+    ///
+    /// [`s2d_bits`]: crate::parse::s2d_bits
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// assert_eq!(Formatter::format_f64_nan_payload(f64::NAN).as_str(), "nan");
+    /// assert_eq!(
+    ///     Formatter::format_f64_nan_payload(-f64::NAN).as_str(),
+    ///     "-nan"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_f64_nan_payload(f64::from_bits(0x7ff8000000000001)).as_str(),
+    ///     "nan(0x8000000000001)"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_f64_nan_payload(f64::from_bits(0x7ff0000000000001)).as_str(),
+    ///     "snan(0x1)"
+    /// );
+    /// assert_eq!(Formatter::format_f64_nan_payload(-1.5).as_str(), "-1.5");
+    /// ```
+    pub const fn format_f64_nan_payload(d: f64) -> Formatted {
+        const MANTISSA_MASK: u64 = 0x000fffffffffffff;
+        let bits = d.to_bits();
+        if !d.is_nan() {
+            return Self::format_f64(d);
+        }
+        format_nan(bits >> 63 != 0, bits & MANTISSA_MASK, 1 << 51)
     }
-}
 
-impl Sealed for f64 {
     #[inline]
-    fn format(self) -> Formatted {
-        Formatter::format_f64(self)
+    /// `f32` version of
+    /// [`format_f64_nan_payload`](Self::format_f64_nan_payload).
+    ///
+    /// The trailing significand has 23 bits, of which `0x400000` is the quiet
+    /// bit, and the output parses back to the exact bits with [`s2f_bits`].
+    ///
+    /// [`s2f_bits`]: crate::parse::s2f_bits
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// assert_eq!(
+    ///     Formatter::format_f32_nan_payload(-f32::NAN).as_str(),
+    ///     "-nan"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_f32_nan_payload(f32::from_bits(0xff80_0abc)).as_str(),
+    ///     "-snan(0xabc)"
+    /// );
+    /// ```
+    pub const fn format_f32_nan_payload(f: f32) -> Formatted {
+        const MANTISSA_MASK: u32 = 0x007fffff;
+        let bits = f.to_bits();
+        if !f.is_nan() {
+            return Self::format_f32(f);
+        }
+        format_nan(bits >> 31 != 0, (bits & MANTISSA_MASK) as u64, 1 << 22)
     }
-}
-
-// === nonfinite float helpers ===
 
-const NAN: &str = "NaN";
-const INFINITY: &str = "inf";
-const NEG_INFINITY: &str = "-inf";
+    #[inline]
+    /// Print an `f64` with the shortest digits that parse to the same `f32`
+    /// as `d as f32`.
+    ///
+    /// This is for values kept as `f64` but stored or sent as `f32`. The
+    /// output has as many digits as [`format_f32(d as f32)`](Self::format_f32)
+    /// and parses to the same `f32`, but where several decimals of that
+    /// length would do, the one closest to `d` is chosen instead of the one
+    /// closest to `d as f32`. Both use the rounding interval of the `f32`
+    /// from Ryū's `f2d`, so when `d` is exactly representable as `f32` the
+    /// output is the same as `format_f32`.
+    ///
+    /// Like the cast, values too large for `f32` print as "inf" or "-inf",
+    /// values too small print as "0.0" or "-0.0", and NaN prints as "NaN".
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// assert_eq!(Formatter::format_f64_as_f32(0.1).as_str(), "0.1");
+    /// assert_eq!(
+    ///     Formatter::format_f64(0.1f32 as f64).as_str(),
+    ///     "0.10000000149011612"
+    /// );
+    ///
+    /// // Both parse to the f32 2.71828174591064453125, but the second one is
+    /// // closer to e = 2.71828182845904...
+    /// let e = core::f64::consts::E;
+    /// assert_eq!(Formatter::format_f32(e as f32).as_str(), "2.7182817");
+    /// assert_eq!(Formatter::format_f64_as_f32(e).as_str(), "2.7182818");
+    ///
+    /// assert_eq!(Formatter::format_f64_as_f32(1e39).as_str(), "inf");
+    /// assert_eq!(Formatter::format_f64_as_f32(-1e-46).as_str(), "-0.0");
+    /// ```
+    pub const fn format_f64_as_f32(d: f64) -> Formatted {
+        let f = d as f32;
+        if is_nonfinite_f32(f) {
+            Formatted::nonfinite(format_nonfinite_f32(f))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
 
-#[inline]
-const fn is_nonfinite_f32(f: f32) -> bool {
-    const EXP_MASK: u32 = 0x7f800000;
-    let bits = f.to_bits();
-    bits & EXP_MASK == EXP_MASK
-}
+            // Do format
+            let offset_full =
+                unsafe { raw::format64_as_32_spec(d, bytes.as_mut_ptr().cast::<u8>()) };
 
-#[inline]
-const fn is_nonfinite_f64(d: f64) -> bool {
-    const EXP_MASK: u64 = 0x7ff0000000000000;
-    let bits = d.to_bits();
-    bits & EXP_MASK == EXP_MASK
-}
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// Print the shortest representation of an `f64` that is not above its
+    /// exact value.
+    ///
+    /// Like [`format_f64`](Self::format_f64), the output parses back to `d`
+    /// and uses the same layout, but it is chosen from the part of Ryū's
+    /// rounding interval at or below the value, so it is a lower bound for
+    /// `d` as well. This is useful for interval arithmetic and conservative
+    /// bounds. The output can be longer than the shortest representation,
+    /// and has the same length when that is already on the correct side.
+    ///
+    /// Zero, infinity and NaN print as in [`format_f64`](Self::format_f64).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 0.1 is 0.1000000000000000055511151231257827 as an f64.
+    /// assert_eq!(Formatter::format_f64_round_down(0.1).as_str(), "0.1");
+    /// assert_eq!(
+    ///     Formatter::format_f64_round_down(-0.1).as_str(),
+    ///     "-0.10000000000000001"
+    /// );
+    ///
+    /// // 1/3 is 0.333333333333333314829616256247390992939472198486328125.
+    /// assert_eq!(
+    ///     Formatter::format_f64(1.0 / 3.0).as_str(),
+    ///     "0.3333333333333333"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_f64_round_down(1.0 / 3.0).as_str(),
+    ///     "0.3333333333333333"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_f64_round_up(1.0 / 3.0).as_str(),
+    ///     "0.33333333333333332"
+    /// );
+    /// ```
+    pub const fn format_f64_round_down(d: f64) -> Formatted {
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full =
+                unsafe { raw::format64_directed_spec(d, false, bytes.as_mut_ptr().cast::<u8>()) };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// Print the shortest representation of an `f64` that is not below its
+    /// exact value.
+    ///
+    /// This is the counterpart of
+    /// [`format_f64_round_down`](Self::format_f64_round_down), choosing from
+    /// the part of the rounding interval at or above the value, so the output
+    /// is an upper bound for `d` that still parses back to `d`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 0.1 is 0.1000000000000000055511151231257827 as an f64.
+    /// assert_eq!(
+    ///     Formatter::format_f64_round_up(0.1).as_str(),
+    ///     "0.10000000000000001"
+    /// );
+    /// assert_eq!(Formatter::format_f64_round_up(-0.1).as_str(), "-0.1");
+    /// assert_eq!(Formatter::format_f64_round_up(0.5).as_str(), "0.5");
+    /// ```
+    pub const fn format_f64_round_up(d: f64) -> Formatted {
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full =
+                unsafe { raw::format64_directed_spec(d, true, bytes.as_mut_ptr().cast::<u8>()) };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// Print the shortest decimal between two `f64`s.
+    ///
+    /// This is the decimal with the fewest significant digits whose exact
+    /// value lies between the exact values of `lo` and `hi`, including the
+    /// bounds themselves if `inclusive` is true. Where several decimals of
+    /// that length lie in the interval, only the ones with the largest
+    /// exponent are considered, as in "1e300" rather than "1e-300" for the
+    /// interval between those two. Of these, the one in the middle is chosen,
+    /// and of the two in the middle, the one with an even last digit. The
+    /// output is laid out like [`format_f64`](Self::format_f64), and is
+    /// "0.0" if the interval contains zero.
+    ///
+    /// Returns `None` if a bound is NaN or infinite, if the interval is empty
+    /// because `lo > hi`, or `lo == hi` and it is open, or if no decimal with
+    /// at most 17 significant digits lies in it. The last case can only
+    /// happen when the bounds are the same `f64`, whose exact value is the
+    /// only candidate.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// let shortest = |lo, hi| Formatter::shortest_in_range_f64(lo, hi, true).unwrap();
+    /// // Any of 0.1235 to 0.1239.
+    /// assert_eq!(shortest(0.12345, 0.12399).as_str(), "0.1237");
+    /// // Any of 0.1235 to 0.1238, since 0.1239 as an f64 is slightly below
+    /// // 0.1239.
+    /// assert_eq!(shortest(0.12345, 0.1239).as_str(), "0.1236");
+    /// assert_eq!(shortest(17.0, 42.0).as_str(), "30.0");
+    /// assert_eq!(shortest(-1.5, 2.5).as_str(), "0.0");
+    /// assert_eq!(shortest(-0.3, -0.25).as_str(), "-0.27");
+    ///
+    /// // 0.3 as an f64 is slightly below 0.3, so 0.3 is in the open interval
+    /// // up to the next f64.
+    /// let next = f64::from_bits(0.3f64.to_bits() + 1);
+    /// let shortest = |lo, hi| Formatter::shortest_in_range_f64(lo, hi, false).unwrap();
+    /// assert_eq!(shortest(0.3, next).as_str(), "0.3");
+    /// assert_eq!(shortest(0.25, 0.3).as_str(), "0.28");
+    ///
+    /// assert!(Formatter::shortest_in_range_f64(0.3, 0.3, false).is_none());
+    /// assert!(Formatter::shortest_in_range_f64(1.0, 0.5, true).is_none());
+    /// ```
+    pub const fn shortest_in_range_f64(lo: f64, hi: f64, inclusive: bool) -> Option<Formatted> {
+        if is_nonfinite_f64(lo) || is_nonfinite_f64(hi) {
+            return None;
+        }
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+        // Do format
+        match unsafe {
+            raw::format64_in_range_spec(lo, hi, inclusive, bytes.as_mut_ptr().cast::<u8>())
+        } {
+            Some(offset_full) => {
+                debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+                Some(Formatted {
+                    bytes,
+                    meta: offset_full.meta,
+                    initialized: offset_full.initialized,
+                })
+            }
+            None => None,
+        }
+    }
+
+    #[inline]
+    /// Print the shortest representation of an `f64` that is within a
+    /// tolerance of it.
+    ///
+    /// This is lossy formatting for when full round-trip precision is not
+    /// needed: the output is the decimal with the fewest significant digits
+    /// whose exact value is at most the [`Tolerance`] away from the exact
+    /// value of `d`. Where several decimals of that length would do, the one
+    /// closest to `d` is chosen. Ryū's rounding interval is widened to the
+    /// tolerance, never narrowed, so the output never has more significant
+    /// digits than that of [`format_f64`](Self::format_f64), and is the same
+    /// when the tolerance is smaller than the gap to the neighboring `f64`s.
+    /// The layout is the same as well, so "1000000000000000.0" can still be
+    /// longer than "914514623984419.0".
+    ///
+    /// If zero is within the tolerance, the output is "0.0", or "-0.0" for a
+    /// negative `d`. Infinity and NaN print as in
+    /// [`format_f64`](Self::format_f64).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tolerance is negative or NaN.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::Tolerance;
+    /// # use ryuu::Formatter;
+    /// let rel = |d| Formatter::format_within_tolerance(d, Tolerance::Relative(1e-6));
+    /// assert_eq!(rel(0.1 + 0.2).as_str(), "0.3");
+    /// assert_eq!(rel(3.14159265).as_str(), "3.14159");
+    /// assert_eq!(rel(-2.0 / 3.0).as_str(), "-0.666667");
+    /// assert_eq!(rel(6.02214076e23).as_str(), "6.02214e23");
+    ///
+    /// let abs = |d| Formatter::format_within_tolerance(d, Tolerance::Absolute(0.005));
+    /// assert_eq!(abs(3.14159265).as_str(), "3.14");
+    /// assert_eq!(abs(1234.5678).as_str(), "1234.57");
+    /// assert_eq!(abs(0.004).as_str(), "0.0");
+    /// ```
+    pub const fn format_within_tolerance(d: f64, tolerance: Tolerance) -> Formatted {
+        let (Tolerance::Relative(t) | Tolerance::Absolute(t)) = tolerance;
+        assert!(t >= 0.0, "`tolerance` must not be negative or NaN");
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full =
+                unsafe { raw::format64_within_spec(d, tolerance, bytes.as_mut_ptr().cast::<u8>()) };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// Print an `f64` rounded to `digits` significant digits.
+    ///
+    /// Unlike [`as_str_fixed_dp`](Formatted::as_str_fixed_dp), which cuts the
+    /// shortest digits, this rounds the exact binary value with the given
+    /// [`RoundingMode`], so `0.125` is "0.12" with `HalfEven`, and the output
+    /// is never above the value with `Floor`. The number is then laid out like
+    /// [`format_f64`](Self::format_f64), in positional or exponent form with
+    /// the same thresholds.
+    ///
+    /// With [`TrailingZeros::Keep`], the output always shows `digits`
+    /// significant digits, as in "1.20" for three digits. Zeros that are
+    /// needed as placeholders are written anyway, as in "123000.0".
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is 0 or more than 17.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::{RoundingMode, TrailingZeros};
+    /// # use ryuu::Formatter;
+    /// let sig3 =
+    ///     |d| Formatter::format_f64_significant(d, 3, TrailingZeros::Keep, RoundingMode::HalfEven);
+    /// assert_eq!(sig3(0.0012345).as_str(), "0.00123");
+    /// assert_eq!(sig3(123456.0).as_str(), "123000.0");
+    /// assert_eq!(sig3(1.2).as_str(), "1.20");
+    /// assert_eq!(sig3(9.996).as_str(), "10.0");
+    /// assert_eq!(sig3(1.23456e20).as_str(), "1.23e20");
+    ///
+    /// let sig3 =
+    ///     |d| Formatter::format_f64_significant(d, 3, TrailingZeros::Strip, RoundingMode::HalfEven);
+    /// assert_eq!(sig3(1.2).as_str(), "1.2");
+    /// assert_eq!(sig3(1.0e-7).as_str(), "1e-7");
+    ///
+    /// // 0.1 is 0.1000000000000000055511151231257827 as an f64.
+    /// let sig3 = |d, mode| Formatter::format_f64_significant(d, 3, TrailingZeros::Keep, mode);
+    /// assert_eq!(sig3(0.1, RoundingMode::Floor).as_str(), "0.100");
+    /// assert_eq!(sig3(0.1, RoundingMode::Ceiling).as_str(), "0.101");
+    /// assert_eq!(sig3(-0.1, RoundingMode::Ceiling).as_str(), "-0.100");
+    /// ```
+    pub const fn format_f64_significant(
+        d: f64,
+        digits: u32,
+        trailing_zeros: TrailingZeros,
+        mode: RoundingMode,
+    ) -> Formatted {
+        assert!(
+            digits >= 1 && digits <= MAX_SIGNIFICANT_DIGITS_F64,
+            "`digits` must be between 1 and 17"
+        );
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format64_significant_spec(
+                    d,
+                    digits,
+                    trailing_zeros,
+                    mode,
+                    bytes.as_mut_ptr().cast::<u8>(),
+                )
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// `f32` version of
+    /// [`format_f64_significant`](Self::format_f64_significant),
+    /// laid out like [`format_f32`](Self::format_f32).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is 0 or more than 9.
+    pub const fn format_f32_significant(
+        f: f32,
+        digits: u32,
+        trailing_zeros: TrailingZeros,
+        mode: RoundingMode,
+    ) -> Formatted {
+        assert!(
+            digits >= 1 && digits <= MAX_SIGNIFICANT_DIGITS_F32,
+            "`digits` must be between 1 and 9"
+        );
+        if is_nonfinite_f32(f) {
+            Formatted::nonfinite(format_nonfinite_f32(f))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format32_significant_spec(
+                    f,
+                    digits,
+                    trailing_zeros,
+                    mode,
+                    bytes.as_mut_ptr().cast::<u8>(),
+                )
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// Print an `f64` with exactly `decimal_places` digits after the decimal
+    /// point, rounding its exact binary value, like `printf("%.*f")`.
+    ///
+    /// With [`RoundingMode::Floor`] the output is never above the value of
+    /// `d`, and with [`RoundingMode::Ceiling`] never below it. So `2.675`,
+    /// whose nearest `f64` is
+    /// 2.67499999999999982236431605997495353221893310546875, is "2.67" with
+    /// [`RoundingMode::HalfEven`], unlike with
+    /// [`format_f64_rounding_dp`](Self::format_f64_rounding_dp).
+    ///
+    /// Numbers that [`format_f64`](Self::format_f64) prints in exponent form
+    /// keep it, and `decimal_places` counts the digits after the decimal point
+    /// of the mantissa, like `printf("%.*e")`. With no decimal places, there
+    /// is no decimal point either.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decimal_places` is more than [`MAX_DECIMAL_PLACES`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::RoundingMode;
+    /// # use ryuu::Formatter;
+    /// let dp2 = |d, mode| Formatter::format_f64_fixed(d, 2, mode);
+    /// assert_eq!(dp2(2.675, RoundingMode::HalfEven).as_str(), "2.67");
+    /// assert_eq!(dp2(2.675, RoundingMode::Ceiling).as_str(), "2.68");
+    /// assert_eq!(dp2(0.1, RoundingMode::Floor).as_str(), "0.10");
+    /// assert_eq!(dp2(0.1, RoundingMode::Ceiling).as_str(), "0.11");
+    /// assert_eq!(dp2(-0.1, RoundingMode::Floor).as_str(), "-0.11");
+    /// assert_eq!(dp2(-0.1, RoundingMode::TowardZero).as_str(), "-0.10");
+    /// assert_eq!(dp2(1.5e20, RoundingMode::HalfEven).as_str(), "1.50e20");
+    /// ```
+    pub const fn format_f64_fixed(d: f64, decimal_places: usize, mode: RoundingMode) -> Formatted {
+        assert!(
+            decimal_places <= MAX_DECIMAL_PLACES,
+            "`decimal_places` must be at most 14"
+        );
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format64_fixed_spec(d, decimal_places, mode, bytes.as_mut_ptr().cast::<u8>())
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// `f32` version of [`format_f64_fixed`](Self::format_f64_fixed), laid out
+    /// like [`format_f32`](Self::format_f32).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decimal_places` is more than [`MAX_DECIMAL_PLACES`].
+    pub const fn format_f32_fixed(f: f32, decimal_places: usize, mode: RoundingMode) -> Formatted {
+        assert!(
+            decimal_places <= MAX_DECIMAL_PLACES,
+            "`decimal_places` must be at most 14"
+        );
+        if is_nonfinite_f32(f) {
+            Formatted::nonfinite(format_nonfinite_f32(f))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format32_fixed_spec(f, decimal_places, mode, bytes.as_mut_ptr().cast::<u8>())
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// Print an `f64` with between the minimum and maximum numbers of digits
+    /// after the decimal point of `digits`, and at least its minimum number
+    /// of integer digits, like JavaScript's `Intl.NumberFormat`.
+    ///
+    /// The exact binary value of `d` is rounded to the maximum number of
+    /// fraction digits as in [`format_f64_fixed`](Self::format_f64_fixed),
+    /// and then trailing zeros are removed down to the minimum. As with
+    /// `Intl.NumberFormat`, the number is never in exponent form: large
+    /// numbers have all of their integer digits, and small ones are rounded
+    /// to zero past the maximum number of fraction digits.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::{FractionDigits, RoundingMode};
+    /// # use ryuu::Formatter;
+    /// let digits = FractionDigits::new(1, 3);
+    /// let format = |d| Formatter::format_f64_fraction_digits(d, digits, RoundingMode::HalfEven);
+    /// assert_eq!(format(2.0).as_str(), "2.0");
+    /// assert_eq!(format(2.12345).as_str(), "2.123");
+    /// assert_eq!(format(2.5).as_str(), "2.5");
+    /// assert_eq!(format(2.9999).as_str(), "3.0");
+    /// assert_eq!(format(1.5e20).as_str(), "150000000000000000000.0");
+    /// assert_eq!(format(1e-7).as_str(), "0.0");
+    ///
+    /// let digits = FractionDigits::new(0, 2).with_min_integer_digits(3);
+    /// let format = |d| Formatter::format_f64_fraction_digits(d, digits, RoundingMode::HalfEven);
+    /// assert_eq!(format(7.0).as_str(), "007");
+    /// assert_eq!(format(-0.125).as_str(), "-000.12");
+    /// assert_eq!(format(1234.5).as_str(), "1234.5");
+    /// ```
+    pub const fn format_f64_fraction_digits(
+        d: f64,
+        digits: FractionDigits,
+        mode: RoundingMode,
+    ) -> Formatted<BUFFER_LEN_FRACTION_DIGITS> {
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_FRACTION_DIGITS];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format64_fraction_digits_spec(
+                    d,
+                    digits.min_integer_digits,
+                    digits.min_fraction_digits,
+                    digits.max_fraction_digits,
+                    mode,
+                    bytes.as_mut_ptr().cast::<u8>(),
+                )
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN_FRACTION_DIGITS);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// `f32` version of
+    /// [`format_f64_fraction_digits`](Self::format_f64_fraction_digits).
+    pub const fn format_f32_fraction_digits(
+        f: f32,
+        digits: FractionDigits,
+        mode: RoundingMode,
+    ) -> Formatted<BUFFER_LEN_FRACTION_DIGITS> {
+        if is_nonfinite_f32(f) {
+            Formatted::nonfinite(format_nonfinite_f32(f))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_FRACTION_DIGITS];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format32_fraction_digits_spec(
+                    f,
+                    digits.min_integer_digits,
+                    digits.min_fraction_digits,
+                    digits.max_fraction_digits,
+                    mode,
+                    bytes.as_mut_ptr().cast::<u8>(),
+                )
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN_FRACTION_DIGITS);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// Print an `f64` with exactly `decimal_places` digits after the decimal
+    /// point, rounding its shortest representation.
+    ///
+    /// This rounds the digits of [`format_f64`](Self::format_f64) the way a
+    /// person would round the printed number, unlike
+    /// [`as_str_fixed_dp`](Formatted::as_str_fixed_dp), which cuts them. So
+    /// `2.675` is "2.68" with [`RoundingMode::HalfUp`], even though the
+    /// nearest `f64` is slightly below 2.675. With no decimal places, there is
+    /// no decimal point either.
+    ///
+    /// Numbers that `format_f64` prints in exponent form, such as `1.5e20`,
+    /// keep it, and `decimal_places` counts the digits after the decimal point
+    /// of the mantissa, as with [`copy_to_bytes`](Formatted::copy_to_bytes).
+    ///
+    /// The directed rounding modes also apply to the shortest digits, so
+    /// `Floor` may give a decimal slightly above the exact value of `d`. Use
+    /// [`format_f64_fixed`](Self::format_f64_fixed) for a guaranteed bound.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decimal_places` is more than [`MAX_DECIMAL_PLACES`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::RoundingMode;
+    /// # use ryuu::Formatter;
+    /// let dp2 = |d| Formatter::format_f64_rounding_dp(d, 2, RoundingMode::HalfUp);
+    /// assert_eq!(dp2(2.675).as_str(), "2.68");
+    /// assert_eq!(dp2(-2.675).as_str(), "-2.67");
+    /// assert_eq!(dp2(9.995).as_str(), "10.00");
+    /// assert_eq!(dp2(3.1).as_str(), "3.10");
+    /// assert_eq!(dp2(9.995e20).as_str(), "1.00e21");
+    ///
+    /// let dp2 = |d| Formatter::format_f64_rounding_dp(d, 2, RoundingMode::HalfEven);
+    /// assert_eq!(dp2(2.675).as_str(), "2.68");
+    /// assert_eq!(dp2(2.665).as_str(), "2.66");
+    ///
+    /// let dp0 = |d| Formatter::format_f64_rounding_dp(d, 0, RoundingMode::HalfAwayFromZero);
+    /// assert_eq!(dp0(-2.5).as_str(), "-3");
+    /// ```
+    pub const fn format_f64_rounding_dp(
+        d: f64,
+        decimal_places: usize,
+        mode: RoundingMode,
+    ) -> Formatted {
+        assert!(
+            decimal_places <= MAX_DECIMAL_PLACES,
+            "`decimal_places` must be at most 14"
+        );
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format64_shortest_dp_spec(
+                    d,
+                    decimal_places,
+                    mode,
+                    bytes.as_mut_ptr().cast::<u8>(),
+                )
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// `f32` version of
+    /// [`format_f64_rounding_dp`](Self::format_f64_rounding_dp),
+    /// rounding the digits of [`format_f32`](Self::format_f32).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decimal_places` is more than [`MAX_DECIMAL_PLACES`].
+    pub const fn format_f32_rounding_dp(
+        f: f32,
+        decimal_places: usize,
+        mode: RoundingMode,
+    ) -> Formatted {
+        assert!(
+            decimal_places <= MAX_DECIMAL_PLACES,
+            "`decimal_places` must be at most 14"
+        );
+        if is_nonfinite_f32(f) {
+            Formatted::nonfinite(format_nonfinite_f32(f))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+            // Do format
+            let offset_full = unsafe {
+                raw::format32_shortest_dp_spec(
+                    f,
+                    decimal_places,
+                    mode,
+                    bytes.as_mut_ptr().cast::<u8>(),
+                )
+            };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// The smallest number of decimal places at which no two different
+    /// numbers of `values` print as the same number, as for axis labels or a
+    /// column of a table: 0.1, 0.15 and 0.2 need two, as "0.10", "0.15" and
+    /// "0.20".
+    ///
+    /// The decimal places are counted and rounded as in
+    /// [`format_f64_rounding_dp`](Self::format_f64_rounding_dp), so numbers in
+    /// exponent form count those of their mantissa. The result never exceeds
+    /// the number needed to show the shortest digits of every value in full,
+    /// which may be more than [`MAX_DECIMAL_PLACES`], up to 21. Zeros of
+    /// either sign count as the same number, and NaN and infinities are
+    /// ignored.
+    ///
+    /// This compares every pair of values, so it is meant for the few dozen
+    /// numbers of an axis or a column rather than for large data sets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_SHARED_VALUES`] values.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::RoundingMode;
+    /// # use ryuu::Formatter;
+    /// let dp = |values: &[f64]| Formatter::shared_decimal_places_f64(values, RoundingMode::HalfEven);
+    /// assert_eq!(dp(&[0.1, 0.15, 0.2]), 2);
+    /// assert_eq!(dp(&[0.0, 0.5, 1.0, 1.5]), 1);
+    /// assert_eq!(dp(&[100.0, 200.0, 300.0]), 0);
+    /// assert_eq!(dp(&[1.04, 1.06]), 1);
+    /// assert_eq!(dp(&[1.0, 1.0, f64::NAN]), 0);
+    /// ```
+    pub const fn shared_decimal_places_f64(values: &[f64], mode: RoundingMode) -> usize {
+        assert!(
+            values.len() <= MAX_SHARED_VALUES,
+            "`values` must have at most 256 values"
+        );
+        raw::shared_decimal_places64(values, mode)
+    }
+
+    #[inline]
+    /// `f32` version of
+    /// [`shared_decimal_places_f64`](Self::shared_decimal_places_f64), with
+    /// the decimal places of
+    /// [`format_f32_rounding_dp`](Self::format_f32_rounding_dp), up to 14.
+    pub const fn shared_decimal_places_f32(values: &[f32], mode: RoundingMode) -> usize {
+        assert!(
+            values.len() <= MAX_SHARED_VALUES,
+            "`values` must have at most 256 values"
+        );
+        raw::shared_decimal_places32(values, mode)
+    }
+
+    #[inline]
+    /// Formats each of `values` with the number of decimal places given by
+    /// [`shared_decimal_places_f64`](Self::shared_decimal_places_f64), and
+    /// rounded as by
+    /// [`format_f64_rounding_dp`](Self::format_f64_rounding_dp).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_SHARED_VALUES`] values.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::RoundingMode;
+    /// # use ryuu::Formatter;
+    /// let labels = Formatter::format_shared_dp_f64(&[0.1, 0.15, 0.2], RoundingMode::HalfEven);
+    /// assert_eq!(labels.decimal_places(), 2);
+    ///
+    /// let mut labels = labels.map(|label| label.as_str().to_owned());
+    /// assert_eq!(labels.next().as_deref(), Some("0.10"));
+    /// assert_eq!(labels.next().as_deref(), Some("0.15"));
+    /// assert_eq!(labels.next().as_deref(), Some("0.20"));
+    /// assert_eq!(labels.next(), None);
+    /// ```
+    pub const fn format_shared_dp_f64(
+        values: &[f64],
+        mode: RoundingMode,
+    ) -> SharedDecimalPlaces<'_, f64> {
+        SharedDecimalPlaces {
+            values,
+            decimal_places: Self::shared_decimal_places_f64(values, mode),
+            mode,
+        }
+    }
+
+    #[inline]
+    /// `f32` version of
+    /// [`format_shared_dp_f64`](Self::format_shared_dp_f64).
+    pub const fn format_shared_dp_f32(
+        values: &[f32],
+        mode: RoundingMode,
+    ) -> SharedDecimalPlaces<'_, f32> {
+        SharedDecimalPlaces {
+            values,
+            decimal_places: Self::shared_decimal_places_f32(values, mode),
+            mode,
+        }
+    }
+
+    #[inline]
+    /// Formats each of `values` as [`format_f64`](Self::format_f64) does,
+    /// padded with spaces so that they all have the same width and their
+    /// decimal points line up, as in a column of a report.
+    ///
+    /// A number is aligned on its decimal point, or on the end of its
+    /// integer part if it has none. Numbers in exponent form are aligned on
+    /// the decimal point of their mantissa, so their exponent extends the
+    /// fraction side of the column, and NaN and infinities are aligned as
+    /// integers. The widths of both sides are the largest of any value.
+    ///
+    /// The numbers keep their shortest digits, so no value is lost.
+    /// [`format_aligned_dp_f64`](Self::format_aligned_dp_f64) rounds them to
+    /// a number of decimal places instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// let column = Formatter::format_aligned_f64(&[1.25, 1.5, -20.0, 300.0]);
+    /// assert_eq!(column.decimal_places(), None);
+    /// assert_eq!(column.width(), 6);
+    ///
+    /// let mut cells = column.map(|cell| cell.to_string());
+    /// assert_eq!(cells.next().as_deref(), Some("  1.25"));
+    /// assert_eq!(cells.next().as_deref(), Some("  1.5 "));
+    /// assert_eq!(cells.next().as_deref(), Some("-20.0 "));
+    /// assert_eq!(cells.next().as_deref(), Some("300.0 "));
+    /// assert_eq!(cells.next(), None);
+    ///
+    /// let values = [0.25, 0.5, 1.5e20, f64::NAN];
+    /// let mut cells = Formatter::format_aligned_f64(&values).map(|cell| cell.to_string());
+    /// assert_eq!(cells.next().as_deref(), Some("  0.25  "));
+    /// assert_eq!(cells.next().as_deref(), Some("  0.5   "));
+    /// assert_eq!(cells.next().as_deref(), Some("  1.5e20"));
+    /// assert_eq!(cells.next().as_deref(), Some("NaN     "));
+    /// ```
+    pub const fn format_aligned_f64(values: &[f64]) -> AlignedColumn<'_, f64> {
+        aligned_f64(values, None, RoundingMode::HalfEven)
+    }
+
+    #[inline]
+    /// `f32` version of [`format_aligned_f64`](Self::format_aligned_f64).
+    pub const fn format_aligned_f32(values: &[f32]) -> AlignedColumn<'_, f32> {
+        aligned_f32(values, None, RoundingMode::HalfEven)
+    }
+
+    #[inline]
+    /// Formats `values` as [`format_aligned_f64`](Self::format_aligned_f64)
+    /// does, with their shortest representations rounded to `decimal_places`
+    /// as by [`format_f64_rounding_dp`](Self::format_f64_rounding_dp).
+    ///
+    /// [`shared_decimal_places_f64`](Self::shared_decimal_places_f64) gives
+    /// the fewest decimal places that keep distinct values apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decimal_places` is more than 21, the most that
+    /// `shared_decimal_places_f64` returns.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::format::RoundingMode;
+    /// # use ryuu::Formatter;
+    /// let values = [1.25, 1.2, -20.5, 300.0];
+    /// let column = Formatter::format_aligned_dp_f64(&values, 1, RoundingMode::HalfEven);
+    /// assert_eq!(column.decimal_places(), Some(1));
+    ///
+    /// let mut cells = column.map(|cell| cell.to_string());
+    /// assert_eq!(cells.next().as_deref(), Some("  1.2"));
+    /// assert_eq!(cells.next().as_deref(), Some("  1.2"));
+    /// assert_eq!(cells.next().as_deref(), Some("-20.5"));
+    /// assert_eq!(cells.next().as_deref(), Some("300.0"));
+    /// assert_eq!(cells.next(), None);
+    /// ```
+    pub const fn format_aligned_dp_f64(
+        values: &[f64],
+        decimal_places: usize,
+        mode: RoundingMode,
+    ) -> AlignedColumn<'_, f64> {
+        assert!(decimal_places <= 21, "`decimal_places` must be at most 21");
+        aligned_f64(values, Some(decimal_places), mode)
+    }
+
+    #[inline]
+    /// `f32` version of
+    /// [`format_aligned_dp_f64`](Self::format_aligned_dp_f64).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decimal_places` is more than 21.
+    pub const fn format_aligned_dp_f32(
+        values: &[f32],
+        decimal_places: usize,
+        mode: RoundingMode,
+    ) -> AlignedColumn<'_, f32> {
+        assert!(decimal_places <= 21, "`decimal_places` must be at most 21");
+        aligned_f32(values, Some(decimal_places), mode)
+    }
+
+    #[inline]
+    /// Formats `d` in `radix`, from 2 to 36, with the shortest digits that
+    /// parse back to `d`, like JavaScript's `Number.prototype.toString(radix)`.
+    ///
+    /// Radix 10 gives the same text as [`format_f64`](Self::format_f64).
+    /// Other radices use the lowercase letters as digits above 9, are never in
+    /// exponent form, and have no fraction for integers, so the text can be
+    /// long: `1e300` has 997 digits in radix 2. NaN and infinities are written
+    /// as by `format_f64`. [`parse::s2d_radix`](crate::parse::s2d_radix)
+    /// reads the text back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not from 2 to 36.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// assert_eq!(Formatter::format_f64_radix(255.0, 16).as_str(), "ff");
+    /// assert_eq!(Formatter::format_f64_radix(-0.5, 2).as_str(), "-0.1");
+    /// assert_eq!(
+    ///     Formatter::format_f64_radix(0.1, 3).as_str(),
+    ///     "0.0022002200220022002200220022002201"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_f64_radix(1e21, 36).as_str(),
+    ///     "5v1j4f4ds7a000"
+    /// );
+    /// assert_eq!(Formatter::format_f64_radix(1e21, 10).as_str(), "1e21");
+    /// ```
+    pub const fn format_f64_radix(d: f64, radix: u32) -> Formatted<BUFFER_LEN_RADIX> {
+        assert!(2 <= radix && radix <= 36, "`radix` must be from 2 to 36");
+        if is_nonfinite_f64(d) {
+            Formatted::nonfinite(format_nonfinite_f64(d))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_RADIX];
+
+            // Do format
+            let offset_full =
+                unsafe { raw::format64_radix_spec(d, radix, bytes.as_mut_ptr().cast::<u8>()) };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN_RADIX);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// `f32` version of [`format_f64_radix`](Self::format_f64_radix), with the
+    /// shortest digits that parse back to `f` as an `f32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not from 2 to 36.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// assert_eq!(Formatter::format_f32_radix(0.1, 16).as_str(), "0.199999a");
+    /// ```
+    pub const fn format_f32_radix(f: f32, radix: u32) -> Formatted<BUFFER_LEN_RADIX> {
+        assert!(2 <= radix && radix <= 36, "`radix` must be from 2 to 36");
+        if is_nonfinite_f32(f) {
+            Formatted::nonfinite(format_nonfinite_f32(f))
+        } else {
+            let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_RADIX];
+
+            // Do format
+            let offset_full =
+                unsafe { raw::format32_radix_spec(f, radix, bytes.as_mut_ptr().cast::<u8>()) };
+
+            debug_assert!(offset_full.initialized <= BUFFER_LEN_RADIX);
+
+            Formatted {
+                bytes,
+                meta: offset_full.meta,
+                initialized: offset_full.initialized,
+            }
+        }
+    }
+
+    #[inline]
+    /// The exact decimal value of `d`, with every digit of its binary value,
+    /// for numerical debugging and teaching: 0.1 is
+    /// "0.1000000000000000055511151231257827021181583404541015625".
+    ///
+    /// The result is written with its [`Display`](fmt::Display)
+    /// implementation, which streams the digits to any `fmt::Write` without
+    /// holding all of them, or with [`write_to`](ExactDecimal::write_to), to a
+    /// caller buffer of up to [`BUFFER_LEN_EXACT`] bytes. It is never in
+    /// exponent form, and integers have a zero fraction, as in "1.0". NaN and
+    /// infinities are written as by [`format_f64`](Self::format_f64).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// let exact = Formatter::exact_decimal_f64(0.1);
+    /// assert_eq!(
+    ///     exact.to_string(),
+    ///     "0.1000000000000000055511151231257827021181583404541015625"
+    /// );
+    ///
+    /// let mut buffer = [0u8; 32];
+    /// let exact = Formatter::exact_decimal_f64(1e23);
+    /// assert_eq!(
+    ///     exact.write_to(&mut buffer),
+    ///     Some("99999999999999991611392.0")
+    /// );
+    /// let exact = Formatter::exact_decimal_f64(f64::MIN_POSITIVE);
+    /// assert_eq!(exact.str_len(), 1024);
+    /// assert_eq!(exact.write_to(&mut buffer), None);
+    /// ```
+    pub const fn exact_decimal_f64(d: f64) -> ExactDecimal {
+        ExactDecimal { value: d }
+    }
+
+    #[inline]
+    /// `f32` version of [`exact_decimal_f64`](Self::exact_decimal_f64), whose
+    /// text fits in [`BUFFER_LEN_EXACT_F32`] bytes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// let exact = Formatter::exact_decimal_f32(0.1);
+    /// assert_eq!(exact.to_string(), "0.100000001490116119384765625");
+    /// ```
+    pub const fn exact_decimal_f32(f: f32) -> ExactDecimal {
+        ExactDecimal { value: f as f64 }
+    }
+
+    #[inline]
+    /// Const version of [`format`](Self::format), for IEEE 754 binary16
+    /// (`f16`) given as raw bits.
+    ///
+    /// This prints the shortest representation that round-trips as `f16`,
+    /// which is usually much shorter than formatting the value widened to
+    /// `f32`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 0.1 rounded to f16 is 0.0999755859375.
+    /// assert_eq!(Formatter::format_f16_bits(0x2e66).as_str(), "0.1");
+    /// assert_eq!(
+    ///     Formatter::format_f32(0.0999755859375).as_str(),
+    ///     "0.099975586"
+    /// );
+    /// assert_eq!(Formatter::format_f16_bits(0x7bff).as_str(), "65500.0");
+    /// assert_eq!(Formatter::format_f16_bits(0xfc00).as_str(), "-inf");
+    /// ```
+    pub const fn format_f16_bits(bits: u16) -> Formatted {
+        if is_nonfinite_f16(bits) {
+            Formatted::nonfinite(format_nonfinite_f16(bits))
+        } else {
+            Self::format_finite_f16_bits(bits)
+        }
+    }
+
+    #[inline]
+    /// `f16` version of [`format_finite_f64`](Self::format_finite_f64), for
+    /// IEEE 754 binary16 given as raw bits.
+    pub const fn format_finite_f16_bits(bits: u16) -> Formatted {
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+        // Do format
+        let offset_full = unsafe { raw::format16_spec(bits, bytes.as_mut_ptr().cast::<u8>()) };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+
+    #[inline]
+    /// Const version of [`format`](Self::format), for bfloat16 given as raw
+    /// bits.
+    ///
+    /// This prints the shortest representation that round-trips as bfloat16,
+    /// which is usually much shorter than formatting the value widened to
+    /// `f32`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 0.1 rounded to bfloat16 is 0.10009765625.
+    /// assert_eq!(Formatter::format_bf16_bits(0x3dcd).as_str(), "0.1");
+    /// assert_eq!(Formatter::format_f32(0.10009765625).as_str(), "0.100097656");
+    /// assert_eq!(Formatter::format_bf16_bits(0x7f7f).as_str(), "3.39e38");
+    /// assert_eq!(Formatter::format_bf16_bits(0xff80).as_str(), "-inf");
+    /// ```
+    pub const fn format_bf16_bits(bits: u16) -> Formatted {
+        if is_nonfinite_bf16(bits) {
+            Formatted::nonfinite(format_nonfinite_bf16(bits))
+        } else {
+            Self::format_finite_bf16_bits(bits)
+        }
+    }
+
+    #[inline]
+    /// bfloat16 version of [`format_finite_f64`](Self::format_finite_f64), for
+    /// bfloat16 given as raw bits.
+    pub const fn format_finite_bf16_bits(bits: u16) -> Formatted {
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+        // Do format
+        let offset_full = unsafe { raw::format_bf16_spec(bits, bytes.as_mut_ptr().cast::<u8>()) };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+
+    #[inline]
+    /// Const version of [`format`](Self::format), for a minifloat with the
+    /// given [`Layout`], such as FP8 E4M3, given as raw bits.
+    ///
+    /// This prints the shortest representation that round-trips in `layout`,
+    /// using the same layout rules as [`format_f32`](Self::format_f32). Bits
+    /// above the width of the layout are ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// use ryuu::minifloat::Layout;
+    ///
+    /// // 0.1 rounded to E4M3 is 0.1015625.
+    /// assert_eq!(
+    ///     Formatter::format_minifloat_bits(0x1d, Layout::E4M3).as_str(),
+    ///     "0.1"
+    /// );
+    /// // The largest finite E4M3 value is 448.
+    /// assert_eq!(
+    ///     Formatter::format_minifloat_bits(0xfe, Layout::E4M3).as_str(),
+    ///     "-450.0"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_minifloat_bits(0xff, Layout::E4M3).as_str(),
+    ///     "NaN"
+    /// );
+    /// // The largest finite E5M2 value is 57344.
+    /// assert_eq!(
+    ///     Formatter::format_minifloat_bits(0x7b, Layout::E5M2).as_str(),
+    ///     "60000.0"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_minifloat_bits(0xfc, Layout::E5M2).as_str(),
+    ///     "-inf"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_minifloat_bits(0x7, Layout::E2M1).as_str(),
+    ///     "6.0"
+    /// );
+    /// ```
+    pub const fn format_minifloat_bits(bits: u16, layout: Layout) -> Formatted {
+        if !layout.is_finite(bits) {
+            Formatted::nonfinite(format_nonfinite_minifloat(bits, layout))
+        } else {
+            Self::format_finite_minifloat_bits(bits, layout)
+        }
+    }
+
+    #[inline]
+    /// Minifloat version of [`format_finite_f64`](Self::format_finite_f64),
+    /// for a minifloat with the given [`Layout`] given as raw bits.
+    pub const fn format_finite_minifloat_bits(bits: u16, layout: Layout) -> Formatted {
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+        // Do format
+        let offset_full =
+            unsafe { raw::format_minifloat_spec(bits, layout, bytes.as_mut_ptr().cast::<u8>()) };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+
+    #[inline]
+    /// Const version of [`format`](Self::format), for an x87 80-bit extended
+    /// precision float given as its sign and exponent word and its 64-bit
+    /// mantissa including the explicit integer bit.
+    ///
+    /// This prints the shortest representation that round-trips as an
+    /// extended precision value, with up to 21 significant digits, using the
+    /// same layout rules as [`format_f64`](Self::format_f64).
+    ///
+    /// Encodings that are not canonical are handled the way the x87 FPU
+    /// since the 80387 handles them:
+    ///
+    /// * Pseudo-denormals (zero exponent, integer bit set) are printed with
+    ///   their value, which equals the normal number with a biased exponent of
+    ///   1.
+    /// * Unnormals (nonzero exponent, integer bit clear), including
+    ///   pseudo-infinities and pseudo-NaNs, are invalid operands and printed as
+    ///   "NaN".
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 0.1 rounded to extended precision.
+    /// assert_eq!(
+    ///     Formatter::format_x87_bits(0x3ffb, 0xcccccccccccccccd).as_str(),
+    ///     "0.1"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_x87_bits(0x4000, 0xc90fdaa22168c235).as_str(),
+    ///     "3.1415926535897932385"
+    /// );
+    /// // The smallest denormal and the same value as a pseudo-denormal.
+    /// assert_eq!(Formatter::format_x87_bits(0x0000, 1).as_str(), "4e-4951");
+    /// assert_eq!(
+    ///     Formatter::format_x87_bits(0x0000, 0x8000000000000000).as_str(),
+    ///     Formatter::format_x87_bits(0x0001, 0x8000000000000000).as_str()
+    /// );
+    /// // Unnormals.
+    /// assert_eq!(
+    ///     Formatter::format_x87_bits(0x3fff, 0x4000000000000000).as_str(),
+    ///     "NaN"
+    /// );
+    /// assert_eq!(Formatter::format_x87_bits(0x7fff, 0).as_str(), "NaN");
+    /// assert_eq!(
+    ///     Formatter::format_x87_bits(0xffff, 0x8000000000000000).as_str(),
+    ///     "-inf"
+    /// );
+    /// ```
+    pub const fn format_x87_bits(sign_exponent: u16, mantissa: u64) -> Formatted {
+        if is_nonfinite_x87(sign_exponent, mantissa) {
+            Formatted::nonfinite(format_nonfinite_x87(sign_exponent, mantissa))
+        } else {
+            Self::format_finite_x87_bits(sign_exponent, mantissa)
+        }
+    }
+
+    #[inline]
+    /// x87 version of [`format_finite_f64`](Self::format_finite_f64), for an
+    /// 80-bit extended precision float given as its sign and exponent word and
+    /// its 64-bit mantissa.
+    ///
+    /// Unnormals are printed with the value of their encoding instead of as
+    /// "NaN".
+    pub const fn format_finite_x87_bits(sign_exponent: u16, mantissa: u64) -> Formatted {
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+        // Do format
+        let offset_full =
+            unsafe { raw::format80_spec(sign_exponent, mantissa, bytes.as_mut_ptr().cast::<u8>()) };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+
+    #[inline]
+    /// Const version of [`format`](Self::format), for IEEE 754 binary128
+    /// (`f128`) given as raw bits.
+    ///
+    /// This prints the shortest representation that round-trips as `f128`,
+    /// with up to 36 significant digits, using the same layout rules as
+    /// [`format_f64`](Self::format_f64). The result does not fit in
+    /// [`BUFFER_LEN`] bytes, so it is returned in a buffer of
+    /// [`BUFFER_LEN_F128`] bytes instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 0.1 rounded to f128.
+    /// let bits = 0x3ffb999999999999999999999999999a;
+    /// assert_eq!(Formatter::format_f128_bits(bits).as_str(), "0.1");
+    ///
+    /// // 1/3 rounded to f128.
+    /// let bits = 0x3ffd5555555555555555555555555555;
+    /// assert_eq!(
+    ///     Formatter::format_f128_bits(bits).as_str(),
+    ///     "0.3333333333333333333333333333333333"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_f128_bits(0xffff0000000000000000000000000000).as_str(),
+    ///     "-inf"
+    /// );
+    /// ```
+    pub const fn format_f128_bits(bits: u128) -> Formatted<BUFFER_LEN_F128> {
+        if is_nonfinite_f128(bits) {
+            Formatted::nonfinite(format_nonfinite_f128(bits))
+        } else {
+            Self::format_finite_f128_bits(bits)
+        }
+    }
+
+    #[inline]
+    /// `f128` version of [`format_finite_f64`](Self::format_finite_f64), for
+    /// IEEE 754 binary128 given as raw bits.
+    pub const fn format_finite_f128_bits(bits: u128) -> Formatted<BUFFER_LEN_F128> {
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_F128];
+
+        // Do format
+        let offset_full = unsafe { raw::format128_spec(bits, bytes.as_mut_ptr().cast::<u8>()) };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN_F128);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+
+    #[inline]
+    /// Const version of [`format`](Self::format), for an IEEE 754 decimal64
+    /// float in the BID encoding, given as raw bits.
+    ///
+    /// This prints the value with the fewest digits and the same layout rules
+    /// as [`format_f64`](Self::format_f64), so all members of a cohort, such
+    /// as 1.2 and 1.20, print the same. A coefficient above 10^16 - 1 is not
+    /// canonical and prints as zero. NaN and infinity print as in
+    /// [`format_f64`](Self::format_f64). The output parses back to the same
+    /// value with [`s2decimal64_bits`].
+    ///
+    /// [`s2decimal64_bits`]: crate::parse::s2decimal64_bits
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 1.20, as the coefficient 120 and the exponent -2.
+    /// assert_eq!(
+    ///     Formatter::format_decimal64_bits(0x3180000000000078).as_str(),
+    ///     "1.2"
+    /// );
+    /// // 1e20, as the coefficient 1000 and the exponent 17.
+    /// assert_eq!(
+    ///     Formatter::format_decimal64_bits(0x33e00000000003e8).as_str(),
+    ///     "1e20"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_decimal64_bits(0xf800000000000000).as_str(),
+    ///     "-inf"
+    /// );
+    /// ```
+    pub const fn format_decimal64_bits(bits: u64) -> Formatted {
+        Self::format_decimal64_bits_with(bits, false)
+    }
+
+    #[inline]
+    /// Like [`format_decimal64_bits`](Self::format_decimal64_bits), but
+    /// prints the exact coefficient and exponent, keeping the trailing zeros
+    /// that tell the members of a cohort apart.
+    ///
+    /// A nonpositive exponent is printed as the number of digits after the
+    /// decimal point, which is left out for zero, as in "1.20" and "120",
+    /// unless there would be more than four zeros after the decimal point.
+    /// Otherwise, the coefficient is printed in exponent form, with all its
+    /// digits after the first one after the decimal point, as in "1.20e5" and
+    /// "1e-7". The output parses back to the same bits with
+    /// [`s2decimal64_bits`], except for NaN and noncanonical encodings.
+    ///
+    /// [`s2decimal64_bits`]: crate::parse::s2decimal64_bits
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// assert_eq!(
+    ///     Formatter::format_decimal64_bits_cohort(0x3180000000000078).as_str(),
+    ///     "1.20"
+    /// );
+    /// assert_eq!(
+    ///     Formatter::format_decimal64_bits_cohort(0x33e00000000003e8).as_str(),
+    ///     "1.000e20"
+    /// );
+    /// // Zero with the exponent -2.
+    /// assert_eq!(
+    ///     Formatter::format_decimal64_bits_cohort(0x3180000000000000).as_str(),
+    ///     "0.00"
+    /// );
+    /// ```
+    pub const fn format_decimal64_bits_cohort(bits: u64) -> Formatted {
+        Self::format_decimal64_bits_with(bits, true)
+    }
+
+    #[inline]
+    const fn format_decimal64_bits_with(bits: u64, cohort: bool) -> Formatted {
+        if is_nonfinite_decimal64(bits) {
+            return Formatted::nonfinite(format_nonfinite_decimal64(bits));
+        }
+
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+        // Do format
+        let offset_full =
+            unsafe { raw::format_decimal64_spec(bits, cohort, bytes.as_mut_ptr().cast::<u8>()) };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+
+    #[inline]
+    /// Const version of [`format`](Self::format), for an IEEE 754 decimal128
+    /// float in the BID encoding, given as raw bits.
+    ///
+    /// This is the decimal128 version of
+    /// [`format_decimal64_bits`](Self::format_decimal64_bits), with up to 34
+    /// significant digits. A coefficient above 10^34 - 1 is not canonical and
+    /// prints as zero. The output parses back to the same value with
+    /// [`s2decimal128_bits`].
+    ///
+    /// [`s2decimal128_bits`]: crate::parse::s2decimal128_bits
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // -0.1, as the coefficient 1 and the exponent -1.
+    /// let bits = 0xb03e0000000000000000000000000001;
+    /// assert_eq!(Formatter::format_decimal128_bits(bits).as_str(), "-0.1");
+    /// // The largest decimal128.
+    /// let bits = 0x5fffed09bead87c0378d8e63ffffffff;
+    /// assert_eq!(
+    ///     Formatter::format_decimal128_bits(bits).as_str(),
+    ///     "9.999999999999999999999999999999999e6144"
+    /// );
+    /// ```
+    pub const fn format_decimal128_bits(bits: u128) -> Formatted<BUFFER_LEN_F128> {
+        Self::format_decimal128_bits_with(bits, false)
+    }
+
+    #[inline]
+    /// Like [`format_decimal128_bits`](Self::format_decimal128_bits), but
+    /// prints the exact coefficient and exponent, with the layout of
+    /// [`format_decimal64_bits_cohort`](Self::format_decimal64_bits_cohort).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // 100, as the coefficient 100 and the exponent 0.
+    /// let bits = 0x30400000000000000000000000000064;
+    /// assert_eq!(Formatter::format_decimal128_bits(bits).as_str(), "100.0");
+    /// assert_eq!(
+    ///     Formatter::format_decimal128_bits_cohort(bits).as_str(),
+    ///     "100"
+    /// );
+    /// ```
+    pub const fn format_decimal128_bits_cohort(bits: u128) -> Formatted<BUFFER_LEN_F128> {
+        Self::format_decimal128_bits_with(bits, true)
+    }
+
+    #[inline]
+    const fn format_decimal128_bits_with(bits: u128, cohort: bool) -> Formatted<BUFFER_LEN_F128> {
+        if is_nonfinite_decimal128(bits) {
+            return Formatted::nonfinite(format_nonfinite_decimal128(bits));
+        }
+
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_F128];
+
+        // Do format
+        let offset_full =
+            unsafe { raw::format_decimal128_spec(bits, cohort, bytes.as_mut_ptr().cast::<u8>()) };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN_F128);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+
+    #[inline]
+    /// Formats the binary fixed-point number `raw * 2^-frac_bits`, as used in
+    /// the Q formats of DSPs, with the shortest decimal that tells it apart
+    /// from the adjacent ones.
+    ///
+    /// The output rounds back to `raw` with ties to even, which is what
+    /// [`s2fixed_point`] does, and of the shortest such decimals, it is the
+    /// one closest to the number. It is never in exponent form, and integers
+    /// end in ".0" as in [`format_f64`](Self::format_f64).
+    ///
+    /// [`s2fixed_point`]: crate::parse::s2fixed_point
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frac_bits` is above 64.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use ryuu::Formatter;
+    /// // Q15.16
+    /// assert_eq!(Formatter::format_fixed_point(72090, 16).as_str(), "1.1");
+    /// assert_eq!(Formatter::format_fixed_point(1, 16).as_str(), "0.00002");
+    /// assert_eq!(Formatter::format_fixed_point(-3 << 16, 16).as_str(), "-3.0");
+    /// // Q1.31
+    /// assert_eq!(Formatter::format_fixed_point(214748365, 31).as_str(), "0.1");
+    /// assert_eq!(
+    ///     Formatter::format_fixed_point(i32::MAX as i64, 31).as_str(),
+    ///     "0.9999999995"
+    /// );
+    /// ```
+    pub const fn format_fixed_point(raw: i64, frac_bits: u32) -> Formatted {
+        assert!(frac_bits <= 64, "`frac_bits` must be at most 64");
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+
+        // Do format
+        let offset_full = unsafe {
+            raw::format_fixed_point_spec(raw, frac_bits, bytes.as_mut_ptr().cast::<u8>())
+        };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// An iterator over numbers formatted with a shared number of decimal places,
+/// returned by [`Formatter::format_shared_dp_f64`] and
+/// [`Formatter::format_shared_dp_f32`].
+pub struct SharedDecimalPlaces<'a, F> {
+    values: &'a [F],
+    decimal_places: usize,
+    mode: RoundingMode,
+}
+
+impl<F> SharedDecimalPlaces<'_, F> {
+    #[inline]
+    /// The number of decimal places that every number is formatted with.
+    pub const fn decimal_places(&self) -> usize {
+        self.decimal_places
+    }
+}
+
+impl<F: Float> Iterator for SharedDecimalPlaces<'_, F> {
+    type Item = Formatted<BUFFER_LEN_COLUMN>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, rest) = self.values.split_first()?;
+        self.values = rest;
+        Some(first.format_dp(self.decimal_places, self.mode))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.values.len(), Some(self.values.len()))
+    }
+}
+
+impl<F: Float> DoubleEndedIterator for SharedDecimalPlaces<'_, F> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (&last, rest) = self.values.split_last()?;
+        self.values = rest;
+        Some(last.format_dp(self.decimal_places, self.mode))
+    }
+}
+
+impl<F: Float> ExactSizeIterator for SharedDecimalPlaces<'_, F> {}
+
+#[derive(Debug, Clone)]
+/// An iterator over numbers aligned on the decimal point, returned by
+/// [`Formatter::format_aligned_f64`], [`Formatter::format_aligned_dp_f64`]
+/// and their `f32` versions.
+pub struct AlignedColumn<'a, F> {
+    values: &'a [F],
+    decimal_places: Option<usize>,
+    mode: RoundingMode,
+    integer_width: usize,
+    fraction_width: usize,
+}
+
+impl<F> AlignedColumn<'_, F> {
+    #[inline]
+    /// The number of decimal places that every number is rounded to, or
+    /// `None` if the numbers keep their shortest digits.
+    pub const fn decimal_places(&self) -> Option<usize> {
+        self.decimal_places
+    }
+
+    #[inline]
+    /// The width of the column before the decimal points, including the
+    /// minus signs.
+    pub const fn integer_width(&self) -> usize {
+        self.integer_width
+    }
+
+    #[inline]
+    /// The width of the column from the decimal points on, including the
+    /// exponents.
+    pub const fn fraction_width(&self) -> usize {
+        self.fraction_width
+    }
+
+    #[inline]
+    /// The width of every cell of the column.
+    pub const fn width(&self) -> usize {
+        self.integer_width + self.fraction_width
+    }
+
+    #[inline]
+    const fn cell(&self, formatted: Formatted<BUFFER_LEN_COLUMN>) -> AlignedCell {
+        let offset = formatted.alignment_offset();
+        AlignedCell {
+            leading_spaces: self.integer_width - offset,
+            trailing_spaces: self.fraction_width - (formatted.initialized - offset),
+            formatted,
+        }
+    }
+}
+
+impl<F: Float> Iterator for AlignedColumn<'_, F> {
+    type Item = AlignedCell;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, rest) = self.values.split_first()?;
+        self.values = rest;
+        Some(self.cell(first.format_column(self.decimal_places, self.mode)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.values.len(), Some(self.values.len()))
+    }
+}
+
+impl<F: Float> DoubleEndedIterator for AlignedColumn<'_, F> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (&last, rest) = self.values.split_last()?;
+        self.values = rest;
+        Some(self.cell(last.format_column(self.decimal_places, self.mode)))
+    }
+}
+
+impl<F: Float> ExactSizeIterator for AlignedColumn<'_, F> {}
+
+#[derive(Debug, Clone, Copy)]
+/// A number of an [`AlignedColumn`], with the spaces around it that align it.
+pub struct AlignedCell {
+    formatted: Formatted<BUFFER_LEN_COLUMN>,
+    leading_spaces: usize,
+    trailing_spaces: usize,
+}
+
+impl AlignedCell {
+    #[inline]
+    /// The number without the spaces.
+    pub const fn formatted(&self) -> &Formatted<BUFFER_LEN_COLUMN> {
+        &self.formatted
+    }
+
+    #[inline]
+    /// The number of spaces before the number.
+    pub const fn leading_spaces(&self) -> usize {
+        self.leading_spaces
+    }
+
+    #[inline]
+    /// The number of spaces after the number.
+    pub const fn trailing_spaces(&self) -> usize {
+        self.trailing_spaces
+    }
+
+    #[inline]
+    /// The length of the text with the spaces, which is the
+    /// [`width`](AlignedColumn::width) of the column.
+    pub const fn str_len(&self) -> usize {
+        self.leading_spaces + self.formatted.initialized + self.trailing_spaces
+    }
+
+    #[inline]
+    /// Writes the text with the spaces to the start of `buffer` and returns
+    /// it, or returns `None` if `buffer` is shorter than
+    /// [`str_len`](Self::str_len).
+    pub const fn write_to<'a>(&self, buffer: &'a mut [u8]) -> Option<&'a str> {
+        let len = self.str_len();
+        if buffer.len() < len {
+            return None;
+        }
+        let mut offset = 0;
+        while offset < self.leading_spaces {
+            buffer[offset] = b' ';
+            offset += 1;
+        }
+        offset = put(buffer, offset, self.formatted.as_bytes());
+        while offset < len {
+            buffer[offset] = b' ';
+            offset += 1;
+        }
+        Some(unsafe { str::from_utf8_unchecked(slice::from_raw_parts(buffer.as_ptr(), len)) })
+    }
+}
+
+impl fmt::Display for AlignedCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:leading$}{}{:trailing$}",
+            "",
+            self.formatted.as_str(),
+            "",
+            leading = self.leading_spaces,
+            trailing = self.trailing_spaces,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// The exact decimal value of a floating point number, returned by
+/// [`Formatter::exact_decimal_f64`] and [`Formatter::exact_decimal_f32`].
+pub struct ExactDecimal {
+    value: f64,
+}
+
+impl ExactDecimal {
+    #[inline]
+    /// The length of the text, which is at most [`BUFFER_LEN_EXACT`].
+    pub const fn str_len(&self) -> usize {
+        if is_nonfinite_f64(self.value) {
+            return format_nonfinite_f64(self.value).len();
+        }
+        let digits = ExactDigits::new(self.value);
+        let integer_length = digits.integer_length();
+        let fraction_length = digits.fraction_length();
+        self.value.is_sign_negative() as usize
+            + if integer_length == 0 {
+                1
+            } else {
+                integer_length
+            }
+            + 1
+            + if fraction_length == 0 {
+                1
+            } else {
+                fraction_length
+            }
+    }
+
+    #[inline]
+    /// Writes the text to the start of `buffer` and returns it, or returns
+    /// `None` if `buffer` is shorter than [`str_len`](Self::str_len).
+    pub const fn write_to<'a>(&self, buffer: &'a mut [u8]) -> Option<&'a str> {
+        if buffer.len() < self.str_len() {
+            return None;
+        }
+        let len = if is_nonfinite_f64(self.value) {
+            let nonfinite_formatted = format_nonfinite_f64(self.value);
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    nonfinite_formatted.as_ptr(),
+                    buffer.as_mut_ptr(),
+                    nonfinite_formatted.len(),
+                );
+            }
+            nonfinite_formatted.len()
+        } else {
+            unsafe { raw::format64_exact(self.value, buffer.as_mut_ptr()) }
+        };
+        Some(unsafe { str::from_utf8_unchecked(slice::from_raw_parts(buffer.as_ptr(), len)) })
+    }
+}
+
+impl fmt::Display for ExactDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_nonfinite_f64(self.value) {
+            return f.write_str(format_nonfinite_f64(self.value));
+        }
+        if self.value.is_sign_negative() {
+            f.write_str("-")?;
+        }
+
+        let mut digits = ExactDigits::new(self.value);
+        let mut chunk = [0u8; CHUNK_DIGITS];
+        if digits.integer_length() == 0 {
+            f.write_str("0")?;
+        }
+        loop {
+            let length = unsafe { digits.next_integer(chunk.as_mut_ptr()) };
+            if length == 0 {
+                break;
+            }
+            f.write_str(unsafe { str::from_utf8_unchecked(&chunk[..length]) })?;
+        }
+        f.write_str(".")?;
+        if digits.fraction_length() == 0 {
+            f.write_str("0")?;
+        }
+        loop {
+            let length = unsafe { digits.next_fraction(chunk.as_mut_ptr()) };
+            if length == 0 {
+                break;
+            }
+            f.write_str(unsafe { str::from_utf8_unchecked(&chunk[..length]) })?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(private_bounds)]
+/// A floating point number, f32 or f64, that can be formatted to text.
+///
+/// This trait is sealed and cannot be implemented for types outside of the
+/// `ryu` crate.
+pub trait Float: Sealed {}
+
+impl Float for f32 {}
+impl Float for f64 {}
+
+trait Sealed: Copy {
+    fn format(self) -> Formatted;
+
+    /// Formats with any number of decimal places that
+    /// `shared_decimal_places_*` may return, which may be more than
+    /// [`MAX_DECIMAL_PLACES`].
+    fn format_dp(self, decimal_places: usize, mode: RoundingMode) -> Formatted<BUFFER_LEN_COLUMN>;
+
+    /// Formats a cell of an `AlignedColumn`, with the shortest digits if
+    /// there are no decimal places.
+    fn format_column(
+        self,
+        decimal_places: Option<usize>,
+        mode: RoundingMode,
+    ) -> Formatted<BUFFER_LEN_COLUMN>;
+}
+
+impl Sealed for f32 {
+    #[inline]
+    fn format(self) -> Formatted {
+        Formatter::format_f32(self)
+    }
+
+    #[inline]
+    fn format_dp(self, decimal_places: usize, mode: RoundingMode) -> Formatted<BUFFER_LEN_COLUMN> {
+        format_f32_dp(self, decimal_places, mode)
+    }
+
+    #[inline]
+    fn format_column(
+        self,
+        decimal_places: Option<usize>,
+        mode: RoundingMode,
+    ) -> Formatted<BUFFER_LEN_COLUMN> {
+        format_f32_column(self, decimal_places, mode)
+    }
+}
+
+impl Sealed for f64 {
+    #[inline]
+    fn format(self) -> Formatted {
+        Formatter::format_f64(self)
+    }
+
+    #[inline]
+    fn format_dp(self, decimal_places: usize, mode: RoundingMode) -> Formatted<BUFFER_LEN_COLUMN> {
+        format_f64_dp(self, decimal_places, mode)
+    }
+
+    #[inline]
+    fn format_column(
+        self,
+        decimal_places: Option<usize>,
+        mode: RoundingMode,
+    ) -> Formatted<BUFFER_LEN_COLUMN> {
+        format_f64_column(self, decimal_places, mode)
+    }
+}
+
+// `Sealed::format_dp` of `f32`, which can be called in const.
+#[inline]
+const fn format_f32_dp(
+    f: f32,
+    decimal_places: usize,
+    mode: RoundingMode,
+) -> Formatted<BUFFER_LEN_COLUMN> {
+    if is_nonfinite_f32(f) {
+        Formatted::nonfinite(format_nonfinite_f32(f))
+    } else {
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_COLUMN];
+
+        let offset_full = unsafe {
+            raw::format32_shortest_dp_spec(f, decimal_places, mode, bytes.as_mut_ptr().cast::<u8>())
+        };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN_COLUMN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+}
+
+// `Sealed::format_dp` of `f64`, which can be called in const.
+#[inline]
+const fn format_f64_dp(
+    d: f64,
+    decimal_places: usize,
+    mode: RoundingMode,
+) -> Formatted<BUFFER_LEN_COLUMN> {
+    if is_nonfinite_f64(d) {
+        Formatted::nonfinite(format_nonfinite_f64(d))
+    } else {
+        let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN_COLUMN];
+
+        let offset_full = unsafe {
+            raw::format64_shortest_dp_spec(d, decimal_places, mode, bytes.as_mut_ptr().cast::<u8>())
+        };
+
+        debug_assert!(offset_full.initialized <= BUFFER_LEN_COLUMN);
+
+        Formatted {
+            bytes,
+            meta: offset_full.meta,
+            initialized: offset_full.initialized,
+        }
+    }
+}
+
+// `Sealed::format_column` of `f32`, which can be called in const.
+#[inline]
+const fn format_f32_column(
+    f: f32,
+    decimal_places: Option<usize>,
+    mode: RoundingMode,
+) -> Formatted<BUFFER_LEN_COLUMN> {
+    match decimal_places {
+        Some(decimal_places) => format_f32_dp(f, decimal_places, mode),
+        None => Formatter::format_f32(f).resize(),
+    }
+}
+
+// `Sealed::format_column` of `f64`, which can be called in const.
+#[inline]
+const fn format_f64_column(
+    d: f64,
+    decimal_places: Option<usize>,
+    mode: RoundingMode,
+) -> Formatted<BUFFER_LEN_COLUMN> {
+    match decimal_places {
+        Some(decimal_places) => format_f64_dp(d, decimal_places, mode),
+        None => Formatter::format_f64(d).resize(),
+    }
+}
+
+// The column of `Formatter::format_aligned_f32` and
+// `Formatter::format_aligned_dp_f32`.
+#[inline]
+const fn aligned_f32(
+    values: &[f32],
+    decimal_places: Option<usize>,
+    mode: RoundingMode,
+) -> AlignedColumn<'_, f32> {
+    let mut integer_width = 0;
+    let mut fraction_width = 0;
+    let mut i = 0;
+    while i < values.len() {
+        let formatted = format_f32_column(values[i], decimal_places, mode);
+        let offset = formatted.alignment_offset();
+        if offset > integer_width {
+            integer_width = offset;
+        }
+        if formatted.initialized - offset > fraction_width {
+            fraction_width = formatted.initialized - offset;
+        }
+        i += 1;
+    }
+    AlignedColumn {
+        values,
+        decimal_places,
+        mode,
+        integer_width,
+        fraction_width,
+    }
+}
+
+// The column of `Formatter::format_aligned_f64` and
+// `Formatter::format_aligned_dp_f64`.
+#[inline]
+const fn aligned_f64(
+    values: &[f64],
+    decimal_places: Option<usize>,
+    mode: RoundingMode,
+) -> AlignedColumn<'_, f64> {
+    let mut integer_width = 0;
+    let mut fraction_width = 0;
+    let mut i = 0;
+    while i < values.len() {
+        let formatted = format_f64_column(values[i], decimal_places, mode);
+        let offset = formatted.alignment_offset();
+        if offset > integer_width {
+            integer_width = offset;
+        }
+        if formatted.initialized - offset > fraction_width {
+            fraction_width = formatted.initialized - offset;
+        }
+        i += 1;
+    }
+    AlignedColumn {
+        values,
+        decimal_places,
+        mode,
+        integer_width,
+        fraction_width,
+    }
+}
+
+// === nonfinite float helpers ===
+
+const NAN: &str = "NaN";
+const INFINITY: &str = "inf";
+const NEG_INFINITY: &str = "-inf";
+
+#[inline]
+const fn is_nonfinite_f32(f: f32) -> bool {
+    const EXP_MASK: u32 = 0x7f800000;
+    let bits = f.to_bits();
+    bits & EXP_MASK == EXP_MASK
+}
+
+#[inline]
+const fn is_nonfinite_f16(bits: u16) -> bool {
+    const EXP_MASK: u16 = 0x7c00;
+    bits & EXP_MASK == EXP_MASK
+}
+
+#[inline]
+const fn is_nonfinite_bf16(bits: u16) -> bool {
+    const EXP_MASK: u16 = 0x7f80;
+    bits & EXP_MASK == EXP_MASK
+}
+
+#[inline]
+const fn is_nonfinite_x87(sign_exponent: u16, mantissa: u64) -> bool {
+    const EXP_MASK: u16 = 0x7fff;
+    const INTEGER_BIT: u64 = 1 << 63;
+    let exponent = sign_exponent & EXP_MASK;
+    exponent == EXP_MASK || (exponent != 0 && mantissa & INTEGER_BIT == 0)
+}
+
+#[inline]
+const fn is_nonfinite_f128(bits: u128) -> bool {
+    const EXP_MASK: u128 = 0x7fff << 112;
+    bits & EXP_MASK == EXP_MASK
+}
+
+#[inline]
+const fn is_nonfinite_f64(d: f64) -> bool {
+    const EXP_MASK: u64 = 0x7ff0000000000000;
+    let bits = d.to_bits();
+    bits & EXP_MASK == EXP_MASK
+}
 
 #[cold]
 #[inline]
@@ -708,6 +3376,146 @@ const fn format_nonfinite_f32(f: f32) -> &'static str {
     }
 }
 
+// Writes a NaN with the given sign and trailing significand, in which
+// `quiet_bit` is the quiet bit.
+#[cold]
+const fn format_nan(negative: bool, significand: u64, quiet_bit: u64) -> Formatted {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut bytes = [MaybeUninit::uninit(); BUFFER_LEN];
+    let mut index = 0;
+    if negative {
+        bytes[index] = MaybeUninit::new(b'-');
+        index += 1;
+    }
+    if significand & quiet_bit == 0 {
+        bytes[index] = MaybeUninit::new(b's');
+        index += 1;
+    }
+    let mut k = 0;
+    while k < 3 {
+        bytes[index + k] = MaybeUninit::new(b"nan"[k]);
+        k += 1;
+    }
+    index += 3;
+
+    if significand != quiet_bit {
+        bytes[index] = MaybeUninit::new(b'(');
+        bytes[index + 1] = MaybeUninit::new(b'0');
+        bytes[index + 2] = MaybeUninit::new(b'x');
+        index += 3;
+        // A signaling NaN has a nonzero significand.
+        let mut shift = (63 - significand.leading_zeros()) / 4 * 4;
+        loop {
+            bytes[index] = MaybeUninit::new(HEX_DIGITS[(significand >> shift) as usize & 0xf]);
+            index += 1;
+            if shift == 0 {
+                break;
+            }
+            shift -= 4;
+        }
+        bytes[index] = MaybeUninit::new(b')');
+        index += 1;
+    }
+
+    Formatted {
+        bytes,
+        meta: FormattedMeta::Nonfinite,
+        initialized: index,
+    }
+}
+
+#[cold]
+#[inline]
+const fn format_nonfinite_f16(bits: u16) -> &'static str {
+    const MANTISSA_MASK: u16 = 0x03ff;
+    const SIGN_MASK: u16 = 0x8000;
+    if bits & MANTISSA_MASK != 0 {
+        NAN
+    } else if bits & SIGN_MASK != 0 {
+        NEG_INFINITY
+    } else {
+        INFINITY
+    }
+}
+
+#[cold]
+#[inline]
+const fn format_nonfinite_bf16(bits: u16) -> &'static str {
+    const MANTISSA_MASK: u16 = 0x007f;
+    const SIGN_MASK: u16 = 0x8000;
+    if bits & MANTISSA_MASK != 0 {
+        NAN
+    } else if bits & SIGN_MASK != 0 {
+        NEG_INFINITY
+    } else {
+        INFINITY
+    }
+}
+
+#[cold]
+#[inline]
+const fn format_nonfinite_minifloat(bits: u16, layout: Layout) -> &'static str {
+    if layout.is_nan(bits) {
+        NAN
+    } else if layout.is_negative(bits) {
+        NEG_INFINITY
+    } else {
+        INFINITY
+    }
+}
+
+#[cold]
+#[inline]
+const fn format_nonfinite_x87(sign_exponent: u16, mantissa: u64) -> &'static str {
+    const EXP_MASK: u16 = 0x7fff;
+    const SIGN_MASK: u16 = 0x8000;
+    if sign_exponent & EXP_MASK != EXP_MASK || mantissa != 1 << 63 {
+        NAN
+    } else if sign_exponent & SIGN_MASK != 0 {
+        NEG_INFINITY
+    } else {
+        INFINITY
+    }
+}
+
+#[cold]
+#[inline]
+const fn format_nonfinite_f128(bits: u128) -> &'static str {
+    const MANTISSA_MASK: u128 = (1 << 112) - 1;
+    const SIGN_MASK: u128 = 1 << 127;
+    if bits & MANTISSA_MASK != 0 {
+        NAN
+    } else if bits & SIGN_MASK != 0 {
+        NEG_INFINITY
+    } else {
+        INFINITY
+    }
+}
+
+#[cold]
+#[inline]
+const fn format_nonfinite_decimal64(bits: u64) -> &'static str {
+    if is_nan_decimal64(bits) {
+        NAN
+    } else if bits >> 63 != 0 {
+        NEG_INFINITY
+    } else {
+        INFINITY
+    }
+}
+
+#[cold]
+#[inline]
+const fn format_nonfinite_decimal128(bits: u128) -> &'static str {
+    if is_nan_decimal128(bits) {
+        NAN
+    } else if bits >> 127 != 0 {
+        NEG_INFINITY
+    } else {
+        INFINITY
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::approx_constant)]
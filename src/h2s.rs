@@ -0,0 +1,39 @@
+// Shortest round-trip formatting of 16-bit floats: IEEE 754 binary16 (half
+// precision) and bfloat16.
+//
+// Every 16-bit float value is also a binary32 value, but its rounding interval
+// is much wider, so formatting it as `f32` prints more digits than needed to
+// round-trip. The interval logic of `f2s::f2d` works unchanged for narrower
+// layouts, including its power-of-5 tables.
+
+use crate::f2s::{f2d_layout, FloatingDecimal32};
+
+pub const HALF_MANTISSA_BITS: u32 = 10;
+pub const HALF_EXPONENT_BITS: u32 = 5;
+const HALF_BIAS: i32 = 15;
+
+pub const BFLOAT_MANTISSA_BITS: u32 = 7;
+pub const BFLOAT_EXPONENT_BITS: u32 = 8;
+const BFLOAT_BIAS: i32 = 127;
+
+#[inline]
+pub const fn h2d(ieee_mantissa: u32, ieee_exponent: u32) -> FloatingDecimal32 {
+    f2d_layout(ieee_mantissa, ieee_exponent, HALF_MANTISSA_BITS, HALF_BIAS)
+}
+
+#[inline]
+pub const fn bf2d(ieee_mantissa: u32, ieee_exponent: u32) -> FloatingDecimal32 {
+    f2d_layout(
+        ieee_mantissa,
+        ieee_exponent,
+        BFLOAT_MANTISSA_BITS,
+        BFLOAT_BIAS,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+
+    include!("../unittests/h2s_test.rs");
+}
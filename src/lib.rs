@@ -22,7 +22,14 @@
     clippy::wildcard_imports
 )]
 
+mod bid;
 mod common;
+mod d2directed;
+mod d2exact;
+mod d2f;
+mod d2fixed;
+mod d2radix;
+mod d2range;
 mod d2s;
 #[cfg(any(test, not(feature = "small"), feature = "feat-exp-parse"))]
 mod d2s_full_table;
@@ -32,9 +39,16 @@ mod d2s_small_table;
 mod digit_table;
 mod f2s;
 mod f2s_intrinsics;
+mod fixed2d;
 pub mod format;
+mod h2s;
+pub mod minifloat;
 #[cfg(any(test, feature = "feat-exp-parse"))]
 pub mod parse;
+pub mod pattern;
+mod q2s;
+mod q2s_intrinsics;
+mod q2s_table;
 pub mod raw;
 
 pub use crate::format::{Formatted, Formatter};
@@ -0,0 +1,237 @@
+//! Small binary floating point formats with a configurable layout, such as
+//! the OCP 8-bit floats (FP8 E4M3 and E5M2) and the MX 6-bit and 4-bit floats
+//! used by quantized models.
+//!
+//! A [`Layout`] describes the format, and is passed to
+//! [`Formatter::format_minifloat_bits`](crate::Formatter::format_minifloat_bits)
+//! to print the shortest representation that round-trips, or to
+//! `parse::s2minifloat_bits` to parse a number with correct rounding.
+//!
+//! ## Example
+//!
+//! ```
+//! use ryuu::minifloat::Layout;
+//! use ryuu::Formatter;
+//!
+//! // 0b0_0111_010 is 1.25 in E4M3, where its neighbors are 1.125 and 1.375,
+//! // so "1.2" is enough to round-trip.
+//! assert_eq!(
+//!     Formatter::format_minifloat_bits(0x3a, Layout::E4M3).as_str(),
+//!     "1.2"
+//! );
+//! assert_eq!(
+//!     Formatter::format_minifloat_bits(0x3e, Layout::E5M2).as_str(),
+//!     "1.5"
+//! );
+//! // E4M3 has no infinity, and a single NaN encoding per sign. Its largest
+//! // finite value is 448.
+//! assert_eq!(
+//!     Formatter::format_minifloat_bits(0x7e, Layout::E4M3).as_str(),
+//!     "450.0"
+//! );
+//! assert_eq!(
+//!     Formatter::format_minifloat_bits(0x7f, Layout::E4M3).as_str(),
+//!     "NaN"
+//! );
+//! assert_eq!(
+//!     Formatter::format_minifloat_bits(0x7c, Layout::E5M2).as_str(),
+//!     "inf"
+//! );
+//! ```
+
+use crate::f2s::{f2d_layout, FloatingDecimal32};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How a [`Layout`] encodes infinities and NaNs.
+pub enum Special {
+    /// As in IEEE 754: the largest exponent is reserved, and encodes infinity
+    /// with a zero mantissa and NaN otherwise, e.g. E5M2.
+    Ieee,
+
+    /// There is no infinity, and only the largest exponent with all mantissa
+    /// bits set encodes NaN, e.g. E4M3.
+    NanOnly,
+
+    /// Every encoding is a finite number, e.g. the MX FP6 and FP4 formats.
+    FiniteOnly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The layout of a small binary floating point format: a sign bit followed by
+/// the biased exponent and the mantissa without its implicit leading bit,
+/// stored in the low bits of a `u16`.
+///
+/// A biased exponent of zero encodes zero and the subnormal numbers.
+pub struct Layout {
+    exponent_bits: u32,
+    mantissa_bits: u32,
+    bias: i32,
+    special: Special,
+}
+
+// The range of the binary exponent of the least significant mantissa bit
+// that the interval logic of `f2s::f2d` supports, the one of `f32`.
+const MIN_E2: i32 = 1 - 127 - 23;
+const MAX_E2: i32 = 254 - 127 - 23;
+
+impl Layout {
+    /// MX FP4 E2M1: 2 exponent bits, 1 mantissa bit, bias 1, no special
+    /// values. The largest value is 6.
+    pub const E2M1: Self = Self::new(2, 1, 1, Special::FiniteOnly);
+    /// MX FP6 E2M3: 2 exponent bits, 3 mantissa bits, bias 1, no special
+    /// values. The largest value is 7.5.
+    pub const E2M3: Self = Self::new(2, 3, 1, Special::FiniteOnly);
+    /// MX FP6 E3M2: 3 exponent bits, 2 mantissa bits, bias 3, no special
+    /// values. The largest value is 28.
+    pub const E3M2: Self = Self::new(3, 2, 3, Special::FiniteOnly);
+    /// OCP FP8 E4M3: 4 exponent bits, 3 mantissa bits, bias 7, no infinity.
+    /// The largest finite value is 448.
+    pub const E4M3: Self = Self::new(4, 3, 7, Special::NanOnly);
+    /// OCP FP8 E5M2: 5 exponent bits, 2 mantissa bits, bias 15, IEEE 754
+    /// special values. The largest finite value is 57344.
+    pub const E5M2: Self = Self::new(5, 2, 15, Special::Ieee);
+
+    /// Creates a layout with the given number of exponent and mantissa bits,
+    /// exponent bias and special value encoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no exponent bit, if the format including the sign
+    /// bit is wider than 16 bits, or if the range of its finite values exceeds
+    /// the one of `f32`.
+    pub const fn new(exponent_bits: u32, mantissa_bits: u32, bias: i32, special: Special) -> Self {
+        assert!(exponent_bits >= 1, "a minifloat needs an exponent bit");
+        assert!(
+            1 + exponent_bits + mantissa_bits <= 16,
+            "a minifloat is at most 16 bits wide"
+        );
+        let layout = Self {
+            exponent_bits,
+            mantissa_bits,
+            bias,
+            special,
+        };
+        assert!(
+            1 - bias - mantissa_bits as i32 >= MIN_E2
+                && layout.max_exponent() as i32 - bias - mantissa_bits as i32 <= MAX_E2,
+            "the minifloat exceeds the range of f32"
+        );
+        layout
+    }
+
+    /// The number of exponent bits.
+    pub const fn exponent_bits(&self) -> u32 {
+        self.exponent_bits
+    }
+
+    /// The number of mantissa bits, not counting the implicit leading bit.
+    pub const fn mantissa_bits(&self) -> u32 {
+        self.mantissa_bits
+    }
+
+    /// The exponent bias.
+    pub const fn bias(&self) -> i32 {
+        self.bias
+    }
+
+    /// The encoding of infinities and NaNs.
+    pub const fn special(&self) -> Special {
+        self.special
+    }
+
+    /// The total number of bits, including the sign bit.
+    pub const fn width(&self) -> u32 {
+        1 + self.exponent_bits + self.mantissa_bits
+    }
+
+    /// Returns whether `bits` encodes NaN. Bits above [`width`](Self::width)
+    /// are ignored.
+    pub const fn is_nan(&self, bits: u16) -> bool {
+        let magnitude = self.magnitude(bits);
+        match self.special {
+            Special::Ieee => magnitude > self.infinity(),
+            Special::NanOnly => magnitude == self.magnitude_mask(),
+            Special::FiniteOnly => false,
+        }
+    }
+
+    /// Returns whether `bits` encodes positive or negative infinity. Bits
+    /// above [`width`](Self::width) are ignored.
+    pub const fn is_infinite(&self, bits: u16) -> bool {
+        matches!(self.special, Special::Ieee) && self.magnitude(bits) == self.infinity()
+    }
+
+    /// Returns whether `bits` encodes a finite number. Bits above
+    /// [`width`](Self::width) are ignored.
+    pub const fn is_finite(&self, bits: u16) -> bool {
+        !self.is_nan(bits) && !self.is_infinite(bits)
+    }
+
+    /// The encoding of the largest finite value.
+    pub const fn max_finite(&self) -> u16 {
+        match self.special {
+            Special::Ieee => self.infinity() - 1,
+            Special::NanOnly => self.magnitude_mask() - 1,
+            Special::FiniteOnly => self.magnitude_mask(),
+        }
+    }
+
+    /// The biased exponent of the largest finite values.
+    const fn max_exponent(&self) -> u32 {
+        match self.special {
+            Special::Ieee => (1 << self.exponent_bits) - 2,
+            Special::NanOnly | Special::FiniteOnly => (1 << self.exponent_bits) - 1,
+        }
+    }
+
+    /// The encoding of positive infinity in the IEEE 754 style.
+    const fn infinity(&self) -> u16 {
+        (((1u32 << self.exponent_bits) - 1) << self.mantissa_bits) as u16
+    }
+
+    const fn magnitude_mask(&self) -> u16 {
+        ((1u32 << (self.exponent_bits + self.mantissa_bits)) - 1) as u16
+    }
+
+    pub(crate) const fn sign_bit(&self) -> u16 {
+        1 << (self.exponent_bits + self.mantissa_bits)
+    }
+
+    pub(crate) const fn magnitude(&self, bits: u16) -> u16 {
+        bits & self.magnitude_mask()
+    }
+
+    pub(crate) const fn is_negative(&self, bits: u16) -> bool {
+        bits & self.sign_bit() != 0
+    }
+
+    pub(crate) const fn ieee_mantissa(&self, bits: u16) -> u32 {
+        bits as u32 & ((1u32 << self.mantissa_bits) - 1)
+    }
+
+    pub(crate) const fn ieee_exponent(&self, bits: u16) -> u32 {
+        (bits as u32 >> self.mantissa_bits) & ((1u32 << self.exponent_bits) - 1)
+    }
+}
+
+// Same as `f2s::f2d`, for a minifloat with the given layout.
+#[inline]
+pub(crate) const fn m2d(
+    ieee_mantissa: u32,
+    ieee_exponent: u32,
+    layout: Layout,
+) -> FloatingDecimal32 {
+    f2d_layout(
+        ieee_mantissa,
+        ieee_exponent,
+        layout.mantissa_bits,
+        layout.bias,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+
+    include!("../unittests/minifloat_test.rs");
+}
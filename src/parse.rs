@@ -1,13 +1,25 @@
 //! Experimental parsing functions.
 
+mod bid;
+mod decimal;
+mod radix;
+
 use core::fmt::{self, Display};
 
+use self::bid::{
+    decimal128_from_parts, decimal64_from_parts, parse_decimal_float, ParsedDecimal, DECIMAL128,
+    DECIMAL64,
+};
+use self::decimal::{BinaryFormat, Decimal};
+use self::radix::radix_to_binary;
 use crate::common::{ceil_log2_pow5, log2_pow5};
 use crate::d2s_intrinsics::{mul_shift_64, multiple_of_power_of_2, multiple_of_power_of_5};
 use crate::f2s_intrinsics::{
     mul_pow5_div_pow2, mul_pow5_inv_div_pow2, multiple_of_power_of_2_32, multiple_of_power_of_5_32,
 };
-use crate::{d2s, f2s};
+use crate::format::NumberingSystem;
+use crate::minifloat::{Layout, Special};
+use crate::{d2s, f2s, h2s};
 
 const FLOAT_EXPONENT_BIAS: usize = 127;
 const DOUBLE_EXPONENT_BIAS: usize = 1023;
@@ -20,6 +32,15 @@ const fn floor_log2_d(value: u64) -> u32 {
     63_u32.wrapping_sub(value.leading_zeros())
 }
 
+// `Ord::max` is not `const`.
+const fn max_i32(a: i32, b: i32) -> i32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Error {
     InputTooShort,
@@ -34,14 +55,20 @@ impl PartialEq for Error {
     }
 }
 
-impl Display for Error {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        let msg = match self {
+impl Error {
+    /// Returns a short description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
             Error::InputTooShort => "input too short",
             Error::InputTooLong => "input too long",
             Error::MalformedInput => "malformed input",
-        };
-        formatter.write_str(msg)
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.as_str())
     }
 }
 
@@ -51,7 +78,7 @@ impl Display for Error {
 ///
 /// This function can return an `Error` if the input is malformed, too short, or
 /// too long.
-pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
+pub const fn s2f(buffer: &[u8]) -> Result<f32, Error> {
     let len = buffer.len();
     if len == 0 {
         return Err(Error::InputTooShort);
@@ -67,12 +94,13 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
     let mut signed_e = false;
 
     let mut i = 0;
-    if unsafe { *buffer.get_unchecked(0) } == b'-' {
+    if buffer[0] == b'-' {
         signed_m = true;
         i += 1;
     }
 
-    while let Some(c) = buffer.get(i).copied() {
+    while i < len {
+        let c = buffer[i];
         if c == b'.' {
             if dot_index != len {
                 return Err(Error::MalformedInput);
@@ -94,18 +122,21 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
         i += 1;
     }
 
-    if let Some(b'e' | b'E') = buffer.get(i) {
+    if i < len && (buffer[i] == b'e' || buffer[i] == b'E') {
         e_index = i;
         i += 1;
-        match buffer.get(i) {
-            Some(b'-') => {
-                signed_e = true;
-                i += 1;
+        if i < len {
+            match buffer[i] {
+                b'-' => {
+                    signed_e = true;
+                    i += 1;
+                }
+                b'+' => i += 1,
+                _ => {}
             }
-            Some(b'+') => i += 1,
-            _ => {}
         }
-        while let Some(c) = buffer.get(i).copied() {
+        while i < len {
+            let c = buffer[i];
             if !(c.is_ascii_digit()) {
                 return Err(Error::MalformedInput);
             }
@@ -219,7 +250,7 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
     }
 
     // Compute the final IEEE exponent.
-    let mut ieee_e2 = i32::max(0, e2 + FLOAT_EXPONENT_BIAS as i32 + floor_log2_f(m2) as i32) as u32;
+    let mut ieee_e2 = max_i32(0, e2 + FLOAT_EXPONENT_BIAS as i32 + floor_log2_f(m2) as i32) as u32;
 
     if ieee_e2 > 0xfe {
         // Final IEEE exponent is larger than the maximum representable; return
@@ -266,13 +297,35 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
     Ok(f32::from_bits(ieee))
 }
 
+/// Const version of [`s2f`] that panics on invalid input.
+///
+/// This is meant for constant contexts, where the panic becomes a
+/// compile-time error.
+///
+/// ## Panics
+///
+/// Panics if [`s2f`] returns an error.
+///
+/// ## Example
+///
+/// ```
+/// const X: f32 = ryuu::parse::s2f_const(b"0.1");
+/// assert_eq!(X, 0.1);
+/// ```
+pub const fn s2f_const(buffer: &[u8]) -> f32 {
+    match s2f(buffer) {
+        Ok(f) => f,
+        Err(e) => panic!("{}", e.as_str()),
+    }
+}
+
 /// Converts `f64`'s string representation back to an `f64`.
 ///
 /// ## Errors
 ///
 /// This function can return an `Error` if the input is malformed, too short, or
 /// too long.
-pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
+pub const fn s2d(buffer: &[u8]) -> Result<f64, Error> {
     let len = buffer.len();
     if len == 0 {
         return Err(Error::InputTooShort);
@@ -288,12 +341,13 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
     let mut signed_e = false;
 
     let mut i = 0;
-    if unsafe { *buffer.get_unchecked(0) } == b'-' {
+    if buffer[0] == b'-' {
         signed_m = true;
         i += 1;
     }
 
-    while let Some(c) = buffer.get(i).copied() {
+    while i < len {
+        let c = buffer[i];
         if c == b'.' {
             if dot_index != len {
                 return Err(Error::MalformedInput);
@@ -315,18 +369,21 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
         i += 1;
     }
 
-    if let Some(b'e' | b'E') = buffer.get(i) {
+    if i < len && (buffer[i] == b'e' || buffer[i] == b'E') {
         e_index = i;
         i += 1;
-        match buffer.get(i) {
-            Some(b'-') => {
-                signed_e = true;
-                i += 1;
+        if i < len {
+            match buffer[i] {
+                b'-' => {
+                    signed_e = true;
+                    i += 1;
+                }
+                b'+' => i += 1,
+                _ => {}
             }
-            Some(b'+') => i += 1,
-            _ => {}
         }
-        while let Some(c) = buffer.get(i).copied() {
+        while i < len {
+            let c = buffer[i];
             if !c.is_ascii_digit() {
                 return Err(Error::MalformedInput);
             }
@@ -353,21 +410,27 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
     } else {
         0
     };
+    Ok(m10_e10_to_f64(signed_m, m10, m10digits, e10))
+}
+
+/// Converts `(-1)^signed_m * m10 * 10^e10` to the nearest `f64`, ties to
+/// even, where `m10` has `m10digits` decimal digits and at most 17.
+const fn m10_e10_to_f64(signed_m: bool, m10: u64, m10digits: i32, e10: i32) -> f64 {
     if m10 == 0 {
-        return Ok(if signed_m { -0.0 } else { 0.0 });
+        return if signed_m { -0.0 } else { 0.0 };
     }
 
     if m10digits + e10 <= -324 || m10 == 0 {
         // Number is less than 1e-324, which should be rounded down to 0; return
         // +/-0.0.
         let ieee = (signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS);
-        return Ok(f64::from_bits(ieee));
+        return f64::from_bits(ieee);
     }
     if m10digits + e10 >= 310 {
         // Number is larger than 1e+309, which should be rounded to +/-Infinity.
         let ieee = ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS))
             | (0x7ff_u64 << d2s::DOUBLE_MANTISSA_BITS);
-        return Ok(f64::from_bits(ieee));
+        return f64::from_bits(ieee);
     }
 
     // Convert to binary float m2 * 2^e2, while retaining information about
@@ -400,11 +463,7 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
             .wrapping_add(d2s::DOUBLE_POW5_BITCOUNT);
         debug_assert!(j >= 0);
         debug_assert!(e10 < d2s::DOUBLE_POW5_SPLIT.len() as i32);
-        m2 = mul_shift_64(
-            m10,
-            unsafe { d2s::DOUBLE_POW5_SPLIT.get_unchecked(e10 as usize) },
-            j as u32,
-        );
+        m2 = mul_shift_64(m10, &d2s::DOUBLE_POW5_SPLIT[e10 as usize], j as u32);
 
         // We also compute if the result is exact, i.e.,
         //   [m10 * 10^e10 / 2^e2] == m10 * 10^e10 / 2^e2.
@@ -425,16 +484,12 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
             .wrapping_sub(1)
             .wrapping_add(d2s::DOUBLE_POW5_INV_BITCOUNT);
         debug_assert!(-e10 < d2s::DOUBLE_POW5_INV_SPLIT.len() as i32);
-        m2 = mul_shift_64(
-            m10,
-            unsafe { d2s::DOUBLE_POW5_INV_SPLIT.get_unchecked(-e10 as usize) },
-            j as u32,
-        );
+        m2 = mul_shift_64(m10, &d2s::DOUBLE_POW5_INV_SPLIT[-e10 as usize], j as u32);
         trailing_zeros = multiple_of_power_of_5(m10, -e10 as u32);
     }
 
     // Compute the final IEEE exponent.
-    let mut ieee_e2 = i32::max(
+    let mut ieee_e2 = max_i32(
         0,
         e2 + DOUBLE_EXPONENT_BIAS as i32 + floor_log2_d(m2) as i32,
     ) as u32;
@@ -444,7 +499,7 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
         // +/-Infinity.
         let ieee = ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS))
             | (0x7ff_u64 << d2s::DOUBLE_MANTISSA_BITS);
-        return Ok(f64::from_bits(ieee));
+        return f64::from_bits(ieee);
     }
 
     // We need to figure out how much we need to shift m2. The tricky part is
@@ -478,7 +533,1059 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
     let ieee = ((((signed_m as u64) << d2s::DOUBLE_EXPONENT_BITS) | ieee_e2 as u64)
         << d2s::DOUBLE_MANTISSA_BITS)
         | ieee_m2;
-    Ok(f64::from_bits(ieee))
+    f64::from_bits(ieee)
+}
+
+/// Const version of [`s2d`] that panics on invalid input.
+///
+/// This is meant for constant contexts, where the panic becomes a
+/// compile-time error.
+///
+/// ## Panics
+///
+/// Panics if [`s2d`] returns an error.
+///
+/// ## Example
+///
+/// ```
+/// const X: f64 = ryuu::parse::s2d_const(b"0.1");
+/// assert_eq!(X, 0.1);
+/// ```
+pub const fn s2d_const(buffer: &[u8]) -> f64 {
+    match s2d(buffer) {
+        Ok(d) => d,
+        Err(e) => panic!("{}", e.as_str()),
+    }
+}
+
+// Parses the same grammar as `s2d`, but without a limit on the number of
+// digits, into an arbitrary precision decimal and its sign.
+const fn parse_decimal(buffer: &[u8]) -> Result<(bool, Decimal), Error> {
+    parse_decimal_in(buffer, NumberingSystem::ASCII)
+}
+
+// Like `parse_decimal`, with the digits, decimal separator and minus sign of
+// `system`.
+const fn parse_decimal_in(
+    buffer: &[u8],
+    system: NumberingSystem,
+) -> Result<(bool, Decimal), Error> {
+    let len = buffer.len();
+    if len == 0 {
+        return Err(Error::InputTooShort);
+    }
+
+    let mut d = Decimal::new();
+    let mut seen_dot = false;
+    let mut signed_m = false;
+
+    let mut i = 0;
+    let (c, width) = read_char(buffer, i, system);
+    if c == b'-' {
+        signed_m = true;
+        i += width;
+    }
+
+    while i < len {
+        let (c, width) = read_char(buffer, i, system);
+        if c == b'.' {
+            if seen_dot {
+                return Err(Error::MalformedInput);
+            }
+            seen_dot = true;
+            i += width;
+            continue;
+        }
+        if !c.is_ascii_digit() {
+            break;
+        }
+        if c == b'0' && d.num_digits == 0 {
+            // Leading zeros only move the decimal point if they follow it.
+            d.decimal_point -= seen_dot as i32;
+        } else {
+            d.push_digit(c - b'0');
+            d.decimal_point += !seen_dot as i32;
+        }
+        i += width;
+    }
+
+    // The exponent saturates, and is only clamped once it is added to the
+    // position of the decimal point.
+    let mut e10 = 0i64;
+    if i < len && matches!(read_char(buffer, i, system), (b'e' | b'E', _)) {
+        i += 1;
+        let mut signed_e = false;
+        if i < len {
+            let (c, width) = read_char(buffer, i, system);
+            if c == b'-' || c == b'+' {
+                signed_e = c == b'-';
+                i += width;
+            }
+        }
+        while i < len {
+            let (c, width) = read_char(buffer, i, system);
+            if !c.is_ascii_digit() {
+                return Err(Error::MalformedInput);
+            }
+            e10 = e10.saturating_mul(10).saturating_add((c - b'0') as i64);
+            i += width;
+        }
+        if signed_e {
+            e10 = -e10;
+        }
+    }
+
+    if i < len {
+        return Err(Error::MalformedInput);
+    }
+    d.decimal_point = clamp_exponent(d.decimal_point as i64, e10);
+    d.finish();
+    Ok((signed_m, d))
+}
+
+// Decodes the UTF-8 character at `i` and returns the ASCII character that it
+// stands for in `system`, or zero if it is not part of the grammar, and its
+// length in bytes. Invalid UTF-8 is read as a single byte that stands for
+// nothing.
+const fn read_char(buffer: &[u8], i: usize, system: NumberingSystem) -> (u8, usize) {
+    let first = buffer[i];
+    let (mut code, width, min) = match first {
+        0x00..=0x7f => (first as u32, 1, 0),
+        0xc0..=0xdf => ((first & 0x1f) as u32, 2, 0x80),
+        0xe0..=0xef => ((first & 0x0f) as u32, 3, 0x800),
+        0xf0..=0xf7 => ((first & 0x07) as u32, 4, 0x10000),
+        _ => return (0, 1),
+    };
+    if i + width > buffer.len() {
+        return (0, 1);
+    }
+    let mut k = 1;
+    while k < width {
+        let next = buffer[i + k];
+        if next & 0xc0 != 0x80 {
+            return (0, 1);
+        }
+        code = (code << 6) | (next & 0x3f) as u32;
+        k += 1;
+    }
+    let c = match char::from_u32(code) {
+        Some(c) if code >= min => c,
+        _ => return (0, 1),
+    };
+
+    let ascii = if let Some(digit) = system.digit_value(c) {
+        b'0' + digit
+    } else if c == system.decimal_separator() {
+        b'.'
+    } else if c == system.minus_sign() {
+        b'-'
+    } else if matches!(c, 'e' | 'E' | '+') {
+        c as u8
+    } else {
+        0
+    };
+    (ascii, width)
+}
+
+/// Converts the string representation of an IEEE 754 binary16 (`f16`) number
+/// to its raw bits.
+///
+/// This accepts the same syntax as [`s2d`], but any number of digits, and the
+/// result is always correctly rounded (ties to even) to `f16`, without the
+/// double rounding of going through `f32` or `f64`.
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2f16_bits;
+///
+/// assert_eq!(s2f16_bits(b"0.1").unwrap(), 0x2e66);
+/// assert_eq!(s2f16_bits(b"-65504").unwrap(), 0xfbff);
+/// assert_eq!(s2f16_bits(b"65520").unwrap(), 0x7c00); // Rounds to infinity.
+/// ```
+pub const fn s2f16_bits(buffer: &[u8]) -> Result<u16, Error> {
+    let (signed_m, mut d) = match parse_decimal(buffer) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    let ieee = d.round_to_binary(BinaryFormat::ieee(
+        h2s::HALF_MANTISSA_BITS,
+        h2s::HALF_EXPONENT_BITS,
+    )) as u16;
+    Ok(((signed_m as u16) << (h2s::HALF_EXPONENT_BITS + h2s::HALF_MANTISSA_BITS)) | ieee)
+}
+
+/// Converts the string representation of a bfloat16 number to its raw bits.
+///
+/// This accepts the same syntax as [`s2d`], but any number of digits, and the
+/// result is always correctly rounded (ties to even) to bfloat16, without the
+/// double rounding of going through `f32` or `f64`.
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2bf16_bits;
+///
+/// assert_eq!(s2bf16_bits(b"0.1").unwrap(), 0x3dcd);
+/// assert_eq!(s2bf16_bits(b"-3.39e38").unwrap(), 0xff7f);
+/// assert_eq!(s2bf16_bits(b"3.4e38").unwrap(), 0x7f80); // Rounds to infinity.
+/// ```
+pub const fn s2bf16_bits(buffer: &[u8]) -> Result<u16, Error> {
+    let (signed_m, mut d) = match parse_decimal(buffer) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    let ieee = d.round_to_binary(BinaryFormat::ieee(
+        h2s::BFLOAT_MANTISSA_BITS,
+        h2s::BFLOAT_EXPONENT_BITS,
+    )) as u16;
+    Ok(((signed_m as u16) << (h2s::BFLOAT_EXPONENT_BITS + h2s::BFLOAT_MANTISSA_BITS)) | ieee)
+}
+
+/// Converts the string representation of a minifloat with the given
+/// [`Layout`], such as FP8 E4M3, to its raw bits.
+///
+/// This accepts the same syntax as [`s2d`], but any number of digits, and the
+/// result is always correctly rounded (ties to even) to `layout`. A value
+/// whose magnitude rounds above the largest finite value of the layout
+/// becomes infinity if the layout has one, NaN if it only has NaN, and the
+/// largest finite value otherwise. The sign is kept in all cases.
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::minifloat::Layout;
+/// use ryuu::parse::s2minifloat_bits;
+///
+/// assert_eq!(s2minifloat_bits(b"0.1", Layout::E4M3).unwrap(), 0x1d);
+/// assert_eq!(s2minifloat_bits(b"-448", Layout::E4M3).unwrap(), 0xfe);
+/// assert_eq!(s2minifloat_bits(b"480", Layout::E4M3).unwrap(), 0x7f); // Rounds to NaN.
+/// assert_eq!(s2minifloat_bits(b"61440", Layout::E5M2).unwrap(), 0x7c); // Rounds to infinity.
+/// assert_eq!(s2minifloat_bits(b"-100", Layout::E2M1).unwrap(), 0xf); // Saturates.
+/// ```
+pub const fn s2minifloat_bits(buffer: &[u8], layout: Layout) -> Result<u16, Error> {
+    let (signed_m, mut d) = match parse_decimal(buffer) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    // Round with one more binade than the layout has, so that overflow can
+    // be told apart from the largest finite value.
+    let mut ieee = d.round_to_binary(BinaryFormat {
+        mantissa_bits: layout.mantissa_bits(),
+        min_exponent: -layout.bias(),
+        infinite_power: (1 << layout.exponent_bits()) + 1,
+    }) as u16;
+    if ieee > layout.max_finite() {
+        ieee = if let Special::FiniteOnly = layout.special() {
+            layout.max_finite()
+        } else {
+            // The next encoding is infinity, or the single NaN.
+            layout.max_finite() + 1
+        };
+    }
+    let sign = if signed_m { layout.sign_bit() } else { 0 };
+    Ok(sign | ieee)
+}
+
+/// Converts the string representation of an `f64` in `radix` back to an
+/// `f64`, like [`Formatter::format_f64_radix`] writes it.
+///
+/// The input is an optional `-`, then digits `0`-`9` and `a`-`z` (in either
+/// case) below `radix`, with at most one `.` anywhere among them. There is no
+/// exponent, except in radix 10, which accepts the syntax of [`s2d`] with any
+/// number of digits. In the other radices, the significant digits must fit in
+/// 64 bits. The result is always correctly rounded (ties to even), and values
+/// too large for an `f64` become infinity.
+///
+/// [`Formatter::format_f64_radix`]: crate::Formatter::format_f64_radix
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed, empty, or
+/// has too many significant digits.
+///
+/// ## Panics
+///
+/// Panics if `radix` is not in the range from 2 to 36.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2d_radix;
+///
+/// assert_eq!(s2d_radix(b"ff.8", 16).unwrap(), 255.5);
+/// assert_eq!(s2d_radix(b"-0.1", 2).unwrap(), -0.5);
+/// assert_eq!(
+///     s2d_radix(b"0.0022002200220022002200220022002201", 3).unwrap(),
+///     0.1
+/// );
+/// ```
+pub const fn s2d_radix(buffer: &[u8], radix: u32) -> Result<f64, Error> {
+    assert!(2 <= radix && radix <= 36, "`radix` must be from 2 to 36");
+    let format = BinaryFormat::F64;
+    let (signed_m, ieee) = match parse_radix(buffer, radix, format) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    Ok(f64::from_bits(
+        ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS)) | ieee,
+    ))
+}
+
+/// Converts the string representation of an `f32` in `radix` back to an
+/// `f32`, like [`Formatter::format_f32_radix`] writes it.
+///
+/// This accepts the same syntax as [`s2d_radix`], and the result is always
+/// correctly rounded (ties to even) to `f32`.
+///
+/// [`Formatter::format_f32_radix`]: crate::Formatter::format_f32_radix
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed, empty, or
+/// has too many significant digits.
+///
+/// ## Panics
+///
+/// Panics if `radix` is not in the range from 2 to 36.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2f_radix;
+///
+/// assert_eq!(s2f_radix(b"0.199999a", 16).unwrap(), 0.1);
+/// assert_eq!(s2f_radix(b"Z", 36).unwrap(), 35.0);
+/// ```
+pub const fn s2f_radix(buffer: &[u8], radix: u32) -> Result<f32, Error> {
+    assert!(2 <= radix && radix <= 36, "`radix` must be from 2 to 36");
+    let format = BinaryFormat::ieee(f2s::FLOAT_MANTISSA_BITS, f2s::FLOAT_EXPONENT_BITS);
+    let (signed_m, ieee) = match parse_radix(buffer, radix, format) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    let ieee = ieee as u32;
+    Ok(f32::from_bits(
+        ((signed_m as u32) << (f2s::FLOAT_EXPONENT_BITS + f2s::FLOAT_MANTISSA_BITS)) | ieee,
+    ))
+}
+
+// Parses a number in `radix` into its sign and the unsigned bits of the
+// nearest value in `format`.
+const fn parse_radix(
+    buffer: &[u8],
+    radix: u32,
+    format: BinaryFormat,
+) -> Result<(bool, u64), Error> {
+    if radix == 10 {
+        return match parse_decimal(buffer) {
+            Ok((signed_m, mut d)) => Ok((signed_m, d.round_to_binary(format))),
+            Err(e) => Err(e),
+        };
+    }
+
+    let len = buffer.len();
+    if len == 0 {
+        return Err(Error::InputTooShort);
+    }
+
+    let mut signed_m = false;
+    let mut i = 0;
+    if buffer[0] == b'-' {
+        signed_m = true;
+        i += 1;
+    }
+
+    let mut seen_dot = false;
+    let mut seen_digit = false;
+    let mut mantissa = 0u64;
+    let mut exponent = 0i32;
+    // Zeros after the last nonzero digit, which only scale the mantissa once
+    // another nonzero digit follows.
+    let mut zeros = 0i32;
+    while i < len {
+        let c = buffer[i];
+        i += 1;
+        if c == b'.' {
+            if seen_dot {
+                return Err(Error::MalformedInput);
+            }
+            seen_dot = true;
+            continue;
+        }
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'z' => c - b'a' + 10,
+            b'A'..=b'Z' => c - b'A' + 10,
+            _ => return Err(Error::MalformedInput),
+        } as u32;
+        if digit >= radix {
+            return Err(Error::MalformedInput);
+        }
+        seen_digit = true;
+        exponent -= seen_dot as i32;
+        if digit == 0 {
+            zeros += (mantissa != 0) as i32;
+            continue;
+        }
+        while zeros >= 0 {
+            mantissa = match mantissa.checked_mul(radix as u64) {
+                Some(m) => m,
+                None => return Err(Error::InputTooLong),
+            };
+            zeros -= 1;
+        }
+        mantissa = match mantissa.checked_add(digit as u64) {
+            Some(m) => m,
+            None => return Err(Error::InputTooLong),
+        };
+        zeros = 0;
+    }
+
+    if !seen_digit {
+        return Err(Error::MalformedInput);
+    }
+    let ieee = radix_to_binary(mantissa, exponent + zeros, radix, format);
+    Ok((signed_m, ieee))
+}
+
+/// Converts the string representation of an `f64` to its exact bits,
+/// including the sign, quiet bit and payload of NaN.
+///
+/// This accepts the output of [`Formatter::format_f64_nan_payload`]: `nan`,
+/// `snan`, `nan(0x...)` and `snan(0x...)` with an optional `-`, where the
+/// hexadecimal number is the trailing significand. The quiet bit is implied
+/// by `nan`, so `nan(0x1)` and `nan(0x8000000000001)` are the same NaN, and
+/// must be clear for `snan`. `inf`, `-inf` and finite numbers in the syntax of
+/// [`s2d`] are also accepted, with any number of digits and correct rounding
+/// (ties to even). The words are not case sensitive.
+///
+/// The result is returned as bits rather than as an `f64`, since some
+/// platforms quiet a signaling NaN when it is moved through a floating point
+/// register.
+///
+/// [`Formatter::format_f64_nan_payload`]: crate::Formatter::format_f64_nan_payload
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty, if
+/// the payload does not fit in the trailing significand, or if a signaling NaN
+/// has the quiet bit set or no payload.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2d_bits;
+///
+/// assert_eq!(s2d_bits(b"-nan").unwrap(), 0xfff8000000000000);
+/// assert_eq!(
+///     s2d_bits(b"nan(0x8000000000001)").unwrap(),
+///     0x7ff8000000000001
+/// );
+/// assert_eq!(s2d_bits(b"snan(0x1)").unwrap(), 0x7ff0000000000001);
+/// assert_eq!(s2d_bits(b"-inf").unwrap(), 0xfff0000000000000);
+/// assert_eq!(s2d_bits(b"0.1").unwrap(), 0.1f64.to_bits());
+/// ```
+pub const fn s2d_bits(buffer: &[u8]) -> Result<u64, Error> {
+    const EXPONENT_MASK: u64 = 0x7ff0000000000000;
+    let (signed_m, ieee) = match parse_nonfinite(buffer, d2s::DOUBLE_MANTISSA_BITS) {
+        Ok(Some((signed_m, significand))) => (signed_m, EXPONENT_MASK | significand),
+        Ok(None) => match parse_decimal(buffer) {
+            Ok((signed_m, mut d)) => (signed_m, d.round_to_binary(BinaryFormat::F64)),
+            Err(e) => return Err(e),
+        },
+        Err(e) => return Err(e),
+    };
+    Ok(((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS)) | ieee)
+}
+
+/// Converts the string representation of an `f32` to its exact bits,
+/// including the sign, quiet bit and payload of NaN.
+///
+/// This accepts the same syntax as [`s2d_bits`], for the 23-bit trailing
+/// significand of an `f32`, which is the output of
+/// [`Formatter::format_f32_nan_payload`].
+///
+/// [`Formatter::format_f32_nan_payload`]: crate::Formatter::format_f32_nan_payload
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty, if
+/// the payload does not fit in the trailing significand, or if a signaling NaN
+/// has the quiet bit set or no payload.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2f_bits;
+///
+/// assert_eq!(s2f_bits(b"-snan(0xabc)").unwrap(), 0xff800abc);
+/// assert_eq!(s2f_bits(b"NaN").unwrap(), 0x7fc00000);
+/// ```
+pub const fn s2f_bits(buffer: &[u8]) -> Result<u32, Error> {
+    const EXPONENT_MASK: u32 = 0x7f800000;
+    let (signed_m, ieee) = match parse_nonfinite(buffer, f2s::FLOAT_MANTISSA_BITS) {
+        Ok(Some((signed_m, significand))) => (signed_m, EXPONENT_MASK | significand as u32),
+        Ok(None) => match parse_decimal(buffer) {
+            Ok((signed_m, mut d)) => (
+                signed_m,
+                d.round_to_binary(BinaryFormat::ieee(
+                    f2s::FLOAT_MANTISSA_BITS,
+                    f2s::FLOAT_EXPONENT_BITS,
+                )) as u32,
+            ),
+            Err(e) => return Err(e),
+        },
+        Err(e) => return Err(e),
+    };
+    Ok(((signed_m as u32) << (f2s::FLOAT_EXPONENT_BITS + f2s::FLOAT_MANTISSA_BITS)) | ieee)
+}
+
+// Whether `word` follows at `index` in `buffer`, in any case.
+const fn starts_with_word(buffer: &[u8], index: usize, word: &[u8]) -> bool {
+    if buffer.len() - index < word.len() {
+        return false;
+    }
+    let mut k = 0;
+    while k < word.len() {
+        if !buffer[index + k].eq_ignore_ascii_case(&word[k]) {
+            return false;
+        }
+        k += 1;
+    }
+    true
+}
+
+// Parses an infinity or a NaN, with `mantissa_bits` bits in its trailing
+// significand, into the sign and the trailing significand, which is zero for
+// an infinity. Returns `None` if `buffer` is neither.
+const fn parse_nonfinite(buffer: &[u8], mantissa_bits: u32) -> Result<Option<(bool, u64)>, Error> {
+    let len = buffer.len();
+    let signed_m = len > 0 && buffer[0] == b'-';
+    let mut i = signed_m as usize;
+    if starts_with_word(buffer, i, b"inf") && i + 3 == len {
+        return Ok(Some((signed_m, 0)));
+    }
+    let signaling = starts_with_word(buffer, i, b"snan");
+    i += signaling as usize;
+    if !starts_with_word(buffer, i, b"nan") {
+        return Ok(None);
+    }
+    i += 3;
+
+    let quiet_bit = 1u64 << (mantissa_bits - 1);
+    if i == len {
+        return if signaling {
+            Err(Error::MalformedInput)
+        } else {
+            Ok(Some((signed_m, quiet_bit)))
+        };
+    }
+    if !starts_with_word(buffer, i, b"(0x") || i + 4 >= len || buffer[len - 1] != b')' {
+        return Err(Error::MalformedInput);
+    }
+    i += 3;
+    let mut significand = 0u64;
+    while i < len - 1 {
+        let c = buffer[i];
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => return Err(Error::MalformedInput),
+        };
+        if significand >> (mantissa_bits - 4) != 0 {
+            return Err(Error::InputTooLong);
+        }
+        significand = (significand << 4) | digit as u64;
+        i += 1;
+    }
+
+    if !signaling {
+        significand |= quiet_bit;
+    } else if significand == 0 || significand & quiet_bit != 0 {
+        return Err(Error::MalformedInput);
+    }
+    Ok(Some((signed_m, significand)))
+}
+
+/// Converts the string representation of a decimal number to an IEEE 754
+/// decimal64 float in the BID encoding, as raw bits.
+///
+/// This accepts the same syntax as [`s2d`], with any number of digits, as
+/// well as `inf` and `nan` in any case after an optional `-`. The digits are
+/// kept as they are up to the precision of 16 digits, so the trailing zeros
+/// choose the member of the cohort, as in the output of
+/// [`Formatter::format_decimal64_bits_cohort`]: `1.20` has the coefficient 120
+/// and the exponent -2. Longer inputs, and those below the smallest exponent,
+/// are correctly rounded (ties to even). Values above the largest finite one
+/// become infinity.
+///
+/// [`Formatter::format_decimal64_bits_cohort`]: crate::Formatter::format_decimal64_bits_cohort
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2decimal64_bits;
+///
+/// assert_eq!(s2decimal64_bits(b"1.20").unwrap(), 0x3180000000000078);
+/// assert_eq!(s2decimal64_bits(b"1.2").unwrap(), 0x31a000000000000c);
+/// assert_eq!(s2decimal64_bits(b"-inf").unwrap(), 0xf800000000000000);
+/// ```
+pub const fn s2decimal64_bits(buffer: &[u8]) -> Result<u64, Error> {
+    match parse_decimal_float(buffer, DECIMAL64) {
+        Ok(ParsedDecimal::Finite(parts)) => Ok(decimal64_from_parts(parts)),
+        Ok(ParsedDecimal::Infinite(sign)) => Ok(((sign as u64) << 63) | (0b11110 << 58)),
+        Ok(ParsedDecimal::Nan(sign)) => Ok(((sign as u64) << 63) | (0b11111 << 58)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Converts the string representation of a decimal number to an IEEE 754
+/// decimal128 float in the BID encoding, as raw bits.
+///
+/// This is the decimal128 version of [`s2decimal64_bits`], with a precision
+/// of 34 digits.
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2decimal128_bits;
+///
+/// assert_eq!(
+///     s2decimal128_bits(b"-0.1").unwrap(),
+///     0xb03e0000000000000000000000000001
+/// );
+/// assert_eq!(
+///     s2decimal128_bits(b"1e6145").unwrap(),
+///     0x78000000000000000000000000000000
+/// );
+/// ```
+pub const fn s2decimal128_bits(buffer: &[u8]) -> Result<u128, Error> {
+    match parse_decimal_float(buffer, DECIMAL128) {
+        Ok(ParsedDecimal::Finite(parts)) => Ok(decimal128_from_parts(parts)),
+        Ok(ParsedDecimal::Infinite(sign)) => Ok(((sign as u128) << 127) | (0b11110 << 122)),
+        Ok(ParsedDecimal::Nan(sign)) => Ok(((sign as u128) << 127) | (0b11111 << 122)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Converts the string representation of an `f64` in the digits of a Unicode
+/// numbering system back to an `f64`, like
+/// [`Formatted::write_numbering`] writes it.
+///
+/// This accepts the syntax of [`s2d`] with any number of digits, but with the
+/// digits, decimal separator and minus sign of `system` in place of the ASCII
+/// ones, and the result is always correctly rounded (ties to even). The
+/// exponent marker is `e` or `E`, and its sign may also be `+`.
+///
+/// [`Formatted::write_numbering`]: crate::Formatted::write_numbering
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty,
+/// which includes digits of other numbering systems and invalid UTF-8.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::format::NumberingSystem;
+/// use ryuu::parse::s2d_numbering;
+///
+/// assert_eq!(
+///     s2d_numbering("١٢٣٫٤٥".as_bytes(), NumberingSystem::ARABIC_INDIC).unwrap(),
+///     123.45
+/// );
+/// assert_eq!(
+///     s2d_numbering("－０．１".as_bytes(), NumberingSystem::FULLWIDTH).unwrap(),
+///     -0.1
+/// );
+/// assert!(s2d_numbering(b"123.45", NumberingSystem::THAI).is_err());
+/// ```
+pub const fn s2d_numbering(buffer: &[u8], system: NumberingSystem) -> Result<f64, Error> {
+    let (signed_m, mut d) = match parse_decimal_in(buffer, system) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    let ieee = d.round_to_binary(BinaryFormat::F64);
+    Ok(f64::from_bits(
+        ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS)) | ieee,
+    ))
+}
+
+/// Converts the string representation of an `f32` in the digits of a Unicode
+/// numbering system back to an `f32`.
+///
+/// This accepts the same syntax as [`s2d_numbering`], and the result is
+/// always correctly rounded (ties to even) to `f32`.
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty,
+/// which includes digits of other numbering systems and invalid UTF-8.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::format::NumberingSystem;
+/// use ryuu::parse::s2f_numbering;
+///
+/// assert_eq!(
+///     s2f_numbering("०.१".as_bytes(), NumberingSystem::DEVANAGARI).unwrap(),
+///     0.1
+/// );
+/// ```
+pub const fn s2f_numbering(buffer: &[u8], system: NumberingSystem) -> Result<f32, Error> {
+    let (signed_m, mut d) = match parse_decimal_in(buffer, system) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    let ieee = d.round_to_binary(BinaryFormat::ieee(
+        f2s::FLOAT_MANTISSA_BITS,
+        f2s::FLOAT_EXPONENT_BITS,
+    )) as u32;
+    Ok(f32::from_bits(
+        ((signed_m as u32) << (f2s::FLOAT_EXPONENT_BITS + f2s::FLOAT_MANTISSA_BITS)) | ieee,
+    ))
+}
+
+/// Converts the string representation of a decimal number to the binary
+/// fixed-point number `raw * 2^-frac_bits` nearest to it, and returns `raw`.
+///
+/// This accepts the same syntax as [`s2d`], but any number of digits, and the
+/// result is always correctly rounded (ties to even), so it reads back the
+/// output of [`Formatter::format_fixed_point`]. Values outside of the range
+/// of `i64` saturate to `i64::MIN` or `i64::MAX`.
+///
+/// [`Formatter::format_fixed_point`]: crate::Formatter::format_fixed_point
+///
+/// ## Errors
+///
+/// This function can return an `Error` if the input is malformed or empty.
+///
+/// ## Panics
+///
+/// Panics if `frac_bits` is above 64.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::s2fixed_point;
+///
+/// // Q15.16
+/// assert_eq!(s2fixed_point(b"1.1", 16).unwrap(), 72090);
+/// assert_eq!(s2fixed_point(b"-3", 16).unwrap(), -3 << 16);
+/// // A tie between 0 and 2^-16 rounds to even.
+/// assert_eq!(s2fixed_point(b"0.00000762939453125", 16).unwrap(), 0);
+/// // Q1.31
+/// assert_eq!(s2fixed_point(b"0.1", 31).unwrap(), 214748365);
+/// assert_eq!(s2fixed_point(b"1e10", 31).unwrap(), i64::MAX); // Saturates.
+/// ```
+pub const fn s2fixed_point(buffer: &[u8], frac_bits: u32) -> Result<i64, Error> {
+    assert!(frac_bits <= 64, "`frac_bits` must be at most 64");
+    let (signed_m, mut d) = match parse_decimal(buffer) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(e),
+    };
+    let magnitude = d.round_to_fixed(frac_bits);
+    Ok(if !signed_m {
+        if magnitude > i64::MAX as u64 {
+            i64::MAX
+        } else {
+            magnitude as i64
+        }
+    } else if magnitude > i64::MIN.unsigned_abs() {
+        i64::MIN
+    } else {
+        0i64.wrapping_sub_unsigned(magnitude)
+    })
+}
+
+/// The kind of a [`JsonError`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JsonErrorKind {
+    /// The input ended where a digit was expected.
+    UnexpectedEnd,
+
+    /// A byte other than a digit was found where a digit was expected.
+    ExpectedDigit,
+
+    /// The integer part has a leading zero, e.g. `01`.
+    LeadingZero,
+
+    /// The number is grammatically valid but too large to be represented as
+    /// a finite `f64`.
+    OutOfRange,
+}
+
+/// The error type of [`s2d_json`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct JsonError {
+    /// What went wrong.
+    pub kind: JsonErrorKind,
+
+    /// The offset of the offending byte in the input. For
+    /// [`JsonErrorKind::OutOfRange`], this is the offset of the start of the
+    /// number.
+    pub index: usize,
+}
+
+impl JsonError {
+    /// Returns a short description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self.kind {
+            JsonErrorKind::UnexpectedEnd => "unexpected end of input",
+            JsonErrorKind::ExpectedDigit => "expected digit",
+            JsonErrorKind::LeadingZero => "leading zero",
+            JsonErrorKind::OutOfRange => "number out of range",
+        }
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} at index {}", self.as_str(), self.index)
+    }
+}
+
+/// Parses a number following the strict JSON grammar of [RFC 8259], from the
+/// start of `buffer`.
+///
+/// ```text
+/// number = [ minus ] int [ frac ] [ exp ]
+/// int    = zero / ( digit1-9 *DIGIT )
+/// frac   = decimal-point 1*DIGIT
+/// exp    = e [ minus / plus ] 1*DIGIT
+/// ```
+///
+/// The number of digits is unlimited and the result is always correctly
+/// rounded (ties to even). Parsing stops at the first byte that cannot
+/// continue the number, and the number of bytes consumed is returned along
+/// with the value, so the caller can go on with the rest of a JSON document.
+///
+/// [RFC 8259]: https://www.rfc-editor.org/rfc/rfc8259#section-6
+///
+/// ## Errors
+///
+/// Returns a [`JsonError`] pointing at the offending byte if `buffer` does not
+/// start with a valid JSON number, e.g. `01`, `.5`, `1.`, `1.e5`, `+1` or
+/// `NaN`. Numbers which round to an infinite `f64` are reported as
+/// [`JsonErrorKind::OutOfRange`]; numbers which are too small round to zero.
+///
+/// ## Example
+///
+/// ```
+/// use ryuu::parse::{s2d_json, JsonErrorKind};
+///
+/// assert_eq!(s2d_json(b"-1.5e3,").unwrap(), (-1500.0, 6));
+/// assert_eq!(
+///     s2d_json(b"01").unwrap_err().kind,
+///     JsonErrorKind::LeadingZero
+/// );
+/// assert_eq!(s2d_json(b"1.").unwrap_err().index, 2);
+/// ```
+pub const fn s2d_json(buffer: &[u8]) -> Result<(f64, usize), JsonError> {
+    const fn digit_at(buffer: &[u8], i: usize) -> Result<u8, JsonError> {
+        if i >= buffer.len() {
+            Err(JsonError {
+                kind: JsonErrorKind::UnexpectedEnd,
+                index: i,
+            })
+        } else if !buffer[i].is_ascii_digit() {
+            Err(JsonError {
+                kind: JsonErrorKind::ExpectedDigit,
+                index: i,
+            })
+        } else {
+            Ok(buffer[i] - b'0')
+        }
+    }
+
+    const fn skip_digits(buffer: &[u8], mut i: usize) -> usize {
+        while i < buffer.len() && buffer[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    }
+
+    let len = buffer.len();
+    let mut i = 0;
+
+    let signed_m = len > 0 && buffer[0] == b'-';
+    if signed_m {
+        i += 1;
+    }
+
+    // int
+    let int_start = i;
+    match digit_at(buffer, i) {
+        Ok(0) => {
+            i += 1;
+            if i < len && buffer[i].is_ascii_digit() {
+                return Err(JsonError {
+                    kind: JsonErrorKind::LeadingZero,
+                    index: int_start,
+                });
+            }
+        }
+        Ok(_) => i = skip_digits(buffer, i + 1),
+        Err(e) => return Err(e),
+    }
+    let int_end = i;
+
+    // frac
+    let mut frac_start = i;
+    let mut frac_end = i;
+    if i < len && buffer[i] == b'.' {
+        i += 1;
+        if let Err(e) = digit_at(buffer, i) {
+            return Err(e);
+        }
+        frac_start = i;
+        i = skip_digits(buffer, i);
+        frac_end = i;
+    }
+
+    // exp; the magnitude saturates, and is only clamped once the position of
+    // the digits is known.
+    let mut e10 = 0i64;
+    if i < len && (buffer[i] == b'e' || buffer[i] == b'E') {
+        i += 1;
+        let mut signed_e = false;
+        if i < len && (buffer[i] == b'-' || buffer[i] == b'+') {
+            signed_e = buffer[i] == b'-';
+            i += 1;
+        }
+        if let Err(e) = digit_at(buffer, i) {
+            return Err(e);
+        }
+        while i < len && buffer[i].is_ascii_digit() {
+            e10 = e10
+                .saturating_mul(10)
+                .saturating_add((buffer[i] - b'0') as i64);
+            i += 1;
+        }
+        if signed_e {
+            e10 = -e10;
+        }
+    }
+
+    let value = s2d_json_digits(buffer, int_start, int_end, frac_start, frac_end, e10);
+    let value = if signed_m { -value } else { value };
+    if is_infinite_f64(value) {
+        return Err(JsonError {
+            kind: JsonErrorKind::OutOfRange,
+            index: 0,
+        });
+    }
+    Ok((value, i))
+}
+
+// Converts the already validated digits of a JSON number to a nonnegative
+// `f64`.
+const fn s2d_json_digits(
+    buffer: &[u8],
+    int_start: usize,
+    int_end: usize,
+    frac_start: usize,
+    frac_end: usize,
+    e10: i64,
+) -> f64 {
+    // Skip leading zeros, both of the integer and of the fraction part.
+    let mut first = int_start;
+    while first < int_end && buffer[first] == b'0' {
+        first += 1;
+    }
+    let mut frac_first = frac_start;
+    if first == int_end {
+        while frac_first < frac_end && buffer[frac_first] == b'0' {
+            frac_first += 1;
+        }
+        if frac_first == frac_end {
+            return 0.0;
+        }
+    }
+
+    let int_digits = int_end - first;
+    let frac_skipped = frac_first - frac_start;
+    let frac_digits = frac_end - frac_first;
+
+    if int_digits + frac_digits <= 17 && -100_000 < e10 && e10 < 100_000 {
+        // Fast path, the same as `s2d`.
+        let mut m10 = 0u64;
+        let mut j = first;
+        while j < int_end {
+            m10 = 10 * m10 + (buffer[j] - b'0') as u64;
+            j += 1;
+        }
+        j = frac_first;
+        while j < frac_end {
+            m10 = 10 * m10 + (buffer[j] - b'0') as u64;
+            j += 1;
+        }
+        let m10digits = (int_digits + frac_digits) as i32;
+        let e10 = clamp_exponent(-((frac_end - frac_start) as i64), e10);
+        return m10_e10_to_f64(false, m10, m10digits, e10);
+    }
+
+    // Slow path with arbitrary precision.
+    let mut d = Decimal::new();
+    let mut j = first;
+    while j < int_end {
+        d.push_digit(buffer[j] - b'0');
+        j += 1;
+    }
+    j = frac_first;
+    while j < frac_end {
+        d.push_digit(buffer[j] - b'0');
+        j += 1;
+    }
+    d.decimal_point = clamp_exponent(int_digits as i64 - frac_skipped as i64, e10);
+    d.finish();
+    f64::from_bits(d.round_to_binary(BinaryFormat::F64))
+}
+
+// Exponents are clamped to this magnitude, far beyond any that can affect a
+// result, once the adjustment for the position of the digits is added.
+const MAX_EXPONENT: i64 = 1 << 20;
+
+// Returns `adjustment + e10`, clamped to `MAX_EXPONENT`, so that a long run
+// of digits can still make up for a huge exponent.
+#[inline]
+const fn clamp_exponent(adjustment: i64, e10: i64) -> i32 {
+    let exponent = adjustment.saturating_add(e10);
+    if exponent > MAX_EXPONENT {
+        MAX_EXPONENT as i32
+    } else if exponent < -MAX_EXPONENT {
+        -MAX_EXPONENT as i32
+    } else {
+        exponent as i32
+    }
+}
+
+#[inline]
+const fn is_infinite_f64(d: f64) -> bool {
+    d.to_bits() & 0x7fffffffffffffff == 0x7ff0000000000000
 }
 
 #[cfg(test)]
@@ -524,4 +1631,48 @@ mod tests {
 
         include!("../unittests/s2d_test.rs");
     }
+
+    mod s2d_json_test {
+        #![allow(
+            clippy::float_cmp,
+            clippy::unreadable_literal,
+            clippy::unseparated_literal_suffix
+        )]
+
+        include!("../unittests/s2d_json_test.rs");
+    }
+
+    mod s2d_radix_test {
+        #![allow(
+            clippy::float_cmp,
+            clippy::unreadable_literal,
+            clippy::unseparated_literal_suffix
+        )]
+
+        include!("../unittests/s2d_radix_test.rs");
+    }
+
+    mod s2d_bits_test {
+        #![allow(clippy::unreadable_literal)]
+
+        include!("../unittests/s2d_bits_test.rs");
+    }
+
+    mod s2decimal_test {
+        #![allow(clippy::unreadable_literal)]
+
+        include!("../unittests/s2decimal_test.rs");
+    }
+
+    mod s2d_numbering_test {
+        #![allow(clippy::float_cmp)]
+
+        include!("../unittests/s2d_numbering_test.rs");
+    }
+
+    mod s2fixed_point_test {
+        #![allow(clippy::unreadable_literal)]
+
+        include!("../unittests/s2fixed_point_test.rs");
+    }
 }
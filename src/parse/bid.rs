@@ -0,0 +1,196 @@
+// Parsing of decimal64 and decimal128 floats in the BID encoding.
+//
+// The digits are kept exactly up to the precision of the format, which keeps
+// the cohort of the input, e.g. 1.20 has the coefficient 120 and the exponent
+// -2. Beyond the precision, and below the smallest exponent, the coefficient
+// is rounded once, ties to even, and above the largest exponent it is padded
+// with zeros while it has room for them.
+
+use super::{clamp_exponent, starts_with_word, Error};
+use crate::bid::{
+    DecimalParts, DECIMAL128_DIGITS, DECIMAL128_MIN_EXPONENT, DECIMAL64_DIGITS,
+    DECIMAL64_MIN_EXPONENT,
+};
+
+/// The range of the coefficient and exponent of a decimal float.
+#[derive(Clone, Copy)]
+pub(crate) struct DecimalFormat {
+    digits: u32,
+    min_exponent: i32,
+    max_exponent: i32,
+}
+
+pub(crate) const DECIMAL64: DecimalFormat = DecimalFormat {
+    digits: DECIMAL64_DIGITS,
+    min_exponent: DECIMAL64_MIN_EXPONENT,
+    max_exponent: 369,
+};
+
+pub(crate) const DECIMAL128: DecimalFormat = DecimalFormat {
+    digits: DECIMAL128_DIGITS,
+    min_exponent: DECIMAL128_MIN_EXPONENT,
+    max_exponent: 6111,
+};
+
+pub(crate) enum ParsedDecimal {
+    Finite(DecimalParts),
+    Infinite(bool),
+    Nan(bool),
+}
+
+/// Parses a decimal float with the syntax of `s2d`, or `inf` or `nan` in any
+/// case after an optional `-`.
+pub(crate) const fn parse_decimal_float(
+    buffer: &[u8],
+    format: DecimalFormat,
+) -> Result<ParsedDecimal, Error> {
+    let len = buffer.len();
+    if len == 0 {
+        return Err(Error::InputTooShort);
+    }
+
+    let sign = buffer[0] == b'-';
+    let mut i = sign as usize;
+    if i + 3 == len && starts_with_word(buffer, i, b"inf") {
+        return Ok(ParsedDecimal::Infinite(sign));
+    }
+    if i + 3 == len && starts_with_word(buffer, i, b"nan") {
+        return Ok(ParsedDecimal::Nan(sign));
+    }
+
+    // The first digit beyond the precision, and whether any later one is
+    // nonzero.
+    let mut round = 0u8;
+    let mut sticky = false;
+    let mut coefficient = 0u128;
+    let mut digits = 0u32;
+    let mut exponent = 0i32;
+    let mut seen_dot = false;
+    let mut seen_digit = false;
+    while i < len {
+        let c = buffer[i];
+        if c == b'.' {
+            if seen_dot {
+                return Err(Error::MalformedInput);
+            }
+            seen_dot = true;
+            i += 1;
+            continue;
+        }
+        if !c.is_ascii_digit() {
+            break;
+        }
+        seen_digit = true;
+        let digit = c - b'0';
+        if digits < format.digits {
+            // Leading zeros only move the exponent if they follow the
+            // decimal point.
+            if coefficient != 0 || digit != 0 {
+                coefficient = 10 * coefficient + digit as u128;
+                digits += 1;
+            }
+            exponent -= seen_dot as i32;
+        } else {
+            if digits == format.digits {
+                round = digit;
+                digits += 1;
+            } else {
+                sticky |= digit != 0;
+            }
+            exponent += !seen_dot as i32;
+        }
+        i += 1;
+    }
+    if !seen_digit {
+        return Err(Error::MalformedInput);
+    }
+
+    // The exponent saturates, and is only clamped once it is added to the
+    // exponent of the digits.
+    let mut e10 = 0i64;
+    if i < len && (buffer[i] == b'e' || buffer[i] == b'E') {
+        i += 1;
+        let mut signed_e = false;
+        if i < len && (buffer[i] == b'-' || buffer[i] == b'+') {
+            signed_e = buffer[i] == b'-';
+            i += 1;
+        }
+        if i == len {
+            return Err(Error::MalformedInput);
+        }
+        while i < len {
+            let c = buffer[i];
+            if !c.is_ascii_digit() {
+                return Err(Error::MalformedInput);
+            }
+            e10 = e10.saturating_mul(10).saturating_add((c - b'0') as i64);
+            i += 1;
+        }
+        if signed_e {
+            e10 = -e10;
+        }
+    }
+    if i < len {
+        return Err(Error::MalformedInput);
+    }
+    exponent = clamp_exponent(exponent as i64, e10);
+
+    while exponent < format.min_exponent && (coefficient != 0 || round != 0) {
+        sticky |= round != 0;
+        round = (coefficient % 10) as u8;
+        coefficient /= 10;
+        exponent += 1;
+    }
+    if exponent < format.min_exponent {
+        exponent = format.min_exponent;
+    }
+
+    let limit = 10u128.pow(format.digits);
+    if round > 5 || (round == 5 && (sticky || coefficient % 2 != 0)) {
+        coefficient += 1;
+        if coefficient == limit {
+            coefficient /= 10;
+            exponent += 1;
+        }
+    }
+
+    if coefficient == 0 && exponent > format.max_exponent {
+        exponent = format.max_exponent;
+    }
+    while exponent > format.max_exponent && 10 * coefficient < limit {
+        coefficient *= 10;
+        exponent -= 1;
+    }
+    if exponent > format.max_exponent {
+        return Ok(ParsedDecimal::Infinite(sign));
+    }
+
+    Ok(ParsedDecimal::Finite(DecimalParts {
+        sign,
+        coefficient,
+        exponent,
+    }))
+}
+
+/// Encodes a finite decimal64, whose coefficient and exponent must be in
+/// range.
+#[inline]
+pub(crate) const fn decimal64_from_parts(parts: DecimalParts) -> u64 {
+    let sign = (parts.sign as u64) << 63;
+    let biased_exponent = (parts.exponent - DECIMAL64_MIN_EXPONENT) as u64;
+    let coefficient = parts.coefficient as u64;
+    if coefficient < 1 << 53 {
+        sign | (biased_exponent << 53) | coefficient
+    } else {
+        sign | (0b11 << 61) | (biased_exponent << 51) | (coefficient & ((1 << 51) - 1))
+    }
+}
+
+/// Encodes a finite decimal128, whose coefficient and exponent must be in
+/// range.
+#[inline]
+pub(crate) const fn decimal128_from_parts(parts: DecimalParts) -> u128 {
+    let sign = (parts.sign as u128) << 127;
+    let biased_exponent = (parts.exponent - DECIMAL128_MIN_EXPONENT) as u128;
+    sign | (biased_exponent << 113) | parts.coefficient
+}
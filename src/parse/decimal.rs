@@ -0,0 +1,355 @@
+// Arbitrary-precision decimal to binary conversion, used when the number of
+// significant digits is too large for the Ryū based fast path.
+//
+// This is the "simple decimal conversion" algorithm by Nigel Tao, as used in
+// Wuffs and in the Rust standard library: the decimal is repeatedly shifted by
+// powers of two until it lies in [1/2, 1), and then the binary mantissa is
+// read off with a final left shift. Only the first `MAX_DIGITS` digits are
+// stored; any further digit only matters for breaking a tie, which is tracked
+// in `truncated`.
+
+/// A binary floating point layout that a [`Decimal`] can be rounded to.
+#[derive(Clone, Copy)]
+pub(crate) struct BinaryFormat {
+    /// Number of explicitly stored mantissa bits.
+    pub mantissa_bits: u32,
+
+    /// The unbiased exponent of the smallest normal number minus one, i.e.
+    /// `-bias` for IEEE layouts.
+    pub min_exponent: i32,
+
+    /// The biased exponent which is reserved for infinity, i.e. one past the
+    /// largest biased exponent of a finite number.
+    pub infinite_power: i32,
+}
+
+impl BinaryFormat {
+    pub const F64: Self = Self::ieee(52, 11);
+
+    /// An IEEE 754 layout with the given number of mantissa and exponent
+    /// bits.
+    pub const fn ieee(mantissa_bits: u32, exponent_bits: u32) -> Self {
+        Self {
+            mantissa_bits,
+            min_exponent: 1 - (1 << (exponent_bits - 1)),
+            infinite_power: (1 << exponent_bits) - 1,
+        }
+    }
+}
+
+pub(crate) struct Decimal {
+    /// The number of significant digits, which may exceed `MAX_DIGITS`.
+    pub num_digits: usize,
+
+    /// The position of the decimal point relative to `digits`, the value is
+    /// `0.d0 d1 d2 ... * 10^decimal_point`.
+    pub decimal_point: i32,
+
+    /// Whether any nonzero digit has been dropped past `MAX_DIGITS`.
+    pub truncated: bool,
+
+    /// The digits, most significant first, as values in 0..=9.
+    ///
+    /// One extra slot is kept as scratch space for `left_shift`.
+    pub digits: [u8; Self::MAX_DIGITS + 1],
+}
+
+impl Decimal {
+    /// The largest magnitude of `decimal_point` that is tracked.
+    const DECIMAL_POINT_RANGE: i32 = 2047;
+
+    /// The maximum number of digits required to unambiguously round a
+    /// binary64 number. Since the smallest binary64 subnormal is 2^-1074,
+    /// a halfway point needs at most 767 significant digits, plus one more
+    /// to decide the direction.
+    pub const MAX_DIGITS: usize = 768;
+
+    /// The largest shift that `left_shift` and `right_shift` support without
+    /// overflowing `u64`.
+    const MAX_SHIFT: u32 = 60;
+
+    pub const fn new() -> Self {
+        Self {
+            num_digits: 0,
+            decimal_point: 0,
+            truncated: false,
+            digits: [0; Self::MAX_DIGITS + 1],
+        }
+    }
+
+    /// Appends a digit. Leading zeros must be handled by the caller by
+    /// adjusting `decimal_point`.
+    #[inline]
+    pub const fn push_digit(&mut self, digit: u8) {
+        if self.num_digits < Self::MAX_DIGITS {
+            self.digits[self.num_digits] = digit;
+        } else if digit != 0 {
+            self.truncated = true;
+        }
+        self.num_digits += 1;
+    }
+
+    /// Clamps `num_digits` to the storage capacity and trims trailing zeros.
+    pub const fn finish(&mut self) {
+        if self.num_digits > Self::MAX_DIGITS {
+            self.num_digits = Self::MAX_DIGITS;
+        }
+        self.trim();
+    }
+
+    const fn trim(&mut self) {
+        while self.num_digits != 0 && self.digits[self.num_digits - 1] == 0 {
+            self.num_digits -= 1;
+        }
+    }
+
+    /// Rounds the value to an integer, ties to even.
+    const fn round(&self) -> u64 {
+        if self.num_digits == 0 || self.decimal_point < 0 {
+            return 0;
+        } else if self.decimal_point > 19 {
+            return u64::MAX;
+        }
+
+        let dp = self.decimal_point as usize;
+        let mut n = 0u64;
+        let mut i = 0;
+        while i < dp {
+            n *= 10;
+            if i < self.num_digits {
+                n += self.digits[i] as u64;
+            }
+            i += 1;
+        }
+
+        let mut round_up = false;
+        if dp < self.num_digits {
+            round_up = self.digits[dp] >= 5;
+            if self.digits[dp] == 5 && dp + 1 == self.num_digits {
+                round_up = self.truncated || (dp != 0 && self.digits[dp - 1] & 1 != 0);
+            }
+        }
+        n + round_up as u64
+    }
+
+    /// Multiplies the value by 2^shift.
+    const fn left_shift(&mut self, shift: u32) {
+        debug_assert!(shift <= Self::MAX_SHIFT);
+        if self.num_digits == 0 {
+            return;
+        }
+
+        // Multiplying by 2^shift adds either floor(shift * log10(2)) or one
+        // more digit. We reserve room for the larger count and move the
+        // digits down afterwards if the leading slot stays unused.
+        let max_new_digits = ((shift * 1233) >> 12) as usize + 1;
+        let mut read_index = self.num_digits;
+        let mut write_index = self.num_digits + max_new_digits;
+        let mut n = 0u64;
+        while read_index != 0 || n != 0 {
+            if read_index != 0 {
+                read_index -= 1;
+                n += (self.digits[read_index] as u64) << shift;
+            }
+            write_index -= 1;
+            let quotient = n / 10;
+            let remainder = n - 10 * quotient;
+            if write_index <= Self::MAX_DIGITS {
+                self.digits[write_index] = remainder as u8;
+            } else if remainder != 0 {
+                self.truncated = true;
+            }
+            n = quotient;
+        }
+
+        let mut num_new_digits = max_new_digits;
+        if write_index != 0 {
+            debug_assert!(write_index == 1);
+            num_new_digits -= 1;
+            let end = if self.num_digits + max_new_digits > Self::MAX_DIGITS {
+                Self::MAX_DIGITS
+            } else {
+                self.num_digits + max_new_digits - 1
+            };
+            let mut i = 0;
+            while i < end {
+                self.digits[i] = self.digits[i + 1];
+                i += 1;
+            }
+        }
+
+        self.num_digits += num_new_digits;
+        if self.num_digits > Self::MAX_DIGITS {
+            if self.digits[Self::MAX_DIGITS] != 0 && write_index == 0 {
+                self.truncated = true;
+            }
+            self.num_digits = Self::MAX_DIGITS;
+        }
+        self.decimal_point += num_new_digits as i32;
+        self.trim();
+    }
+
+    /// Divides the value by 2^shift.
+    const fn right_shift(&mut self, shift: u32) {
+        debug_assert!(shift <= Self::MAX_SHIFT);
+        let mut read_index = 0;
+        let mut write_index = 0;
+        let mut n = 0u64;
+        while (n >> shift) == 0 {
+            if read_index < self.num_digits {
+                n = 10 * n + self.digits[read_index] as u64;
+                read_index += 1;
+            } else if n == 0 {
+                return;
+            } else {
+                while (n >> shift) == 0 {
+                    n *= 10;
+                    read_index += 1;
+                }
+                break;
+            }
+        }
+
+        self.decimal_point -= read_index as i32 - 1;
+        if self.decimal_point < -Self::DECIMAL_POINT_RANGE {
+            self.num_digits = 0;
+            self.decimal_point = 0;
+            self.truncated = false;
+            return;
+        }
+
+        let mask = (1u64 << shift) - 1;
+        while read_index < self.num_digits {
+            let new_digit = (n >> shift) as u8;
+            n = 10 * (n & mask) + self.digits[read_index] as u64;
+            read_index += 1;
+            self.digits[write_index] = new_digit;
+            write_index += 1;
+        }
+        while n > 0 {
+            let new_digit = (n >> shift) as u8;
+            n = 10 * (n & mask);
+            if write_index < Self::MAX_DIGITS {
+                self.digits[write_index] = new_digit;
+                write_index += 1;
+            } else if new_digit > 0 {
+                self.truncated = true;
+            }
+        }
+        self.num_digits = write_index;
+        self.trim();
+    }
+
+    /// Converts the decimal to the unsigned bit pattern of the nearest value
+    /// in the given binary format, ties to even.
+    ///
+    /// Values too large for the format are returned as the bit pattern with
+    /// a biased exponent of `infinite_power` and a zero mantissa.
+    pub const fn round_to_binary(&mut self, format: BinaryFormat) -> u64 {
+        const POWERS: [u8; 19] = [
+            0, 3, 6, 9, 13, 16, 19, 23, 26, 29, 33, 36, 39, 43, 46, 49, 53, 56, 59,
+        ];
+
+        const fn get_shift(n: i32) -> u32 {
+            if (n as usize) < POWERS.len() {
+                POWERS[n as usize] as u32
+            } else {
+                Decimal::MAX_SHIFT
+            }
+        }
+
+        let infinity = (format.infinite_power as u64) << format.mantissa_bits;
+
+        // These bounds hold for every layout no wider than binary64.
+        if self.num_digits == 0 || self.decimal_point < -324 {
+            return 0;
+        } else if self.decimal_point >= 310 {
+            return infinity;
+        }
+
+        let mut exp2 = 0i32;
+        while self.decimal_point > 0 {
+            let shift = get_shift(self.decimal_point);
+            self.right_shift(shift);
+            if self.decimal_point < -Self::DECIMAL_POINT_RANGE {
+                return 0;
+            }
+            exp2 += shift as i32;
+        }
+        while self.decimal_point <= 0 {
+            let shift = if self.decimal_point == 0 {
+                match self.digits[0] {
+                    5..=9 => break,
+                    0 | 1 => 2,
+                    _ => 1,
+                }
+            } else {
+                get_shift(-self.decimal_point)
+            };
+            self.left_shift(shift);
+            if self.decimal_point > Self::DECIMAL_POINT_RANGE {
+                return infinity;
+            }
+            exp2 -= shift as i32;
+        }
+
+        // We are now in the range [1/2, 1) but the binary format uses [1, 2).
+        exp2 -= 1;
+        while format.min_exponent + 1 > exp2 {
+            let mut n = (format.min_exponent + 1 - exp2) as u32;
+            if n > Self::MAX_SHIFT {
+                n = Self::MAX_SHIFT;
+            }
+            self.right_shift(n);
+            exp2 += n as i32;
+        }
+        if exp2 - format.min_exponent >= format.infinite_power {
+            return infinity;
+        }
+
+        // Shift the decimal to the hidden bit, and then round the value to get
+        // the high mantissa + 1 bits.
+        self.left_shift(format.mantissa_bits + 1);
+        let mut mantissa = self.round();
+        if mantissa >= (1u64 << (format.mantissa_bits + 1)) {
+            // Rounding up overflowed to the carry bit, need to shift back to
+            // the hidden bit.
+            self.right_shift(1);
+            exp2 += 1;
+            mantissa = self.round();
+            if exp2 - format.min_exponent >= format.infinite_power {
+                return infinity;
+            }
+        }
+
+        let mut power2 = exp2 - format.min_exponent;
+        if mantissa < (1u64 << format.mantissa_bits) {
+            power2 -= 1;
+        }
+        mantissa &= (1u64 << format.mantissa_bits) - 1;
+        ((power2 as u64) << format.mantissa_bits) | mantissa
+    }
+
+    /// Rounds the value times 2^frac_bits to an integer, ties to even.
+    ///
+    /// Values of at least 10^19 are returned as `u64::MAX`.
+    pub const fn round_to_fixed(&mut self, frac_bits: u32) -> u64 {
+        if self.num_digits == 0 {
+            return 0;
+        } else if self.decimal_point > 19 {
+            return u64::MAX;
+        }
+
+        let mut shift = frac_bits;
+        while shift > 0 {
+            let n = if shift > Self::MAX_SHIFT {
+                Self::MAX_SHIFT
+            } else {
+                shift
+            };
+            self.left_shift(n);
+            shift -= n;
+        }
+        self.round()
+    }
+}
@@ -0,0 +1,105 @@
+// Correctly rounded conversion of mantissa * radix^exponent to binary, for
+// the parsers of numbers in a radix other than 10.
+//
+// The mantissa has at most 64 bits, so the exact value fits in the bignum of
+// `d2fixed` when it is not far out of the range of the format: a positive
+// exponent multiplies it by at most 2^1025, and for a negative one, it is
+// scaled by 2^1076 before the division, which keeps two bits below the
+// smallest subnormal and tracks the rest of the remainder as a sticky bit.
+
+use super::decimal::BinaryFormat;
+use crate::d2fixed::{div_small, mul_small, shl, shr, Big, LIMBS};
+use crate::d2radix::floor_log_radix_pow2;
+
+#[inline]
+const fn bit_length(x: &Big) -> u32 {
+    let mut k = LIMBS;
+    while k > 0 {
+        k -= 1;
+        if x[k] != 0 {
+            return 64 * k as u32 + 64 - x[k].leading_zeros();
+        }
+    }
+    0
+}
+
+/// Converts mantissa * radix^exponent to the unsigned bit pattern of the
+/// nearest value in the given binary format, ties to even.
+///
+/// Values too large for the format are returned as the bit pattern with a
+/// biased exponent of `infinite_power` and a zero mantissa.
+pub(crate) const fn radix_to_binary(
+    mantissa: u64,
+    exponent: i32,
+    radix: u32,
+    format: BinaryFormat,
+) -> u64 {
+    let infinity = (format.infinite_power as u64) << format.mantissa_bits;
+    if mantissa == 0 {
+        return 0;
+    }
+
+    // The values from 2^max_exponent up are infinite, and the lowest bit of
+    // the smallest subnormal is 2^min_lsb.
+    let max_exponent = format.infinite_power + format.min_exponent;
+    let min_lsb = format.min_exponent + 1 - format.mantissa_bits as i32;
+
+    let mut x = [0u64; LIMBS];
+    x[0] = mantissa;
+    let mut e2 = 0;
+    let mut sticky = false;
+    if exponent >= 0 {
+        // Otherwise radix^exponent > 2^(max_exponent + 1).
+        if exponent > floor_log_radix_pow2(max_exponent + 1, radix) {
+            return infinity;
+        }
+        let mut p = exponent;
+        while p > 0 {
+            x = mul_small(x, radix as u64);
+            p -= 1;
+        }
+    } else {
+        // Otherwise the value is less than 2^64 / radix^-exponent <
+        // 2^(min_lsb - 2), a quarter of the smallest subnormal.
+        if -exponent > floor_log_radix_pow2(66 - min_lsb, radix) {
+            return 0;
+        }
+        e2 = min_lsb - 2;
+        x = shl(x, -e2 as u32);
+        let mut p = -exponent;
+        while p > 0 {
+            sticky |= div_small(&mut x, radix as u64) != 0;
+            p -= 1;
+        }
+    }
+
+    // The value is x * 2^e2 rounded to the bit at 2^lsb.
+    let length = bit_length(&x) as i32;
+    let mut lsb = length - 1 + e2 - format.mantissa_bits as i32;
+    if lsb < min_lsb {
+        lsb = min_lsb;
+    }
+    let shift = lsb - e2;
+    let mut q = if shift <= 0 {
+        x[0] << -shift
+    } else {
+        sticky |= shr(&mut x, shift as u32 - 1);
+        let round = x[0] & 1 != 0;
+        let q = x[0] >> 1;
+        q + (round && (sticky || q & 1 != 0)) as u64
+    };
+    if q == 1 << (format.mantissa_bits + 1) {
+        q >>= 1;
+        lsb += 1;
+    }
+
+    let power2 = if q < 1 << format.mantissa_bits {
+        0
+    } else {
+        lsb - min_lsb + 1
+    };
+    if power2 >= format.infinite_power {
+        return infinity;
+    }
+    ((power2 as u64) << format.mantissa_bits) | (q & ((1u64 << format.mantissa_bits) - 1))
+}
@@ -0,0 +1,718 @@
+//! Spreadsheet number patterns, such as `#,##0.00;(#,##0.00)` and `0.00E+00`,
+//! in the syntax shared by Excel and ICU's `DecimalFormat`.
+//!
+//! A pattern is a positive subpattern, optionally followed by `;` and a
+//! negative subpattern, of which only the prefix and suffix are used. Without
+//! one, negative numbers get a `-` in front of the positive prefix. Each
+//! subpattern is a literal prefix, the number, and a literal suffix:
+//!
+//! - `0` is a digit that is always shown, and `#` one that is shown only if it
+//!   is significant. In the integer part, the `#` come first, and in the
+//!   fraction, the `0` come first, so they give the minimum and maximum number
+//!   of digits.
+//! - `,` in the integer part groups the digits. The group size is the number of
+//!   digits after the last `,`, and the size of the other groups the number
+//!   between the last two, as in `#,##,##0`.
+//! - `.` separates the fraction, and is always shown if the fraction has no
+//!   digits, as in `0.`.
+//! - `E` after the number, with an optional `+` to show the sign of positive
+//!   exponents and at least one `0`, selects scientific notation. The mantissa
+//!   has as many integer digits as there are `0` in the integer part, or an
+//!   exponent that is a multiple of the number of integer digits if there are
+//!   also `#`, as in `##0.##E0`. It is rounded to the minimum number of integer
+//!   digits plus the maximum number of fraction digits.
+//! - `%` and `‰` in the prefix or suffix multiply the number by 100 or 1000.
+//! - Text in single or double quotes is literal, as is the character after a
+//!   backslash, and `''` is a single quote.
+//!
+//! Numbers are rounded from their exact value, with ties to even unless
+//! another [`RoundingMode`] is chosen, and a negative number that rounds to
+//! zero is formatted as zero. NaN is written as "NaN", and the infinities as
+//! "inf" with the prefix and suffix.
+//!
+//! ## Example
+//!
+//! ```
+//! use ryuu::pattern::NumberPattern;
+//!
+//! let mut buffer = [0u8; 64];
+//!
+//! let accounting = NumberPattern::parse("#,##0.00;(#,##0.00)").unwrap();
+//! assert_eq!(
+//!     accounting.write_to(1234567.891, &mut buffer),
+//!     Some("1,234,567.89")
+//! );
+//! assert_eq!(accounting.write_to(-0.5, &mut buffer), Some("(0.50)"));
+//!
+//! let percent = NumberPattern::parse("0.#%").unwrap();
+//! assert_eq!(percent.write_to(0.1234, &mut buffer), Some("12.3%"));
+//!
+//! let scientific = NumberPattern::parse("0.00E+00").unwrap();
+//! assert_eq!(
+//!     scientific.write_to(-12345.0, &mut buffer),
+//!     Some("-1.23E+04")
+//! );
+//! ```
+
+use core::{fmt, slice, str};
+
+use crate::d2exact::is_zero;
+use crate::d2fixed::{
+    add, d2fixed_significant, div_small, f64_parts, mul_small, rounds_up, shl, shr, Remainder,
+    LIMBS, MAX_SIGNIFICANT_DIGITS_F64,
+};
+use crate::d2s::{DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS};
+use crate::format::{put, RoundingMode};
+
+/// The largest number of fraction digits of a pattern without an exponent.
+pub const MAX_FRACTION_DIGITS: u32 = 30;
+
+// 10^19, the largest power of ten in a limb.
+const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+// The number of digits of the largest scaled number, which is below
+// 2^1024 * 10^33 < 10^342, written in chunks of 19 digits.
+const DIGITS_LEN: usize = 19 * 18;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// The error type of [`NumberPattern::parse`].
+pub enum PatternError {
+    /// A subpattern has neither a `0` nor a `#`.
+    MissingDigits,
+
+    /// A `#` follows a `0` in the integer part, a `0` follows a `#` in the
+    /// fraction, or a digit is in the suffix.
+    MisplacedDigit,
+
+    /// A `,` is in the fraction or ends the integer part, there is a second
+    /// `.`, or an `E` has no `0` after it.
+    MisplacedSymbol,
+
+    /// A quote is not closed.
+    UnterminatedQuote,
+
+    /// There are more than [`MAX_FRACTION_DIGITS`] fraction digits, or more
+    /// than 17 significant digits in scientific notation.
+    TooManyDigits,
+
+    /// There are more than two subpatterns.
+    TooManySubpatterns,
+}
+
+impl PatternError {
+    /// Returns a short description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            PatternError::MissingDigits => "missing digits",
+            PatternError::MisplacedDigit => "misplaced digit",
+            PatternError::MisplacedSymbol => "misplaced symbol",
+            PatternError::UnterminatedQuote => "unterminated quote",
+            PatternError::TooManyDigits => "too many digits",
+            PatternError::TooManySubpatterns => "too many subpatterns",
+        }
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A compiled number pattern, which borrows the text of its prefixes and
+/// suffixes from the pattern. See the [module documentation](self) for the
+/// syntax.
+pub struct NumberPattern<'a> {
+    positive_prefix: &'a [u8],
+    positive_suffix: &'a [u8],
+    negative_prefix: &'a [u8],
+    negative_suffix: &'a [u8],
+    explicit_negative: bool,
+    number: NumberFormat,
+    mode: RoundingMode,
+}
+
+// The number part of a subpattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NumberFormat {
+    min_integer_digits: u32,
+    max_integer_digits: u32,
+    min_fraction_digits: u32,
+    max_fraction_digits: u32,
+    decimal_always_shown: bool,
+    grouping: u32,
+    secondary_grouping: u32,
+    // The power of ten that the number is multiplied by, for `%` and `‰`.
+    scale: u32,
+    // The minimum number of exponent digits, which is zero without an
+    // exponent.
+    min_exponent_digits: u32,
+    exponent_plus: bool,
+}
+
+// A subpattern, and the index after it.
+struct Subpattern<'a> {
+    prefix: &'a [u8],
+    suffix: &'a [u8],
+    number: NumberFormat,
+    end: usize,
+}
+
+impl<'a> NumberPattern<'a> {
+    /// Compiles `pattern`.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns a [`PatternError`] if `pattern` does not follow
+    /// the syntax, or needs more digits than are supported.
+    pub const fn parse(pattern: &'a str) -> Result<Self, PatternError> {
+        let bytes = pattern.as_bytes();
+        let positive = match parse_subpattern(bytes, 0) {
+            Ok(positive) => positive,
+            Err(e) => return Err(e),
+        };
+        let number = positive.number;
+        if number.min_exponent_digits == 0 {
+            if number.max_fraction_digits > MAX_FRACTION_DIGITS {
+                return Err(PatternError::TooManyDigits);
+            }
+        } else if scientific_integer_digits(&number) + number.max_fraction_digits
+            > MAX_SIGNIFICANT_DIGITS_F64
+        {
+            return Err(PatternError::TooManyDigits);
+        }
+
+        let mut result = Self {
+            positive_prefix: positive.prefix,
+            positive_suffix: positive.suffix,
+            negative_prefix: positive.prefix,
+            negative_suffix: positive.suffix,
+            explicit_negative: false,
+            number,
+            mode: RoundingMode::HalfEven,
+        };
+        if positive.end < bytes.len() {
+            let negative = match parse_subpattern(bytes, positive.end + 1) {
+                Ok(negative) => negative,
+                Err(e) => return Err(e),
+            };
+            if negative.end < bytes.len() {
+                return Err(PatternError::TooManySubpatterns);
+            }
+            result.negative_prefix = negative.prefix;
+            result.negative_suffix = negative.suffix;
+            result.explicit_negative = true;
+        }
+        Ok(result)
+    }
+
+    /// Returns the pattern with another rounding mode than the default ties
+    /// to even, such as [`RoundingMode::HalfAwayFromZero`] as in Excel.
+    pub const fn with_rounding_mode(mut self, mode: RoundingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The length of the text of `d`.
+    pub const fn str_len(&self, d: f64) -> usize {
+        self.write_prefix(d, &mut [])
+    }
+
+    /// Writes the text of `d` to the start of `buffer` and returns it, or
+    /// returns `None` if `buffer` is shorter than
+    /// [`str_len`](Self::str_len), in which case its contents are
+    /// unspecified.
+    pub const fn write_to<'b>(&self, d: f64, buffer: &'b mut [u8]) -> Option<&'b str> {
+        let len = self.write_prefix(d, buffer);
+        if len > buffer.len() {
+            return None;
+        }
+        // SAFETY: The prefixes and suffixes are copied whole from a `str`
+        // between ASCII characters, and everything else is ASCII.
+        Some(unsafe { str::from_utf8_unchecked(slice::from_raw_parts(buffer.as_ptr(), len)) })
+    }
+
+    // Writes the text of `d` to `buffer`, leaving out the pieces that do not
+    // fit, and returns the length of all of it.
+    const fn write_prefix(&self, d: f64, buffer: &mut [u8]) -> usize {
+        let bits = d.to_bits();
+        let sign = (bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) != 0;
+        let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+        let ieee_exponent =
+            (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+        if ieee_exponent == (1u32 << DOUBLE_EXPONENT_BITS) - 1 {
+            if ieee_mantissa != 0 {
+                return put(buffer, 0, b"NaN");
+            }
+            let offset = self.write_affix(sign, true, buffer, 0);
+            let offset = put(buffer, offset, b"inf");
+            return self.write_affix(sign, false, buffer, offset);
+        }
+
+        if self.number.min_exponent_digits == 0 {
+            self.write_fixed(ieee_mantissa, ieee_exponent, sign, buffer)
+        } else {
+            self.write_scientific(ieee_mantissa, ieee_exponent, sign, buffer)
+        }
+    }
+
+    // Writes the prefix or suffix for the sign.
+    const fn write_affix(
+        &self,
+        negative: bool,
+        prefix: bool,
+        buffer: &mut [u8],
+        offset: usize,
+    ) -> usize {
+        match (negative, prefix) {
+            (false, true) => write_literal(self.positive_prefix, buffer, offset),
+            (false, false) => write_literal(self.positive_suffix, buffer, offset),
+            (true, true) => {
+                let offset = if self.explicit_negative {
+                    offset
+                } else {
+                    put(buffer, offset, b"-")
+                };
+                write_literal(self.negative_prefix, buffer, offset)
+            }
+            (true, false) => write_literal(self.negative_suffix, buffer, offset),
+        }
+    }
+
+    const fn write_fixed(
+        &self,
+        ieee_mantissa: u64,
+        ieee_exponent: u32,
+        sign: bool,
+        buffer: &mut [u8],
+    ) -> usize {
+        let number = &self.number;
+        let (m2, e2) = f64_parts(ieee_mantissa, ieee_exponent);
+        let mut digits = [b'0'; DIGITS_LEN];
+        let start = scaled_digits(
+            m2,
+            e2,
+            number.max_fraction_digits + number.scale,
+            sign,
+            self.mode,
+            &mut digits,
+        );
+        let (_, digits) = digits.split_at(start);
+        let fraction_digits = number.max_fraction_digits as usize;
+        let integer_digits = digits.len().saturating_sub(fraction_digits);
+
+        // The fraction digit at index i, which is zero before the digits.
+        const fn fraction_digit(digits: &[u8], fraction_digits: usize, i: usize) -> u8 {
+            if digits.len() + i >= fraction_digits {
+                digits[digits.len() + i - fraction_digits]
+            } else {
+                b'0'
+            }
+        }
+
+        let mut fraction_len = fraction_digits;
+        while fraction_len > number.min_fraction_digits as usize
+            && fraction_digit(digits, fraction_digits, fraction_len - 1) == b'0'
+        {
+            fraction_len -= 1;
+        }
+        let mut integer_len = if integer_digits > number.min_integer_digits as usize {
+            integer_digits
+        } else {
+            number.min_integer_digits as usize
+        };
+        if integer_len == 0 && fraction_len == 0 {
+            integer_len = 1;
+        }
+
+        let negative = sign && !digits.is_empty();
+        let mut offset = self.write_affix(negative, true, buffer, 0);
+
+        // The integer digits, from the highest power of ten q.
+        let mut q = integer_len;
+        while q > 0 {
+            q -= 1;
+            let digit = if q < integer_digits {
+                digits[integer_digits - 1 - q]
+            } else {
+                b'0'
+            };
+            offset = put(buffer, offset, &[digit]);
+            if q > 0 && number.is_group_boundary(q as u32) {
+                offset = put(buffer, offset, b",");
+            }
+        }
+
+        if fraction_len > 0 || number.decimal_always_shown {
+            offset = put(buffer, offset, b".");
+        }
+        let mut i = 0;
+        while i < fraction_len {
+            offset = put(
+                buffer,
+                offset,
+                &[fraction_digit(digits, fraction_digits, i)],
+            );
+            i += 1;
+        }
+
+        self.write_affix(negative, false, buffer, offset)
+    }
+
+    const fn write_scientific(
+        &self,
+        ieee_mantissa: u64,
+        ieee_exponent: u32,
+        sign: bool,
+        buffer: &mut [u8],
+    ) -> usize {
+        let number = &self.number;
+        let significant = scientific_integer_digits(number) + number.max_fraction_digits;
+        let zero = ieee_exponent == 0 && ieee_mantissa == 0;
+
+        let mut digits = [b'0'; MAX_SIGNIFICANT_DIGITS_F64 as usize];
+        let (exponent, integer_len) = if zero {
+            (0, scientific_integer_digits(number) as i32)
+        } else {
+            let v = d2fixed_significant(ieee_mantissa, ieee_exponent, significant, sign, self.mode);
+            let mut mantissa = v.mantissa;
+            let mut i = significant as usize;
+            while i > 0 {
+                i -= 1;
+                digits[i] = b'0' + (mantissa % 10) as u8;
+                mantissa /= 10;
+            }
+
+            // The exponent of the first digit.
+            let first = v.exponent + significant as i32 - 1 + number.scale as i32;
+            if number.is_engineering() {
+                let step = number.max_integer_digits as i32;
+                let exponent = first.div_euclid(step) * step;
+                (exponent, first - exponent + 1)
+            } else {
+                let integer_len = scientific_integer_digits(number) as i32;
+                (first - (integer_len - 1), integer_len)
+            }
+        };
+
+        // The digit at index i from the left of the mantissa.
+        const fn digit(digits: &[u8], significant: u32, i: i32) -> u8 {
+            if i < significant as i32 {
+                digits[i as usize]
+            } else {
+                b'0'
+            }
+        }
+
+        let mut fraction_len = significant as i32 - integer_len;
+        if fraction_len < number.min_fraction_digits as i32 {
+            fraction_len = number.min_fraction_digits as i32;
+        }
+        while fraction_len > number.min_fraction_digits as i32
+            && digit(&digits, significant, integer_len + fraction_len - 1) == b'0'
+        {
+            fraction_len -= 1;
+        }
+
+        let negative = sign && !zero;
+        let mut offset = self.write_affix(negative, true, buffer, 0);
+        let mut i = 0;
+        while i < integer_len {
+            offset = put(buffer, offset, &[digit(&digits, significant, i)]);
+            i += 1;
+        }
+        if fraction_len > 0 || number.decimal_always_shown {
+            offset = put(buffer, offset, b".");
+        }
+        while i < integer_len + fraction_len {
+            offset = put(buffer, offset, &[digit(&digits, significant, i)]);
+            i += 1;
+        }
+
+        offset = put(buffer, offset, b"E");
+        if exponent < 0 {
+            offset = put(buffer, offset, b"-");
+        } else if number.exponent_plus {
+            offset = put(buffer, offset, b"+");
+        }
+        let mut exponent_digits = [b'0'; 10];
+        let mut magnitude = exponent.unsigned_abs();
+        let mut len = 0;
+        while magnitude > 0 || len < number.min_exponent_digits as usize {
+            exponent_digits[len] = b'0' + (magnitude % 10) as u8;
+            magnitude /= 10;
+            len += 1;
+        }
+        while len > 0 {
+            len -= 1;
+            offset = put(buffer, offset, &[exponent_digits[len]]);
+        }
+
+        self.write_affix(negative, false, buffer, offset)
+    }
+}
+
+impl NumberFormat {
+    // Whether a grouping separator follows the integer digit for 10^q.
+    const fn is_group_boundary(&self, q: u32) -> bool {
+        if self.grouping == 0 || q < self.grouping {
+            return false;
+        }
+        let secondary = if self.secondary_grouping == 0 {
+            self.grouping
+        } else {
+            self.secondary_grouping
+        };
+        (q - self.grouping) % secondary == 0
+    }
+
+    // Whether the exponent of scientific notation is a multiple of the
+    // maximum number of integer digits.
+    const fn is_engineering(&self) -> bool {
+        self.max_integer_digits > self.min_integer_digits && self.max_integer_digits > 1
+    }
+}
+
+// The number of integer digits that the significant digits of scientific
+// notation count.
+const fn scientific_integer_digits(number: &NumberFormat) -> u32 {
+    if number.is_engineering() || number.min_integer_digits == 0 {
+        1
+    } else {
+        number.min_integer_digits
+    }
+}
+
+// Writes the digits of m2 * 2^e2 * 10^decimals, rounded to an integer, to the
+// end of `digits`, and returns the index of the first one. Zero has none.
+const fn scaled_digits(
+    m2: u64,
+    e2: i32,
+    decimals: u32,
+    negative: bool,
+    mode: RoundingMode,
+    digits: &mut [u8; DIGITS_LEN],
+) -> usize {
+    let mut n = [0u64; LIMBS];
+    n[0] = m2;
+    let mut p = decimals;
+    while p >= 19 {
+        n = mul_small(n, CHUNK);
+        p -= 19;
+    }
+    n = mul_small(n, 10u64.pow(p));
+
+    let remainder = if e2 >= 0 {
+        n = shl(n, e2 as u32);
+        Remainder::Zero
+    } else {
+        // Keep one more bit, which tells whether the remainder is at least a
+        // half.
+        let inexact = shr(&mut n, (-e2 - 1) as u32);
+        let half = n[0] & 1 != 0;
+        shr(&mut n, 1);
+        match (half, inexact) {
+            (false, false) => Remainder::Zero,
+            (false, true) => Remainder::BelowHalf,
+            (true, false) => Remainder::Half,
+            (true, true) => Remainder::AboveHalf,
+        }
+    };
+    if rounds_up(n[0] & 1 != 0, remainder, negative, mode) {
+        let mut one = [0u64; LIMBS];
+        one[0] = 1;
+        n = add(n, &one);
+    }
+
+    let mut start = DIGITS_LEN;
+    while !is_zero(&n) {
+        let mut chunk = div_small(&mut n, CHUNK);
+        let mut i = 0;
+        while i < 19 {
+            start -= 1;
+            digits[start] = b'0' + (chunk % 10) as u8;
+            chunk /= 10;
+            i += 1;
+        }
+    }
+    while start < DIGITS_LEN && digits[start] == b'0' {
+        start += 1;
+    }
+    start
+}
+
+// Writes a prefix or suffix without its quotes and escapes.
+const fn write_literal(text: &[u8], buffer: &mut [u8], mut offset: usize) -> usize {
+    let mut quote = 0u8;
+    let mut i = 0;
+    while i < text.len() {
+        let c = text[i];
+        if c == b'\'' && quote != b'"' && i + 1 < text.len() && text[i + 1] == b'\'' {
+            offset = put(buffer, offset, b"'");
+            i += 2;
+            continue;
+        }
+        if quote == 0 && (c == b'\'' || c == b'"') {
+            quote = c;
+        } else if quote != 0 && c == quote {
+            quote = 0;
+        } else if quote == 0 && c == b'\\' && i + 1 < text.len() {
+            offset = put(buffer, offset, &[text[i + 1]]);
+            i += 1;
+        } else {
+            offset = put(buffer, offset, &[c]);
+        }
+        i += 1;
+    }
+    offset
+}
+
+// Returns the index of the first character from `i` on that is not quoted,
+// escaped or part of a prefix or suffix, and the power of ten that the `%` and
+// `‰` on the way scale by.
+const fn skip_literal(pattern: &[u8], mut i: usize) -> Result<(usize, u32), PatternError> {
+    let mut quote = 0u8;
+    let mut scale = 0;
+    while i < pattern.len() {
+        let c = pattern[i];
+        if quote != 0 {
+            if c == quote {
+                quote = 0;
+            }
+        } else {
+            match c {
+                b'\'' | b'"' => quote = c,
+                b'\\' if i + 1 < pattern.len() => i += 1,
+                b'0' | b'#' | b',' | b'.' | b';' => break,
+                b'%' => scale = 2,
+                // The UTF-8 encoding of U+2030 PER MILLE SIGN.
+                0xe2 if i + 2 < pattern.len()
+                    && pattern[i + 1] == 0x80
+                    && pattern[i + 2] == 0xb0 =>
+                {
+                    scale = 3;
+                    i += 2;
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    if quote != 0 {
+        return Err(PatternError::UnterminatedQuote);
+    }
+    Ok((i, scale))
+}
+
+// Parses the subpattern that starts at `start` and ends before the next `;`
+// or at the end.
+const fn parse_subpattern(pattern: &[u8], start: usize) -> Result<Subpattern<'_>, PatternError> {
+    let (mut i, prefix_scale) = match skip_literal(pattern, start) {
+        Ok(skipped) => skipped,
+        Err(e) => return Err(e),
+    };
+    let prefix = pattern.split_at(i).0.split_at(start).1;
+
+    let mut integer_hashes = 0;
+    let mut integer_zeros = 0;
+    let mut fraction_zeros = 0;
+    let mut fraction_hashes = 0;
+    let mut in_fraction = false;
+    let mut seen_comma = false;
+    let mut group_digits = 0;
+    let mut secondary_grouping = 0;
+    while i < pattern.len() {
+        match (pattern[i], in_fraction) {
+            (b'#', false) => {
+                if integer_zeros > 0 {
+                    return Err(PatternError::MisplacedDigit);
+                }
+                integer_hashes += 1;
+                group_digits += 1;
+            }
+            (b'0', false) => {
+                integer_zeros += 1;
+                group_digits += 1;
+            }
+            (b',', false) => {
+                if seen_comma {
+                    secondary_grouping = group_digits;
+                }
+                seen_comma = true;
+                group_digits = 0;
+            }
+            (b'.', false) => in_fraction = true,
+            (b'0', true) => {
+                if fraction_hashes > 0 {
+                    return Err(PatternError::MisplacedDigit);
+                }
+                fraction_zeros += 1;
+            }
+            (b'#', true) => fraction_hashes += 1,
+            (b',' | b'.', true) => return Err(PatternError::MisplacedSymbol),
+            _ => break,
+        }
+        i += 1;
+    }
+    if integer_hashes + integer_zeros + fraction_zeros + fraction_hashes == 0 {
+        return Err(PatternError::MissingDigits);
+    }
+    if seen_comma && group_digits == 0 {
+        return Err(PatternError::MisplacedSymbol);
+    }
+
+    let mut min_exponent_digits = 0;
+    let mut exponent_plus = false;
+    if i < pattern.len() && pattern[i] == b'E' {
+        i += 1;
+        if i < pattern.len() && (pattern[i] == b'+' || pattern[i] == b'-') {
+            exponent_plus = pattern[i] == b'+';
+            i += 1;
+        }
+        while i < pattern.len() && pattern[i] == b'0' {
+            min_exponent_digits += 1;
+            i += 1;
+        }
+        if min_exponent_digits == 0 {
+            return Err(PatternError::MisplacedSymbol);
+        }
+    }
+
+    let suffix_start = i;
+    let (end, suffix_scale) = match skip_literal(pattern, suffix_start) {
+        Ok(skipped) => skipped,
+        Err(e) => return Err(e),
+    };
+    if end < pattern.len() {
+        match pattern[end] {
+            b';' => {}
+            b'0' | b'#' => return Err(PatternError::MisplacedDigit),
+            _ => return Err(PatternError::MisplacedSymbol),
+        }
+    }
+    let suffix = pattern.split_at(end).0.split_at(suffix_start).1;
+
+    Ok(Subpattern {
+        prefix,
+        suffix,
+        number: NumberFormat {
+            min_integer_digits: integer_zeros,
+            max_integer_digits: integer_hashes + integer_zeros,
+            min_fraction_digits: fraction_zeros,
+            max_fraction_digits: fraction_zeros + fraction_hashes,
+            decimal_always_shown: in_fraction && fraction_zeros + fraction_hashes == 0,
+            grouping: if seen_comma { group_digits } else { 0 },
+            secondary_grouping,
+            scale: if suffix_scale > prefix_scale {
+                suffix_scale
+            } else {
+                prefix_scale
+            },
+            min_exponent_digits,
+            exponent_plus,
+        },
+        end,
+    })
+}
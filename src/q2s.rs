@@ -0,0 +1,220 @@
+// Translated from C to Rust. The original C code can be found at
+// https://github.com/ulfjack/ryu and carries the following license:
+//
+// Copyright 2018 Ulf Adams
+//
+// The contents of this file may be used under the terms of the Apache License,
+// Version 2.0.
+//
+//    (See accompanying file LICENSE-Apache or copy at
+//     http://www.apache.org/licenses/LICENSE-2.0)
+//
+// Alternatively, the contents of this file may be used under the terms of
+// the Boost Software License, Version 1.0.
+//    (See accompanying file LICENSE-Boost or copy at
+//     https://www.boost.org/LICENSE_1_0.txt)
+//
+// Unless required by applicable law or agreed to in writing, this software
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.
+
+// Shortest round-trip formatting of floats with up to 124 mantissa bits, such
+// as IEEE 754 binary128, using 128-bit mantissas and 256-bit powers of 5. This
+// follows the generic 128-bit variant of the C implementation.
+
+use crate::q2s_intrinsics::{
+    compute_inv_pow5_128, compute_pow5_128, log10_pow2_128, log10_pow5_128, mul_shift_128,
+    multiple_of_power_of_2_128, multiple_of_power_of_5_128, pow5bits_128, Q_POW5_BITCOUNT,
+    Q_POW5_INV_BITCOUNT,
+};
+
+pub const QUAD_MANTISSA_BITS: u32 = 112;
+pub const QUAD_EXPONENT_BITS: u32 = 15;
+const QUAD_BIAS: i32 = 16383;
+
+// The x87 extended precision format stores the integer bit explicitly, so its
+// 64-bit mantissa has 63 fraction bits.
+pub const X87_MANTISSA_BITS: u32 = 63;
+pub const X87_EXPONENT_BITS: u32 = 15;
+const X87_BIAS: i32 = 16383;
+
+#[inline]
+pub const fn decimal_length39(v: u128) -> u32 {
+    let mut length = 1;
+    let mut threshold = 10u128;
+    while length < 39 && v >= threshold {
+        length += 1;
+        if length < 39 {
+            threshold *= 10;
+        }
+    }
+    length
+}
+
+// A floating decimal representing m * 10^e.
+pub struct FloatingDecimal128 {
+    pub mantissa: u128,
+    // Decimal exponent's range is -4966 to 4932 for binary128, and -4951 to
+    // 4932 for x87 extended precision.
+    pub exponent: i32,
+}
+
+#[inline]
+pub const fn q2d(ieee_mantissa: u128, ieee_exponent: u32) -> FloatingDecimal128 {
+    q2d_layout::<QUAD_MANTISSA_BITS, QUAD_BIAS, false>(ieee_mantissa, ieee_exponent)
+}
+
+#[inline]
+pub const fn x2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal128 {
+    q2d_layout::<X87_MANTISSA_BITS, X87_BIAS, true>(ieee_mantissa as u128, ieee_exponent)
+}
+
+// Same as `q2d`, for any binary layout with at most 124 mantissa bits and an
+// exponent range no wider than binary128's.
+//
+// `MANTISSA_BITS` counts the fraction bits only. With `EXPLICIT_LEADING_BIT`,
+// `ieee_mantissa` additionally carries the integer bit at position
+// `MANTISSA_BITS` and is taken as is for every exponent, as in the x87
+// extended precision format.
+#[inline]
+pub const fn q2d_layout<
+    const MANTISSA_BITS: u32,
+    const BIAS: i32,
+    const EXPLICIT_LEADING_BIT: bool,
+>(
+    ieee_mantissa: u128,
+    ieee_exponent: u32,
+) -> FloatingDecimal128 {
+    let biased_exponent = if ieee_exponent == 0 {
+        1
+    } else {
+        ieee_exponent as i32
+    };
+    // We subtract 2 so that the bounds computation has 2 additional bits.
+    let e2 = biased_exponent - BIAS - MANTISSA_BITS as i32 - 2;
+    let m2 = if EXPLICIT_LEADING_BIT || ieee_exponent == 0 {
+        ieee_mantissa
+    } else {
+        (1u128 << MANTISSA_BITS) | ieee_mantissa
+    };
+    let even = (m2 & 1) == 0;
+    let accept_bounds = even;
+
+    // Step 2: Determine the interval of valid decimal representations.
+    let mv = 4 * m2;
+    let lowest_fraction = if EXPLICIT_LEADING_BIT {
+        1u128 << MANTISSA_BITS
+    } else {
+        0
+    };
+    // Implicit bool -> int conversion. True is 1, false is 0.
+    let mm_shift = (ieee_mantissa != lowest_fraction || ieee_exponent <= 1) as u32;
+    // We would compute mp and mm like this:
+    // uint128_t mp = 4 * m2 + 2;
+    // uint128_t mm = mv - 1 - mm_shift;
+
+    // Step 3: Convert to a decimal power base using 256-bit arithmetic.
+    let mut vr: u128;
+    let mut vp: u128;
+    let mut vm: u128;
+    let e10: i32;
+    let mut vm_is_trailing_zeros = false;
+    let mut vr_is_trailing_zeros = false;
+    if e2 >= 0 {
+        // This expression is slightly faster than max(0, log10_pow2(e2) - 1).
+        let q = log10_pow2_128(e2) - (e2 > 3) as u32;
+        e10 = q as i32;
+        let k = Q_POW5_INV_BITCOUNT + pow5bits_128(q as i32) - 1;
+        let i = -e2 + q as i32 + k;
+        let pow5 = compute_inv_pow5_128(q);
+        vr = mul_shift_128(mv, &pow5, i as u32);
+        vp = mul_shift_128(mv + 2, &pow5, i as u32);
+        vm = mul_shift_128(mv - 1 - mm_shift as u128, &pow5, i as u32);
+        // floor(log_5(2^128)) = 55, this is very conservative.
+        if q <= 55 {
+            // Only one of mp, mv, and mm can be a multiple of 5, if any.
+            if mv % 5 == 0 {
+                vr_is_trailing_zeros = multiple_of_power_of_5_128(mv, q);
+            } else if accept_bounds {
+                vm_is_trailing_zeros = multiple_of_power_of_5_128(mv - 1 - mm_shift as u128, q);
+            } else {
+                vp -= multiple_of_power_of_5_128(mv + 2, q) as u128;
+            }
+        }
+    } else {
+        // This expression is slightly faster than max(0, log10_pow5(-e2) - 1).
+        let q = log10_pow5_128(-e2) - (-e2 > 1) as u32;
+        e10 = q as i32 + e2;
+        let i = -e2 - q as i32;
+        let k = pow5bits_128(i) - Q_POW5_BITCOUNT;
+        let j = q as i32 - k;
+        let pow5 = compute_pow5_128(i as u32);
+        vr = mul_shift_128(mv, &pow5, j as u32);
+        vp = mul_shift_128(mv + 2, &pow5, j as u32);
+        vm = mul_shift_128(mv - 1 - mm_shift as u128, &pow5, j as u32);
+        if q <= 1 {
+            // {vr,vp,vm} is trailing zeros if {mv,mp,mm} has at least q trailing 0 bits.
+            // mv = 4 * m2, so it always has at least two trailing 0 bits.
+            vr_is_trailing_zeros = true;
+            if accept_bounds {
+                // mm = mv - 1 - mm_shift, so it has 1 trailing 0 bit iff mm_shift == 1.
+                vm_is_trailing_zeros = mm_shift == 1;
+            } else {
+                // mp = mv + 2, so it always has at least one trailing 0 bit.
+                vp -= 1;
+            }
+        } else if q < 127 {
+            // We want to know if the full product has at least q trailing zeros.
+            // We need to compute min(p2(mv), p5(mv) - e2) >= q
+            // <=> p2(mv) >= q && p5(mv) - e2 >= q
+            // <=> p2(mv) >= q (because -e2 >= q)
+            vr_is_trailing_zeros = multiple_of_power_of_2_128(mv, q);
+        }
+    }
+
+    // Step 4: Find the shortest decimal representation in the interval of valid
+    // representations.
+    let mut removed = 0i32;
+    let mut last_removed_digit = 0u8;
+    while vp / 10 > vm / 10 {
+        vm_is_trailing_zeros &= vm % 10 == 0;
+        vr_is_trailing_zeros &= last_removed_digit == 0;
+        last_removed_digit = (vr % 10) as u8;
+        vr /= 10;
+        vp /= 10;
+        vm /= 10;
+        removed += 1;
+    }
+    if vm_is_trailing_zeros {
+        while vm % 10 == 0 {
+            vr_is_trailing_zeros &= last_removed_digit == 0;
+            last_removed_digit = (vr % 10) as u8;
+            vr /= 10;
+            vm /= 10;
+            removed += 1;
+        }
+    }
+    if vr_is_trailing_zeros && last_removed_digit == 5 && vr % 2 == 0 {
+        // Round even if the exact number is .....50..0.
+        last_removed_digit = 4;
+    }
+    // We need to take vr + 1 if vr is outside bounds or we need to round up.
+    let output = vr
+        + ((vr == vm && (!accept_bounds || !vm_is_trailing_zeros)) || last_removed_digit >= 5)
+            as u128;
+    let exp = e10 + removed;
+
+    FloatingDecimal128 {
+        exponent: exp,
+        mantissa: output,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod q2s_intrinsics_test {
+        #![allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+
+        include!("../unittests/q2s_intrinsics_test.rs");
+    }
+}
@@ -0,0 +1,190 @@
+// Translated from C to Rust. The original C code can be found at
+// https://github.com/ulfjack/ryu and carries the following license:
+//
+// Copyright 2018 Ulf Adams
+//
+// The contents of this file may be used under the terms of the Apache License,
+// Version 2.0.
+//
+//    (See accompanying file LICENSE-Apache or copy at
+//     http://www.apache.org/licenses/LICENSE-2.0)
+//
+// Alternatively, the contents of this file may be used under the terms of
+// the Boost Software License, Version 1.0.
+//    (See accompanying file LICENSE-Boost or copy at
+//     https://www.boost.org/LICENSE_1_0.txt)
+//
+// Unless required by applicable law or agreed to in writing, this software
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.
+
+// 256-bit arithmetic for the 128-bit generic Ryū. Multi-word values are
+// stored as arrays of 64-bit limbs, least significant limb first.
+
+use crate::q2s_table::{
+    Q_POW5_ERRORS, Q_POW5_INV_ERRORS, Q_POW5_INV_SPLIT, Q_POW5_SPLIT, Q_POW5_TABLE,
+    Q_POW5_TABLE_SIZE,
+};
+
+pub const Q_POW5_INV_BITCOUNT: i32 = 249;
+pub const Q_POW5_BITCOUNT: i32 = 249;
+
+// Returns e == 0 ? 1 : ceil(log_2(5^e)); requires 0 <= e <= 5999.
+#[inline]
+pub const fn pow5bits_128(e: i32) -> i32 {
+    debug_assert!(e >= 0);
+    debug_assert!(e <= 5999);
+    (((e as u64 * 163391164108059) >> 46) + 1) as i32
+}
+
+// Returns floor(log_10(2^e)); requires 0 <= e <= 16999.
+#[inline]
+pub const fn log10_pow2_128(e: i32) -> u32 {
+    debug_assert!(e >= 0);
+    debug_assert!(e <= 16999);
+    ((e as u64 * 169464822037455) >> 49) as u32
+}
+
+// Returns floor(log_10(5^e)); requires 0 <= e <= 16999.
+#[inline]
+pub const fn log10_pow5_128(e: i32) -> u32 {
+    debug_assert!(e >= 0);
+    debug_assert!(e <= 16999);
+    ((e as u64 * 196742565691928) >> 48) as u32
+}
+
+#[inline]
+pub(crate) const fn pow5_factor_128(mut value: u128) -> u32 {
+    let mut count = 0u32;
+    loop {
+        debug_assert!(value != 0);
+        if value % 5 != 0 {
+            break;
+        }
+        value /= 5;
+        count += 1;
+    }
+    count
+}
+
+// Returns true if value is divisible by 5^p.
+#[inline]
+pub const fn multiple_of_power_of_5_128(value: u128, p: u32) -> bool {
+    pow5_factor_128(value) >= p
+}
+
+// Returns true if value is divisible by 2^p.
+#[inline]
+pub const fn multiple_of_power_of_2_128(value: u128, p: u32) -> bool {
+    debug_assert!(value != 0);
+    debug_assert!(p < 128);
+    (value & ((1u128 << p) - 1)) == 0
+}
+
+// Returns the full 384-bit product of a and b.
+#[inline]
+const fn mul_256_128(a: &[u64; 4], b: u128) -> [u64; 6] {
+    let b = [b as u64, (b >> 64) as u64];
+    let mut product = [0u64; 6];
+    let mut i = 0;
+    while i < 4 {
+        let mut carry = 0u128;
+        let mut j = 0;
+        while j < 2 {
+            let t = a[i] as u128 * b[j] as u128 + product[i + j] as u128 + carry;
+            product[i + j] = t as u64;
+            carry = t >> 64;
+            j += 1;
+        }
+        product[i + 2] = carry as u64;
+        i += 1;
+    }
+    product
+}
+
+// Returns the low 256 bits of x >> shift.
+#[inline]
+const fn shr_384(x: &[u64; 6], shift: u32) -> [u64; 4] {
+    debug_assert!(shift < 384);
+    let limbs = (shift / 64) as usize;
+    let bits = shift % 64;
+    let mut result = [0u64; 4];
+    let mut k = 0;
+    while k < 4 && k + limbs < 6 {
+        result[k] = x[k + limbs] >> bits;
+        if bits != 0 && k + limbs + 1 < 6 {
+            result[k] |= x[k + limbs + 1] << (64 - bits);
+        }
+        k += 1;
+    }
+    result
+}
+
+// Returns x + (v - 1) for v in 0..=3.
+#[inline]
+const fn add_biased(mut x: [u64; 4], v: u64) -> [u64; 4] {
+    if v >= 1 {
+        let mut carry = v - 1;
+        let mut k = 0;
+        while k < 4 && carry != 0 {
+            let (sum, overflow) = x[k].overflowing_add(carry);
+            x[k] = sum;
+            carry = overflow as u64;
+            k += 1;
+        }
+    } else {
+        let mut k = 0;
+        loop {
+            let (difference, borrow) = x[k].overflowing_sub(1);
+            x[k] = difference;
+            if !borrow {
+                break;
+            }
+            k += 1;
+        }
+    }
+    x
+}
+
+// Computes 5^i in the form required by Ryū, i.e. the top Q_POW5_BITCOUNT bits.
+#[inline]
+pub const fn compute_pow5_128(i: u32) -> [u64; 4] {
+    let base = i / Q_POW5_TABLE_SIZE;
+    let base2 = base * Q_POW5_TABLE_SIZE;
+    debug_assert!(base < Q_POW5_SPLIT.len() as u32);
+    let mul = &Q_POW5_SPLIT[base as usize];
+    if i == base2 {
+        return *mul;
+    }
+    let offset = i - base2;
+    let m = Q_POW5_TABLE[offset as usize];
+    let delta = pow5bits_128(i as i32) - pow5bits_128(base2 as i32);
+    let corr = (Q_POW5_ERRORS[(i / 32) as usize] >> (2 * (i % 32))) & 3;
+    add_biased(shr_384(&mul_256_128(mul, m), delta as u32), corr + 1)
+}
+
+// Computes 5^-i in the form required by Ryū, i.e. the top Q_POW5_INV_BITCOUNT
+// bits of 2^k / 5^i, plus one.
+#[inline]
+pub const fn compute_inv_pow5_128(i: u32) -> [u64; 4] {
+    let base = i.div_ceil(Q_POW5_TABLE_SIZE);
+    let base2 = base * Q_POW5_TABLE_SIZE;
+    debug_assert!(base < Q_POW5_INV_SPLIT.len() as u32);
+    let mul = &Q_POW5_INV_SPLIT[base as usize];
+    if i == base2 {
+        return *mul;
+    }
+    let offset = base2 - i;
+    let m = Q_POW5_TABLE[offset as usize];
+    let delta = pow5bits_128(base2 as i32) - pow5bits_128(i as i32);
+    let corr = (Q_POW5_INV_ERRORS[(i / 32) as usize] >> (2 * (i % 32))) & 3;
+    add_biased(shr_384(&mul_256_128(mul, m), delta as u32), corr)
+}
+
+// Returns the low 128 bits of (m * mul) >> j.
+#[inline]
+pub const fn mul_shift_128(m: u128, mul: &[u64; 4], j: u32) -> u128 {
+    let shifted = shr_384(&mul_256_128(mul, m), j);
+    debug_assert!(shifted[2] == 0 && shifted[3] == 0);
+    ((shifted[1] as u128) << 64) | shifted[0] as u128
+}
@@ -1,17 +1,38 @@
 //! Unsafe API that mirror the API of the C implementation of Ryū.
 
 mod exponent;
-mod mantissa;
+pub(crate) mod mantissa;
 
 use core::ptr;
 
 use unroll_lite::unroll;
 
-use self::exponent::{write_exponent2, write_exponent3};
-use self::mantissa::{write_mantissa, write_mantissa_long};
+use self::exponent::{write_exponent2, write_exponent3, write_exponent4};
+use self::mantissa::{write_mantissa, write_mantissa_long, write_mantissa_u128};
+use crate::bid::{decimal128_parts, decimal64_parts, DecimalParts};
 use crate::common;
-use crate::d2s::{self, d2d, DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS};
-use crate::f2s::{f2d, FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+use crate::d2directed::{d2d_down, d2d_up, decimal_length18, write_mantissa18};
+use crate::d2exact::ExactDigits;
+use crate::d2f::d2f;
+use crate::d2fixed::{
+    d2fixed, d2fixed_significant, f2fixed, f2fixed_significant, f32_parts, f64_parts,
+    round_decimal, strip_trailing_zeros,
+};
+use crate::d2radix::{d2radix, FloatingRadix};
+use crate::d2range::{d2d_in_range, d2d_within};
+use crate::d2s::{
+    self, d2d, FloatingDecimal64, DOUBLE_BIAS, DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS,
+};
+use crate::f2s::{f2d, FloatingDecimal32, FLOAT_BIAS, FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+use crate::fixed2d::fixed2d;
+use crate::format::{RoundingMode, Tolerance, TrailingZeros, MAX_SHARED_VALUES};
+use crate::h2s::{
+    bf2d, h2d, BFLOAT_EXPONENT_BITS, BFLOAT_MANTISSA_BITS, HALF_EXPONENT_BITS, HALF_MANTISSA_BITS,
+};
+use crate::minifloat::{m2d, Layout};
+use crate::q2s::{
+    self, q2d, x2d, FloatingDecimal128, QUAD_EXPONENT_BITS, QUAD_MANTISSA_BITS, X87_EXPONENT_BITS,
+};
 
 /// Print f64 to the given buffer and return number of bytes written.
 ///
@@ -83,16 +104,1839 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
         };
     }
 
+    write_floating_decimal64(index, d2d(ieee_mantissa, ieee_exponent), result)
+}
+
+/// Writes the decimal `v`, with the same layout as [`format64`], after the
+/// sign which has already been written to the first `index` bytes of
+/// `result`.
+#[inline]
+pub(crate) const unsafe fn write_floating_decimal64(
+    index: isize,
+    v: FloatingDecimal64,
+    result: *mut u8,
+) -> Formatted {
+    write_floating_decimal64_digits::<false>(index, v, result)
+}
+
+/// Same as [`write_floating_decimal64`], for a mantissa of up to 18 digits if
+/// `DIRECTED`.
+#[inline]
+const unsafe fn write_floating_decimal64_digits<const DIRECTED: bool>(
+    index: isize,
+    v: FloatingDecimal64,
+    result: *mut u8,
+) -> Formatted {
+    let length = if DIRECTED {
+        decimal_length18(v.mantissa)
+    } else {
+        d2s::decimal_length17(v.mantissa)
+    } as isize;
+    let k = v.exponent as isize;
+    let kk = length + k; // 10^(kk-1) <= v < 10^kk
+
+    // The shortest digits have k >= -324, but the significant digits of
+    // `format64_significant_spec` may give the smallest subnormal,
+    // 4.9406564584124654e-324, all 17 digits, so k >= -324 - 16.
+    debug_assert!(k >= -340);
+
+    if 0 <= k && kk <= 16 {
+        // 1234e7 -> 12340000000.0
+        write_mantissa64::<DIRECTED>(v.mantissa, result.offset(index + length));
+        unroll!(i in length..kk => {
+            *result.offset(index + i) = b'0';
+        });
+        *result.offset(index + kk) = b'.';
+        *result.offset(index + kk + 1) = b'0';
+
+        Formatted {
+            initialized: index as usize + kk as usize + 2,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: (index + kk) as usize,
+            },
+        }
+    } else if 0 < kk && kk <= 16 {
+        // 1234e-2 -> 12.34
+        write_mantissa64::<DIRECTED>(v.mantissa, result.offset(index + length + 1));
+        ptr::copy(result.offset(index + 1), result.offset(index), kk as usize);
+        *result.offset(index + kk) = b'.';
+
+        Formatted {
+            initialized: index as usize + length as usize + 1,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: (index + kk) as usize,
+            },
+        }
+    } else if -5 < kk && kk <= 0 {
+        // 1234e-6 -> 0.001234
+        *result.offset(index) = b'0';
+        *result.offset(index + 1) = b'.';
+        let offset = 2 - kk;
+        unroll!(i in 2..offset => {
+            *result.offset(index + i) = b'0';
+        });
+        write_mantissa64::<DIRECTED>(v.mantissa, result.offset(index + length + offset));
+
+        Formatted {
+            initialized: index as usize + length as usize + offset as usize,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: (index + 1) as usize,
+            },
+        }
+    } else if length == 1 {
+        // 1e30
+        *result.offset(index) = b'0' + v.mantissa as u8;
+        *result.offset(index + 1) = b'e';
+
+        Formatted {
+            initialized: index as usize + 2 + write_exponent3(kk - 1, result.offset(index + 2)),
+            meta: FormattedMeta::Exponent {
+                offset_decimal_point: None,
+                offset_exponent: (index + 1) as usize,
+            },
+        }
+    } else {
+        // 1234e30 -> 1.234e33
+        write_mantissa64::<DIRECTED>(v.mantissa, result.offset(index + length + 1));
+        *result.offset(index) = *result.offset(index + 1);
+        *result.offset(index + 1) = b'.';
+        *result.offset(index + length + 1) = b'e';
+
+        Formatted {
+            initialized: index as usize
+                + length as usize
+                + 2
+                + write_exponent3(kk - 1, result.offset(index + length + 2)),
+            meta: FormattedMeta::Exponent {
+                offset_decimal_point: Some((index + 1) as usize),
+                offset_exponent: (index + length + 1) as usize,
+            },
+        }
+    }
+}
+
+#[inline]
+const unsafe fn write_mantissa64<const DIRECTED: bool>(output: u64, result: *mut u8) {
+    if DIRECTED {
+        write_mantissa18(output, result);
+    } else {
+        write_mantissa_long(output, result);
+    }
+}
+
+/// Print f32 to the given buffer and return number of bytes written.
+///
+/// At most 16 bytes will be written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// Please check [`is_finite`] yourself before calling this function, or
+/// check [`is_nan`] and [`is_infinite`] and handle those cases yourself.
+///
+/// [`is_finite`]: f32::is_finite
+/// [`is_nan`]: f32::is_nan
+/// [`is_infinite`]: f32::is_infinite
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold Ryū's representation of `f`.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// let f = 1.234f32;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 16];
+///     let len = ryuu::raw::format32(f, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "1.234");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format32(f: f32, result: *mut u8) -> usize {
+    format32_spec(f, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// See [`format32`].
+pub(crate) const unsafe fn format32_spec(f: f32, result: *mut u8) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    write_floating_decimal32(index, f2d(ieee_mantissa, ieee_exponent), result)
+}
+
+#[inline]
+#[must_use]
+/// Prints the shortest representation that parses to `f as f32`, choosing
+/// the one closest to `f`, with the same layout as [`format32`]. `f as f32`
+/// must be finite.
+pub(crate) const unsafe fn format64_as_32_spec(f: f64, result: *mut u8) -> Formatted {
+    let sign = f.is_sign_negative();
+    let f = f64::from_bits(f.to_bits() & !(1 << 63));
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if f as f32 == 0.0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    write_floating_decimal32(index, d2f(f), result)
+}
+
+#[inline]
+#[must_use]
+/// Prints the shortest representation that parses to `f` and is not above
+/// (with `round_up` false) or not below (with `round_up` true) its exact
+/// value, with the same layout as [`format64`]. `f` must be finite.
+pub(crate) const unsafe fn format64_directed_spec(
+    f: f64,
+    round_up: bool,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    // The digits are of the magnitude, so rounding toward +inf rounds a
+    // negative value toward zero.
+    let v = if round_up != sign {
+        d2d_up(ieee_mantissa, ieee_exponent)
+    } else {
+        d2d_down(ieee_mantissa, ieee_exponent)
+    };
+    write_floating_decimal64_digits::<true>(index, v, result)
+}
+
+#[inline]
+#[must_use]
+/// Prints the shortest decimal between `lo` and `hi`, with the same layout as
+/// [`format64`], or returns `None` if there is none with at most 17 digits.
+/// The bounds must be finite.
+pub(crate) const unsafe fn format64_in_range_spec(
+    lo: f64,
+    hi: f64,
+    inclusive: bool,
+    result: *mut u8,
+) -> Option<Formatted> {
+    if !(lo < hi || inclusive && lo == hi) {
+        return None;
+    }
+    if (lo < 0.0 || inclusive && lo == 0.0) && (hi > 0.0 || inclusive && hi == 0.0) {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result, 3);
+
+        return Some(Formatted {
+            initialized: 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: 1,
+            },
+        });
+    }
+
+    // Both bounds are on the same side of zero, so a negative interval is
+    // the mirror image of a positive one.
+    let sign = hi <= 0.0;
+    let (lo, hi) = if sign { (hi, lo) } else { (lo, hi) };
+    let lo_bits = lo.to_bits() & !(1 << 63);
+    let hi_bits = hi.to_bits() & !(1 << 63);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    match d2d_in_range(
+        lo_bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1),
+        (lo_bits >> DOUBLE_MANTISSA_BITS) as u32,
+        hi_bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1),
+        (hi_bits >> DOUBLE_MANTISSA_BITS) as u32,
+        inclusive,
+    ) {
+        Some(v) => Some(write_floating_decimal64(index, v, result)),
+        None => None,
+    }
+}
+
+#[inline]
+#[must_use]
+/// Prints the shortest representation that is within `tolerance` of `f` or
+/// parses to it, with the same layout as [`format64`]. `f` must be finite, and
+/// the tolerance must not be negative.
+pub(crate) const unsafe fn format64_within_spec(
+    f: f64,
+    tolerance: Tolerance,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    let v = if ieee_exponent == 0 && ieee_mantissa == 0 {
+        None
+    } else {
+        d2d_within(ieee_mantissa, ieee_exponent, tolerance)
+    };
+    match v {
+        Some(v) => write_floating_decimal64(index, v, result),
+        None => {
+            // Zero is within the tolerance.
+            ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+            Formatted {
+                initialized: sign as usize + 3,
+                meta: FormattedMeta::Decimal {
+                    offset_decimal_point: sign as usize + 1,
+                },
+            }
+        }
+    }
+}
+
+#[inline]
+#[must_use]
+/// Prints `f` rounded to `digits` significant digits, with the same layout as
+/// [`format64`]. `f` must be finite, and `digits` between 1 and 17.
+pub(crate) const unsafe fn format64_significant_spec(
+    f: f64,
+    digits: u32,
+    trailing_zeros: TrailingZeros,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return write_zero(index, digits, trailing_zeros, result);
+    }
+
+    let mut v = d2fixed_significant(ieee_mantissa, ieee_exponent, digits, sign, mode);
+    if let TrailingZeros::Strip = trailing_zeros {
+        (v.mantissa, v.exponent) = strip_trailing_zeros(v.mantissa, v.exponent);
+    }
+    write_floating_decimal64(index, v, result)
+}
+
+#[inline]
+#[must_use]
+/// Prints `f` rounded to `digits` significant digits, with the same layout as
+/// [`format32`]. `f` must be finite, and `digits` between 1 and 9.
+pub(crate) const unsafe fn format32_significant_spec(
+    f: f32,
+    digits: u32,
+    trailing_zeros: TrailingZeros,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return write_zero(index, digits, trailing_zeros, result);
+    }
+
+    let v = f2fixed_significant(ieee_mantissa, ieee_exponent, digits, sign, mode);
+    let (mantissa, exponent) = match trailing_zeros {
+        TrailingZeros::Keep => (v.mantissa, v.exponent),
+        TrailingZeros::Strip => strip_trailing_zeros(v.mantissa, v.exponent),
+    };
+    let v = FloatingDecimal32 {
+        mantissa: mantissa as u32,
+        exponent,
+    };
+    write_floating_decimal32(index, v, result)
+}
+
+#[inline]
+#[must_use]
+/// Prints the shortest representation of `f` rounded to `decimal_places`,
+/// with the same layout as [`format64`] except for the number of digits.
+/// `f` must be finite.
+pub(crate) const unsafe fn format64_shortest_dp_spec(
+    f: f64,
+    decimal_places: usize,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return write_fixed(index, 0, 0, decimal_places, false, result);
+    }
+
+    let v = d2d(ieee_mantissa, ieee_exponent);
+    let kk = d2s::decimal_length17(v.mantissa) as i32 + v.exponent;
+    let exponent_form = !(-5 < kk && kk <= 16);
+    let target = if exponent_form { kk - 1 } else { 0 } - decimal_places as i32;
+    let (mantissa, exponent) = round_decimal(v.mantissa, v.exponent, target, sign, mode);
+    write_fixed(
+        index,
+        mantissa as u128,
+        exponent,
+        decimal_places,
+        exponent_form,
+        result,
+    )
+}
+
+#[inline]
+#[must_use]
+/// Prints the shortest representation of `f` rounded to `decimal_places`,
+/// with the same layout as [`format32`] except for the number of digits.
+/// `f` must be finite.
+pub(crate) const unsafe fn format32_shortest_dp_spec(
+    f: f32,
+    decimal_places: usize,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return write_fixed(index, 0, 0, decimal_places, false, result);
+    }
+
+    let v = f2d(ieee_mantissa, ieee_exponent);
+    let kk = common::decimal_length9(v.mantissa) as i32 + v.exponent;
+    let exponent_form = !(-6 < kk && kk <= 13);
+    let target = if exponent_form { kk - 1 } else { 0 } - decimal_places as i32;
+    let (mantissa, exponent) = round_decimal(v.mantissa as u64, v.exponent, target, sign, mode);
+    write_fixed(
+        index,
+        mantissa as u128,
+        exponent,
+        decimal_places,
+        exponent_form,
+        result,
+    )
+}
+
+/// The shortest digits of a finite `f` as sign, mantissa and exponent, and
+/// the power of ten of the ones place in the layout of [`format64`]: zero for
+/// positional numbers, and that of the leading digit in exponent form. The
+/// mantissa of zero is zero.
+#[inline]
+const fn shortest_layout64(f: f64) -> (bool, u64, i32, i32) {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return (sign, 0, 0, 0);
+    }
+
+    let v = d2d(ieee_mantissa, ieee_exponent);
+    let kk = d2s::decimal_length17(v.mantissa) as i32 + v.exponent;
+    let units = if -5 < kk && kk <= 16 { 0 } else { kk - 1 };
+    (sign, v.mantissa, v.exponent, units)
+}
+
+/// `f32` version of [`shortest_layout64`], for the layout of [`format32`].
+#[inline]
+const fn shortest_layout32(f: f32) -> (bool, u64, i32, i32) {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return (sign, 0, 0, 0);
+    }
+
+    let v = f2d(ieee_mantissa, ieee_exponent);
+    let kk = common::decimal_length9(v.mantissa) as i32 + v.exponent;
+    let units = if -6 < kk && kk <= 13 { 0 } else { kk - 1 };
+    (sign, v.mantissa as u64, v.exponent, units)
+}
+
+/// Whether two shortest decimals, as returned by [`shortest_layout64`], print
+/// as the same number when rounded to `decimal_places`. Zeros of either sign
+/// are the same number.
+#[inline]
+const fn same_when_rounded(
+    a: (bool, u64, i32, i32),
+    b: (bool, u64, i32, i32),
+    decimal_places: u32,
+    mode: RoundingMode,
+) -> bool {
+    let (a_mantissa, a_exponent) = round_decimal(a.1, a.2, a.3 - decimal_places as i32, a.0, mode);
+    let (b_mantissa, b_exponent) = round_decimal(b.1, b.2, b.3 - decimal_places as i32, b.0, mode);
+    if a_mantissa == 0 || b_mantissa == 0 {
+        return a_mantissa == b_mantissa;
+    }
+    let (a_mantissa, a_exponent) = strip_trailing_zeros(a_mantissa, a_exponent);
+    let (b_mantissa, b_exponent) = strip_trailing_zeros(b_mantissa, b_exponent);
+    a.0 == b.0 && a_mantissa == b_mantissa && a_exponent == b_exponent
+}
+
+/// The smallest number of decimal places at which no two different finite
+/// values print as the same number, given the shortest decimals of the
+/// values as returned by [`shortest_layout64`], with `None` for NaN and
+/// infinities. It is at most the number that shows all of the shortest digits
+/// of every value, where they cannot collide.
+const fn shared_decimal_places(
+    layouts: &[Option<(bool, u64, i32, i32)>],
+    len: usize,
+    mode: RoundingMode,
+) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < len {
+        if let Some((_, mantissa, exponent, units)) = layouts[i] {
+            if mantissa != 0 && units - exponent > max {
+                max = units - exponent;
+            }
+        }
+        i += 1;
+    }
+
+    // Bit p is set if two different values are the same with p decimal
+    // places. Rounding to more places may merge values that fewer places
+    // tell apart, as 0.149 and 0.15 with two and one, so every count below
+    // the largest one is checked. Equal values have equal shortest decimals,
+    // which are skipped.
+    let all = (1u32 << max) - 1;
+    let mut collisions = 0u32;
+    let mut i = 0;
+    while i < len {
+        if let Some(a) = layouts[i] {
+            let mut j = i + 1;
+            while j < len {
+                if let Some(b) = layouts[j] {
+                    let same = (a.1 == 0 && b.1 == 0) || (a.0 == b.0 && a.1 == b.1 && a.2 == b.2);
+                    if !same {
+                        let mut p = 0;
+                        while p < max {
+                            if collisions & (1 << p) == 0 && same_when_rounded(a, b, p as u32, mode)
+                            {
+                                collisions |= 1 << p;
+                            }
+                            p += 1;
+                        }
+                        if collisions == all {
+                            return max as usize;
+                        }
+                    }
+                }
+                j += 1;
+            }
+        }
+        i += 1;
+    }
+    (!collisions).trailing_zeros() as usize
+}
+
+/// The smallest number of decimal places, as counted by
+/// [`format64_shortest_dp_spec`], at which no two different finite values
+/// print as the same number. The shortest decimal of each value is computed
+/// once, and `values` must have at most [`MAX_SHARED_VALUES`] of them.
+#[must_use]
+pub(crate) const fn shared_decimal_places64(values: &[f64], mode: RoundingMode) -> usize {
+    debug_assert!(values.len() <= MAX_SHARED_VALUES);
+    let mut layouts = [None; MAX_SHARED_VALUES];
+    let mut i = 0;
+    while i < values.len() {
+        if values[i].is_finite() {
+            layouts[i] = Some(shortest_layout64(values[i]));
+        }
+        i += 1;
+    }
+    shared_decimal_places(&layouts, values.len(), mode)
+}
+
+/// `f32` version of [`shared_decimal_places64`], with the decimal places of
+/// [`format32_shortest_dp_spec`].
+#[must_use]
+pub(crate) const fn shared_decimal_places32(values: &[f32], mode: RoundingMode) -> usize {
+    debug_assert!(values.len() <= MAX_SHARED_VALUES);
+    let mut layouts = [None; MAX_SHARED_VALUES];
+    let mut i = 0;
+    while i < values.len() {
+        if values[i].is_finite() {
+            layouts[i] = Some(shortest_layout32(values[i]));
+        }
+        i += 1;
+    }
+    shared_decimal_places(&layouts, values.len(), mode)
+}
+
+#[inline]
+#[must_use]
+/// Prints `f` rounded to `decimal_places` digits after the decimal point,
+/// with the same layout as [`format64`] except for the number of digits.
+/// `f` must be finite.
+pub(crate) const unsafe fn format64_fixed_spec(
+    f: f64,
+    decimal_places: usize,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return write_fixed(index, 0, 0, decimal_places, false, result);
+    }
+
     let v = d2d(ieee_mantissa, ieee_exponent);
+    let kk = d2s::decimal_length17(v.mantissa) as i32 + v.exponent;
+    if -5 < kk && kk <= 16 {
+        let mantissa = d2fixed(
+            ieee_mantissa,
+            ieee_exponent,
+            decimal_places as u32,
+            sign,
+            mode,
+        );
+        write_fixed(
+            index,
+            mantissa,
+            -(decimal_places as i32),
+            decimal_places,
+            false,
+            result,
+        )
+    } else {
+        let digits = decimal_places as u32 + 1;
+        let v = d2fixed_significant(ieee_mantissa, ieee_exponent, digits, sign, mode);
+        write_fixed(
+            index,
+            v.mantissa as u128,
+            v.exponent,
+            decimal_places,
+            true,
+            result,
+        )
+    }
+}
+
+#[inline]
+#[must_use]
+/// Prints `f` rounded to `decimal_places` digits after the decimal point,
+/// with the same layout as [`format32`] except for the number of digits.
+/// `f` must be finite.
+pub(crate) const unsafe fn format32_fixed_spec(
+    f: f32,
+    decimal_places: usize,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        return write_fixed(index, 0, 0, decimal_places, false, result);
+    }
+
+    let v = f2d(ieee_mantissa, ieee_exponent);
+    let kk = common::decimal_length9(v.mantissa) as i32 + v.exponent;
+    if -6 < kk && kk <= 13 {
+        let mantissa = f2fixed(
+            ieee_mantissa,
+            ieee_exponent,
+            decimal_places as u32,
+            sign,
+            mode,
+        );
+        write_fixed(
+            index,
+            mantissa,
+            -(decimal_places as i32),
+            decimal_places,
+            false,
+            result,
+        )
+    } else {
+        let digits = decimal_places as u32 + 1;
+        let v = f2fixed_significant(ieee_mantissa, ieee_exponent, digits, sign, mode);
+        write_fixed(
+            index,
+            v.mantissa as u128,
+            v.exponent,
+            decimal_places,
+            true,
+            result,
+        )
+    }
+}
+
+#[inline]
+#[must_use]
+/// Prints `f` rounded to `max_fraction_digits` digits after the decimal
+/// point, without the trailing zeros past `min_fraction_digits`, and with
+/// zeros before the integer part up to `min_integer_digits`. Unlike
+/// [`format64`], the number is never in exponent form. `f` must be finite.
+pub(crate) const unsafe fn format64_fraction_digits_spec(
+    f: f64,
+    min_integer_digits: usize,
+    min_fraction_digits: usize,
+    max_fraction_digits: usize,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    let digit_counts = DigitCounts {
+        min_integer_digits,
+        min_fraction_digits,
+        max_fraction_digits,
+    };
+    // From 2^52 on, every double is an integer, which needs no rounding but
+    // may have up to 309 digits. Below, the mantissa for 10^-20 is less than
+    // 2^52 * 10^20, which fits in the 127 bits of `d2fixed`.
+    if ieee_exponent >= (DOUBLE_BIAS as u32) + DOUBLE_MANTISSA_BITS {
+        return write_integer_fraction_digits(index, f, digit_counts, result);
+    }
+    let mantissa = d2fixed(
+        ieee_mantissa,
+        ieee_exponent,
+        max_fraction_digits as u32,
+        sign,
+        mode,
+    );
+    write_fraction_digits(
+        index,
+        mantissa,
+        -(max_fraction_digits as i32),
+        digit_counts,
+        result,
+    )
+}
+
+#[inline]
+#[must_use]
+/// `f32` version of [`format64_fraction_digits_spec`]. `f` must be finite.
+pub(crate) const unsafe fn format32_fraction_digits_spec(
+    f: f32,
+    min_integer_digits: usize,
+    min_fraction_digits: usize,
+    max_fraction_digits: usize,
+    mode: RoundingMode,
+    result: *mut u8,
+) -> Formatted {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    let digit_counts = DigitCounts {
+        min_integer_digits,
+        min_fraction_digits,
+        max_fraction_digits,
+    };
+    // From 2^23 on, every float is an integer, with up to 39 digits.
+    if ieee_exponent >= (FLOAT_BIAS as u32) + FLOAT_MANTISSA_BITS {
+        return write_integer_fraction_digits(index, f as f64, digit_counts, result);
+    }
+    let mantissa = f2fixed(
+        ieee_mantissa,
+        ieee_exponent,
+        max_fraction_digits as u32,
+        sign,
+        mode,
+    );
+    write_fraction_digits(
+        index,
+        mantissa,
+        -(max_fraction_digits as i32),
+        digit_counts,
+        result,
+    )
+}
+
+// The numbers of digits of `format64_fraction_digits_spec`.
+#[derive(Clone, Copy)]
+struct DigitCounts {
+    min_integer_digits: usize,
+    min_fraction_digits: usize,
+    max_fraction_digits: usize,
+}
+
+/// Writes `mantissa * 10^exponent`, which has `max_fraction_digits` digits
+/// after the decimal point, as [`write_fixed`] does, without the trailing
+/// zeros past `min_fraction_digits`, and with zeros before the integer part
+/// up to `min_integer_digits`.
+#[inline]
+const unsafe fn write_fraction_digits(
+    mut index: isize,
+    mut mantissa: u128,
+    mut exponent: i32,
+    digit_counts: DigitCounts,
+    result: *mut u8,
+) -> Formatted {
+    let mut decimal_places = digit_counts.max_fraction_digits;
+    while decimal_places > digit_counts.min_fraction_digits && mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent += 1;
+        decimal_places -= 1;
+    }
+    if mantissa == 0 {
+        exponent = 0;
+    }
+
+    let length = q2s::decimal_length39(mantissa) as i32;
+    let mut integer_digits = if length + exponent > 1 {
+        (length + exponent) as usize
+    } else {
+        1
+    };
+    while integer_digits < digit_counts.min_integer_digits {
+        *result.offset(index) = b'0';
+        index += 1;
+        integer_digits += 1;
+    }
+
+    write_fixed(index, mantissa, exponent, decimal_places, false, result)
+}
+
+/// Writes the integer `f` like [`write_fraction_digits`], with its exact
+/// digits and `min_fraction_digits` zeros after the decimal point.
+#[inline]
+const unsafe fn write_integer_fraction_digits(
+    index: isize,
+    f: f64,
+    digit_counts: DigitCounts,
+    result: *mut u8,
+) -> Formatted {
+    let mut offset = index as usize;
+    let mut digits = ExactDigits::new(f);
+    let mut integer_digits = digits.integer_length();
+    while integer_digits < digit_counts.min_integer_digits {
+        *result.add(offset) = b'0';
+        offset += 1;
+        integer_digits += 1;
+    }
+    loop {
+        let length = digits.next_integer(result.add(offset));
+        if length == 0 {
+            break;
+        }
+        offset += length;
+    }
+
+    let offset_decimal_point = offset;
+    if digit_counts.min_fraction_digits > 0 {
+        *result.add(offset) = b'.';
+        offset += 1;
+        let mut zeros = 0;
+        while zeros < digit_counts.min_fraction_digits {
+            *result.add(offset) = b'0';
+            offset += 1;
+            zeros += 1;
+        }
+    }
+    Formatted {
+        initialized: offset,
+        meta: FormattedMeta::Decimal {
+            offset_decimal_point,
+        },
+    }
+}
+
+/// Writes `mantissa * 10^exponent` with exactly `decimal_places` digits after
+/// the decimal point, and no decimal point if there are none, after the sign
+/// which has already been written to the first `index` bytes of `result`.
+/// Digits of `mantissa` past the last shown one must be zeros.
+#[inline]
+const unsafe fn write_fixed(
+    index: isize,
+    mantissa: u128,
+    exponent: i32,
+    decimal_places: usize,
+    exponent_form: bool,
+    result: *mut u8,
+) -> Formatted {
+    let mut digits = [b'0'; 39];
+    let length = q2s::decimal_length39(mantissa) as i32;
+    write_mantissa_u128(mantissa, digits.as_mut_ptr().add(length as usize));
+
+    // The power of ten of the first and last written digits.
+    let first = if exponent_form || length + exponent > 1 {
+        length + exponent - 1
+    } else {
+        0
+    };
+    let last = if exponent_form { first } else { 0 } - decimal_places as i32;
+
+    let mut offset = index as usize;
+    let mut offset_decimal_point = None;
+    let mut p = first;
+    while p >= last {
+        let i = p - exponent;
+        *result.add(offset) = if mantissa != 0 && 0 <= i && i < length {
+            digits[(length - 1 - i) as usize]
+        } else {
+            b'0'
+        };
+        offset += 1;
+        if p == last + decimal_places as i32 && decimal_places > 0 {
+            offset_decimal_point = Some(offset);
+            *result.add(offset) = b'.';
+            offset += 1;
+        }
+        p -= 1;
+    }
+
+    if exponent_form {
+        let offset_exponent = offset;
+        *result.add(offset) = b'e';
+        offset += 1;
+        offset += write_exponent3(first as isize, result.add(offset));
+        Formatted {
+            initialized: offset,
+            meta: FormattedMeta::Exponent {
+                offset_decimal_point,
+                offset_exponent,
+            },
+        }
+    } else {
+        Formatted {
+            initialized: offset,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: match offset_decimal_point {
+                    Some(offset_decimal_point) => offset_decimal_point,
+                    None => offset,
+                },
+            },
+        }
+    }
+}
+
+/// Writes zero with `digits` significant digits after the sign, i.e. "0.00"
+/// for three digits when keeping trailing zeros, and "0.0" otherwise.
+#[inline]
+const unsafe fn write_zero(
+    index: isize,
+    digits: u32,
+    trailing_zeros: TrailingZeros,
+    result: *mut u8,
+) -> Formatted {
+    let zeros = match trailing_zeros {
+        TrailingZeros::Keep if digits > 2 => digits as usize - 1,
+        _ => 1,
+    };
+    *result.offset(index) = b'0';
+    *result.offset(index + 1) = b'.';
+    result.offset(index + 2).write_bytes(b'0', zeros);
+
+    Formatted {
+        initialized: index as usize + 2 + zeros,
+        meta: FormattedMeta::Decimal {
+            offset_decimal_point: index as usize + 1,
+        },
+    }
+}
+
+/// Writes the shortest representation `v`, with the same layout as
+/// [`format32`], after the sign which has already been written to the first
+/// `index` bytes of `result`.
+#[inline]
+pub(crate) const unsafe fn write_floating_decimal32(
+    index: isize,
+    v: FloatingDecimal32,
+    result: *mut u8,
+) -> Formatted {
+    let length = common::decimal_length9(v.mantissa) as isize;
+    let k = v.exponent as isize;
+    let kk = length + k; // 10^(kk-1) <= v < 10^kk
+
+    // The shortest digits have k >= -45, but the significant digits of
+    // `format32_significant_spec` may give the smallest subnormal,
+    // 1.40129846e-45, all 9 digits, so k >= -45 - 8.
+    debug_assert!(k >= -53);
+
+    if 0 <= k && kk <= 13 {
+        // 1234e7 -> 12340000000.0
+        write_mantissa(v.mantissa, result.offset(index + length));
+        unroll!(i in length..kk => {
+            *result.offset(index + i) = b'0';
+        });
+        *result.offset(index + kk) = b'.';
+        *result.offset(index + kk + 1) = b'0';
+
+        Formatted {
+            initialized: index as usize + kk as usize + 2,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: (index + kk) as usize,
+            },
+        }
+    } else if 0 < kk && kk <= 13 {
+        // 1234e-2 -> 12.34
+        write_mantissa(v.mantissa, result.offset(index + length + 1));
+        ptr::copy(result.offset(index + 1), result.offset(index), kk as usize);
+        *result.offset(index + kk) = b'.';
+
+        Formatted {
+            initialized: index as usize + length as usize + 1,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: (index + kk) as usize,
+            },
+        }
+    } else if -6 < kk && kk <= 0 {
+        // 1234e-6 -> 0.001234
+        *result.offset(index) = b'0';
+        *result.offset(index + 1) = b'.';
+        let offset = 2 - kk;
+        unroll!(i in 2..offset => {
+            *result.offset(index + i) = b'0';
+        });
+        write_mantissa(v.mantissa, result.offset(index + length + offset));
+
+        Formatted {
+            initialized: index as usize + length as usize + offset as usize,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: (index + 1) as usize,
+            },
+        }
+    } else if length == 1 {
+        // 1e30
+        *result.offset(index) = b'0' + v.mantissa as u8;
+        *result.offset(index + 1) = b'e';
+
+        Formatted {
+            initialized: index as usize + 2 + write_exponent2(kk - 1, result.offset(index + 2)),
+            meta: FormattedMeta::Exponent {
+                offset_decimal_point: None,
+                offset_exponent: (index + 1) as usize,
+            },
+        }
+    } else {
+        // 1234e30 -> 1.234e33
+        write_mantissa(v.mantissa, result.offset(index + length + 1));
+        *result.offset(index) = *result.offset(index + 1);
+        *result.offset(index + 1) = b'.';
+        *result.offset(index + length + 1) = b'e';
+
+        Formatted {
+            initialized: index as usize
+                + length as usize
+                + 2
+                + write_exponent2(kk - 1, result.offset(index + length + 2)),
+            meta: FormattedMeta::Exponent {
+                offset_decimal_point: Some((index + 1) as usize),
+                offset_exponent: (index + length + 1) as usize,
+            },
+        }
+    }
+}
+
+/// Print the exact decimal value of an f64 to the given buffer and return
+/// number of bytes written.
+///
+/// Unlike [`format64`], this prints every digit of the binary value, with
+/// its integer part, a decimal point and its fraction, and never in exponent
+/// form: 0.1 is "0.1000000000000000055511151231257827021181583404541015625".
+/// The fraction has no trailing zeros, and is "0" for integers, as in
+/// "1.0". At most 1077 bytes will be written, for the negative subnormals
+/// with 1074 digits after the decimal point.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the exact representation of `f`.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// let f = 0.375f64;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 1077];
+///     let len = ryuu::raw::format64_exact(f, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "0.375");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format64_exact(f: f64, result: *mut u8) -> usize {
+    let mut index = 0;
+    if f.is_sign_negative() {
+        *result = b'-';
+        index += 1;
+    }
+
+    let mut digits = ExactDigits::new(f);
+    if digits.integer_length() == 0 {
+        *result.add(index) = b'0';
+        index += 1;
+    }
+    loop {
+        let length = digits.next_integer(result.add(index));
+        if length == 0 {
+            break;
+        }
+        index += length;
+    }
+
+    *result.add(index) = b'.';
+    index += 1;
+    if digits.fraction_length() == 0 {
+        *result.add(index) = b'0';
+        index += 1;
+    }
+    loop {
+        let length = digits.next_fraction(result.add(index));
+        if length == 0 {
+            break;
+        }
+        index += length;
+    }
+    index
+}
+
+/// Print the exact decimal value of an f32 to the given buffer and return
+/// number of bytes written.
+///
+/// This is [`format64_exact`] of `f` converted to `f64`, which is exact. At
+/// most 152 bytes will be written, for the negative subnormals with 149
+/// digits after the decimal point.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the exact representation of `f`.
+#[must_use]
+pub const unsafe fn format32_exact(f: f32, result: *mut u8) -> usize {
+    format64_exact(f as f64, result)
+}
+
+/// Print f64 in the given radix, from 2 to 36, to the given buffer and return
+/// number of bytes written.
+///
+/// This prints the shortest digits in `radix` that parse back to `f`, with
+/// the lowercase letters as digits above 9, like JavaScript's
+/// `Number.prototype.toString(radix)`. Radix 10 is [`format64`]. Other
+/// radices are never in exponent form, and integers have no fraction: 255 is
+/// "ff" in radix 16 and 0.5 is "0.i" in radix 36. At most 1077 bytes will be
+/// written, for the negative subnormals in radix 2.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the representation of `f`, and `radix` must be from 2 to 36.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// let f = 0.1f64;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 1077];
+///     let len = ryuu::raw::format64_radix(f, 16, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "0.1999999999999a");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format64_radix(f: f64, radix: u32, result: *mut u8) -> usize {
+    format64_radix_spec(f, radix, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// Print f64 in the given radix, returning the [`Formatted`] meta info.
+pub(crate) const unsafe fn format64_radix_spec(f: f64, radix: u32, result: *mut u8) -> Formatted {
+    if radix == 10 {
+        return format64_spec(f, result);
+    }
+
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        *result.offset(index) = b'0';
+        return Formatted {
+            initialized: index as usize + 1,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: index as usize + 1,
+            },
+        };
+    }
+
+    let (m2, e2) = f64_parts(ieee_mantissa, ieee_exponent);
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    write_radix(index, d2radix(m2, e2, mm_shift, radix), radix, result)
+}
+
+/// Print f32 in the given radix, from 2 to 36, to the given buffer and return
+/// number of bytes written.
+///
+/// This prints the shortest digits in `radix` that parse back to `f` as an
+/// f32, in the same layout as [`format64_radix`]. Radix 10 is [`format32`].
+/// At most 152 bytes will be written, for the negative subnormals in radix 2.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the representation of `f`, and `radix` must be from 2 to 36.
+#[must_use]
+pub const unsafe fn format32_radix(f: f32, radix: u32, result: *mut u8) -> usize {
+    format32_radix_spec(f, radix, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// Print f32 in the given radix, returning the [`Formatted`] meta info.
+pub(crate) const unsafe fn format32_radix_spec(f: f32, radix: u32, result: *mut u8) -> Formatted {
+    if radix == 10 {
+        return format32_spec(f, result);
+    }
+
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        *result.offset(index) = b'0';
+        return Formatted {
+            initialized: index as usize + 1,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: index as usize + 1,
+            },
+        };
+    }
+
+    let (m2, e2) = f32_parts(ieee_mantissa, ieee_exponent);
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    write_radix(index, d2radix(m2, e2, mm_shift, radix), radix, result)
+}
+
+/// Writes `v` in `radix` without an exponent, after the sign which has
+/// already been written to the first `index` bytes of `result`.
+#[inline]
+const unsafe fn write_radix(
+    index: isize,
+    v: FloatingRadix,
+    radix: u32,
+    result: *mut u8,
+) -> Formatted {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    // At most 64 digits, the lowest one last.
+    let mut digits = [0u8; 64];
+    let mut length = 0usize;
+    let mut mantissa = v.mantissa;
+    while mantissa != 0 {
+        length += 1;
+        digits[64 - length] = DIGITS[(mantissa % radix as u64) as usize];
+        mantissa /= radix as u64;
+    }
+    let digits = digits.as_ptr().add(64 - length);
+
+    let mut offset = index as usize;
+    let integer_length = length as i32 + v.exponent;
+    if v.exponent >= 0 {
+        ptr::copy_nonoverlapping(digits, result.add(offset), length);
+        offset += length;
+        result.add(offset).write_bytes(b'0', v.exponent as usize);
+        offset += v.exponent as usize;
+        Formatted {
+            initialized: offset,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: offset,
+            },
+        }
+    } else if integer_length > 0 {
+        let integer_length = integer_length as usize;
+        ptr::copy_nonoverlapping(digits, result.add(offset), integer_length);
+        offset += integer_length;
+        let offset_decimal_point = offset;
+        *result.add(offset) = b'.';
+        offset += 1;
+        ptr::copy_nonoverlapping(
+            digits.add(integer_length),
+            result.add(offset),
+            length - integer_length,
+        );
+        offset += length - integer_length;
+        Formatted {
+            initialized: offset,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point,
+            },
+        }
+    } else {
+        *result.add(offset) = b'0';
+        let offset_decimal_point = offset + 1;
+        *result.add(offset + 1) = b'.';
+        offset += 2;
+        result
+            .add(offset)
+            .write_bytes(b'0', -integer_length as usize);
+        offset += -integer_length as usize;
+        ptr::copy_nonoverlapping(digits, result.add(offset), length);
+        offset += length;
+        Formatted {
+            initialized: offset,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point,
+            },
+        }
+    }
+}
+
+/// Print IEEE 754 binary16 (`f16`), given as raw bits, to the given buffer and
+/// return number of bytes written.
+///
+/// This prints the shortest representation that round-trips as `f16`, using
+/// the same layout rules as [`format32`]. At most 13 bytes will be written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold Ryū's representation of `bits`.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// // 0.1 rounded to f16 is 0.0999755859375.
+/// let bits = 0x2e66u16;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 13];
+///     let len = ryuu::raw::format16(bits, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "0.1");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format16(bits: u16, result: *mut u8) -> usize {
+    format16_spec(bits, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// See [`format16`].
+pub(crate) const unsafe fn format16_spec(bits: u16, result: *mut u8) -> Formatted {
+    let bits = bits as u32;
+    let sign = ((bits >> (HALF_MANTISSA_BITS + HALF_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << HALF_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> HALF_MANTISSA_BITS) & ((1u32 << HALF_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    write_floating_decimal32(index, h2d(ieee_mantissa, ieee_exponent), result)
+}
+
+/// Print bfloat16, given as raw bits, to the given buffer and return number of
+/// bytes written.
+///
+/// This prints the shortest representation that round-trips as bfloat16,
+/// using the same layout rules as [`format32`]. At most 16 bytes will be
+/// written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold Ryū's representation of `bits`.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// // 0.1 rounded to bfloat16 is 0.10009765625.
+/// let bits = 0x3dcdu16;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 16];
+///     let len = ryuu::raw::format_bf16(bits, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "0.1");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format_bf16(bits: u16, result: *mut u8) -> usize {
+    format_bf16_spec(bits, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// See [`format_bf16`].
+pub(crate) const unsafe fn format_bf16_spec(bits: u16, result: *mut u8) -> Formatted {
+    let bits = bits as u32;
+    let sign = ((bits >> (BFLOAT_MANTISSA_BITS + BFLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << BFLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> BFLOAT_MANTISSA_BITS) & ((1u32 << BFLOAT_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    write_floating_decimal32(index, bf2d(ieee_mantissa, ieee_exponent), result)
+}
+
+/// Print a minifloat with the given layout, given as raw bits, to the given
+/// buffer and return number of bytes written.
+///
+/// This prints the shortest representation that round-trips in `layout`,
+/// using the same layout rules as [`format32`]. Bits above the width of the
+/// layout are ignored. At most 16 bytes will be written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold Ryū's representation of `bits`.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// use ryuu::minifloat::Layout;
+///
+/// // 0.1 rounded to E4M3 is 0.1015625.
+/// let bits = 0x1du16;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 16];
+///     let len = ryuu::raw::format_minifloat(bits, Layout::E4M3, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "0.1");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format_minifloat(bits: u16, layout: Layout, result: *mut u8) -> usize {
+    format_minifloat_spec(bits, layout, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// See [`format_minifloat`].
+pub(crate) const unsafe fn format_minifloat_spec(
+    bits: u16,
+    layout: Layout,
+    result: *mut u8,
+) -> Formatted {
+    let sign = layout.is_negative(bits);
+    let ieee_mantissa = layout.ieee_mantissa(bits);
+    let ieee_exponent = layout.ieee_exponent(bits);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    write_floating_decimal32(index, m2d(ieee_mantissa, ieee_exponent, layout), result)
+}
+
+/// Print IEEE 754 binary128 (`f128`), given as raw bits, to the given buffer
+/// and return number of bytes written.
+///
+/// This prints the shortest representation that round-trips as `f128`, with
+/// up to 36 significant digits, using the same layout rules as [`format64`].
+/// At most 44 bytes will be written, for negative numbers with 36 significant
+/// digits and a four-digit negative exponent.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold Ryū's representation of `bits`.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// // 0.1 rounded to f128.
+/// let bits = 0x3ffb999999999999999999999999999au128;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 44];
+///     let len = ryuu::raw::format128(bits, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "0.1");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format128(bits: u128, result: *mut u8) -> usize {
+    format128_spec(bits, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// See [`format128`].
+pub(crate) const unsafe fn format128_spec(bits: u128, result: *mut u8) -> Formatted {
+    let sign = ((bits >> (QUAD_MANTISSA_BITS + QUAD_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u128 << QUAD_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> QUAD_MANTISSA_BITS) as u32 & ((1u32 << QUAD_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    write_floating_decimal128(index, q2d(ieee_mantissa, ieee_exponent), result)
+}
+
+/// Print an x87 80-bit extended precision float, given as its sign and
+/// exponent word and its 64-bit mantissa including the explicit integer bit,
+/// to the given buffer and return number of bytes written.
+///
+/// This prints the shortest representation that round-trips as an extended
+/// precision value, with up to 21 significant digits, using the same layout
+/// rules as [`format64`]. At most 29 bytes will be written.
+///
+/// Pseudo-denormals, i.e. a zero exponent with the integer bit set, are
+/// printed with the value the x87 FPU gives them, which is the same as the
+/// normal number with a biased exponent of 1 and the same mantissa.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity, nor for unnormals,
+/// i.e. a nonzero exponent with the integer bit clear, which the x87 FPU
+/// since the 80387 rejects as invalid operands. For these inputs, the printed
+/// representation will be some correctly formatted but unspecified numerical
+/// value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold Ryū's representation of the number.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// // 0.1 rounded to extended precision.
+/// let (sign_exponent, mantissa) = (0x3ffbu16, 0xcccccccccccccccdu64);
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 29];
+///     let len = ryuu::raw::format80(sign_exponent, mantissa, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "0.1");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format80(sign_exponent: u16, mantissa: u64, result: *mut u8) -> usize {
+    format80_spec(sign_exponent, mantissa, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// See [`format80`].
+pub(crate) const unsafe fn format80_spec(
+    sign_exponent: u16,
+    mantissa: u64,
+    result: *mut u8,
+) -> Formatted {
+    let sign = ((sign_exponent >> X87_EXPONENT_BITS) & 1) != 0;
+    let ieee_exponent = sign_exponent as u32 & ((1u32 << X87_EXPONENT_BITS) - 1);
+
+    let mut index = 0isize;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    // Without any bit set, the exponent does not matter.
+    if mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+
+        return Formatted {
+            initialized: sign as usize + 3,
+            meta: FormattedMeta::Decimal {
+                offset_decimal_point: sign as usize + 1,
+            },
+        };
+    }
+
+    write_floating_decimal128(index, x2d(mantissa, ieee_exponent), result)
+}
 
-    let length = d2s::decimal_length17(v.mantissa) as isize;
+/// Writes the shortest representation `v`, with the same layout as
+/// [`format64`], after the sign which has already been written to the first
+/// `index` bytes of `result`.
+#[inline]
+pub(crate) const unsafe fn write_floating_decimal128(
+    index: isize,
+    v: FloatingDecimal128,
+    result: *mut u8,
+) -> Formatted {
+    let length = q2s::decimal_length39(v.mantissa) as isize;
     let k = v.exponent as isize;
     let kk = length + k; // 10^(kk-1) <= v < 10^kk
-    debug_assert!(k >= -324);
+    debug_assert!(k >= -6176);
 
     if 0 <= k && kk <= 16 {
         // 1234e7 -> 12340000000.0
-        write_mantissa_long(v.mantissa, result.offset(index + length));
+        write_mantissa_u128(v.mantissa, result.offset(index + length));
         unroll!(i in length..kk => {
             *result.offset(index + i) = b'0';
         });
@@ -107,7 +1951,7 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
         }
     } else if 0 < kk && kk <= 16 {
         // 1234e-2 -> 12.34
-        write_mantissa_long(v.mantissa, result.offset(index + length + 1));
+        write_mantissa_u128(v.mantissa, result.offset(index + length + 1));
         ptr::copy(result.offset(index + 1), result.offset(index), kk as usize);
         *result.offset(index + kk) = b'.';
 
@@ -125,7 +1969,7 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
         unroll!(i in 2..offset => {
             *result.offset(index + i) = b'0';
         });
-        write_mantissa_long(v.mantissa, result.offset(index + length + offset));
+        write_mantissa_u128(v.mantissa, result.offset(index + length + offset));
 
         Formatted {
             initialized: index as usize + length as usize + offset as usize,
@@ -139,7 +1983,7 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
         *result.offset(index + 1) = b'e';
 
         Formatted {
-            initialized: index as usize + 2 + write_exponent3(kk - 1, result.offset(index + 2)),
+            initialized: index as usize + 2 + write_exponent4(kk - 1, result.offset(index + 2)),
             meta: FormattedMeta::Exponent {
                 offset_decimal_point: None,
                 offset_exponent: (index + 1) as usize,
@@ -147,7 +1991,7 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
         }
     } else {
         // 1234e30 -> 1.234e33
-        write_mantissa_long(v.mantissa, result.offset(index + length + 1));
+        write_mantissa_u128(v.mantissa, result.offset(index + length + 1));
         *result.offset(index) = *result.offset(index + 1);
         *result.offset(index + 1) = b'.';
         *result.offset(index + length + 1) = b'e';
@@ -156,7 +2000,7 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
             initialized: index as usize
                 + length as usize
                 + 2
-                + write_exponent3(kk - 1, result.offset(index + length + 2)),
+                + write_exponent4(kk - 1, result.offset(index + length + 2)),
             meta: FormattedMeta::Exponent {
                 offset_decimal_point: Some((index + 1) as usize),
                 offset_exponent: (index + length + 1) as usize,
@@ -165,27 +2009,68 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
     }
 }
 
-/// Print f32 to the given buffer and return number of bytes written.
+/// Print an IEEE 754 decimal64 float in the BID encoding, given as raw bits,
+/// to the given buffer and return number of bytes written.
 ///
-/// At most 16 bytes will be written.
+/// The value is printed with the fewest digits, i.e. without the trailing
+/// zeros of its coefficient, and with the same layout rules as [`format64`].
+/// All members of a cohort, e.g. 1.2 and 1.20, print the same. A coefficient
+/// above 10^16 - 1 is not canonical and prints as zero. At most 23 bytes will
+/// be written.
 ///
 /// ## Special cases
 ///
-/// This function **does not** check for NaN or infinity. If the input
-/// number is not a finite float, the printed representation will be some
-/// correctly formatted but unspecified numerical value.
+/// This function **does not** check for NaN or infinity. If the input number
+/// is not a finite float, the printed representation will be some correctly
+/// formatted but unspecified numerical value.
 ///
-/// Please check [`is_finite`] yourself before calling this function, or
-/// check [`is_nan`] and [`is_infinite`] and handle those cases yourself.
+/// ## Safety
 ///
-/// [`is_finite`]: f32::is_finite
-/// [`is_nan`]: f32::is_nan
-/// [`is_infinite`]: f32::is_infinite
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the representation of the number.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// // 1.20, as the coefficient 120 and the exponent -2.
+/// let bits = 0x3180_0000_0000_0078u64;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 23];
+///     let len = ryuu::raw::format_decimal64(bits, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "1.2");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format_decimal64(bits: u64, result: *mut u8) -> usize {
+    format_decimal64_spec(bits, false, result).initialized
+}
+
+/// Like [`format_decimal64`], but prints the exact coefficient and exponent,
+/// which tell the members of a cohort apart.
+///
+/// A nonpositive exponent is printed as the number of digits after the
+/// decimal point, which is left out for zero, as in "1.20" and "120", unless
+/// there would be more than four zeros after the decimal point. Otherwise,
+/// the coefficient is printed in exponent form, with all its digits after the
+/// first one after the decimal point, as in "1.20e5" and "1e-7". At most 23
+/// bytes will be written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input number
+/// is not a finite float, the printed representation will be some correctly
+/// formatted but unspecified numerical value.
 ///
 /// ## Safety
 ///
 /// The `result` pointer argument must point to sufficiently many writable bytes
-/// to hold Ryū's representation of `f`.
+/// to hold the representation of the number.
 ///
 /// ## Example
 ///
@@ -193,112 +2078,199 @@ pub(crate) const unsafe fn format64_spec(f: f64, result: *mut u8) -> Formatted {
 /// use core::mem::MaybeUninit;
 /// use core::{slice, str};
 ///
-/// let f = 1.234f32;
+/// // 1.20, as the coefficient 120 and the exponent -2.
+/// let bits = 0x3180_0000_0000_0078u64;
 ///
 /// unsafe {
-///     let mut buffer = [MaybeUninit::<u8>::uninit(); 16];
-///     let len = ryuu::raw::format32(f, buffer.as_mut_ptr() as *mut u8);
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 23];
+///     let len = ryuu::raw::format_decimal64_cohort(bits, buffer.as_mut_ptr() as *mut u8);
 ///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
 ///     let print = str::from_utf8_unchecked(slice);
-///     assert_eq!(print, "1.234");
+///     assert_eq!(print, "1.20");
 /// }
 /// ```
 #[must_use]
-pub const unsafe fn format32(f: f32, result: *mut u8) -> usize {
-    format32_spec(f, result).initialized
+pub const unsafe fn format_decimal64_cohort(bits: u64, result: *mut u8) -> usize {
+    format_decimal64_spec(bits, true, result).initialized
 }
 
 #[inline]
 #[must_use]
-/// See [`format32`].
-pub(crate) const unsafe fn format32_spec(f: f32, result: *mut u8) -> Formatted {
-    let bits = f.to_bits();
-    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
-    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
-    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+/// See [`format_decimal64`] and [`format_decimal64_cohort`].
+pub(crate) const unsafe fn format_decimal64_spec(
+    bits: u64,
+    cohort: bool,
+    result: *mut u8,
+) -> Formatted {
+    write_decimal_parts(decimal64_parts(bits), cohort, result)
+}
+
+/// Print an IEEE 754 decimal128 float in the BID encoding, given as raw
+/// bits, to the given buffer and return number of bytes written.
+///
+/// This is the decimal128 version of [`format_decimal64`], with up to 34
+/// significant digits. A coefficient above 10^34 - 1 is not canonical and
+/// prints as zero. At most 42 bytes will be written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input number
+/// is not a finite float, the printed representation will be some correctly
+/// formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the representation of the number.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// // -0.1, as the coefficient 1 and the exponent -1.
+/// let bits = 0xb03e_0000_0000_0000_0000_0000_0000_0001u128;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 42];
+///     let len = ryuu::raw::format_decimal128(bits, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "-0.1");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format_decimal128(bits: u128, result: *mut u8) -> usize {
+    format_decimal128_spec(bits, false, result).initialized
+}
+
+/// Like [`format_decimal128`], but prints the exact coefficient and exponent
+/// with the layout of [`format_decimal64_cohort`]. At most 42 bytes will be
+/// written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input number
+/// is not a finite float, the printed representation will be some correctly
+/// formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the representation of the number.
+#[must_use]
+pub const unsafe fn format_decimal128_cohort(bits: u128, result: *mut u8) -> usize {
+    format_decimal128_spec(bits, true, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// See [`format_decimal128`] and [`format_decimal128_cohort`].
+pub(crate) const unsafe fn format_decimal128_spec(
+    bits: u128,
+    cohort: bool,
+    result: *mut u8,
+) -> Formatted {
+    write_decimal_parts(decimal128_parts(bits), cohort, result)
+}
 
+// Writes a finite decimal float, either normalized with the same layout as
+// `format64`, or with its exact coefficient and exponent.
+const unsafe fn write_decimal_parts(v: DecimalParts, cohort: bool, result: *mut u8) -> Formatted {
     let mut index = 0isize;
-    if sign {
+    if v.sign {
         *result = b'-';
         index += 1;
     }
 
-    if ieee_exponent == 0 && ieee_mantissa == 0 {
+    if cohort {
+        return write_decimal_cohort(index, v.coefficient, v.exponent, result);
+    }
+
+    if v.coefficient == 0 {
         ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
 
         return Formatted {
-            initialized: sign as usize + 3,
+            initialized: index as usize + 3,
             meta: FormattedMeta::Decimal {
-                offset_decimal_point: sign as usize + 1,
+                offset_decimal_point: index as usize + 1,
             },
         };
     }
 
-    let v = f2d(ieee_mantissa, ieee_exponent);
+    let mut mantissa = v.coefficient;
+    let mut exponent = v.exponent;
+    while mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    write_floating_decimal128(index, FloatingDecimal128 { mantissa, exponent }, result)
+}
 
-    let length = common::decimal_length9(v.mantissa) as isize;
-    let k = v.exponent as isize;
-    let kk = length + k; // 10^(kk-1) <= v < 10^kk
-    debug_assert!(k >= -45);
+// Writes the coefficient and exponent of a decimal float after the sign,
+// which has already been written to the first `index` bytes of `result`.
+const unsafe fn write_decimal_cohort(
+    index: isize,
+    coefficient: u128,
+    exponent: i32,
+    result: *mut u8,
+) -> Formatted {
+    let length = q2s::decimal_length39(coefficient) as isize;
+    let k = exponent as isize;
+    let kk = length + k;
 
-    if 0 <= k && kk <= 13 {
-        // 1234e7 -> 12340000000.0
-        write_mantissa(v.mantissa, result.offset(index + length));
-        unroll!(i in length..kk => {
-            *result.offset(index + i) = b'0';
-        });
-        *result.offset(index + kk) = b'.';
-        *result.offset(index + kk + 1) = b'0';
+    if k == 0 {
+        // 120e0 -> 120
+        write_mantissa_u128(coefficient, result.offset(index + length));
 
         Formatted {
-            initialized: index as usize + kk as usize + 2,
+            initialized: (index + length) as usize,
             meta: FormattedMeta::Decimal {
-                offset_decimal_point: (index + kk) as usize,
+                offset_decimal_point: (index + length) as usize,
             },
         }
-    } else if 0 < kk && kk <= 13 {
-        // 1234e-2 -> 12.34
-        write_mantissa(v.mantissa, result.offset(index + length + 1));
+    } else if k < 0 && 0 < kk {
+        // 120e-2 -> 1.20
+        write_mantissa_u128(coefficient, result.offset(index + length + 1));
         ptr::copy(result.offset(index + 1), result.offset(index), kk as usize);
         *result.offset(index + kk) = b'.';
 
         Formatted {
-            initialized: index as usize + length as usize + 1,
+            initialized: (index + length + 1) as usize,
             meta: FormattedMeta::Decimal {
                 offset_decimal_point: (index + kk) as usize,
             },
         }
-    } else if -6 < kk && kk <= 0 {
-        // 1234e-6 -> 0.001234
+    } else if k < 0 && -5 < kk {
+        // 120e-6 -> 0.000120
         *result.offset(index) = b'0';
         *result.offset(index + 1) = b'.';
         let offset = 2 - kk;
-        unroll!(i in 2..offset => {
-            *result.offset(index + i) = b'0';
-        });
-        write_mantissa(v.mantissa, result.offset(index + length + offset));
+        result.offset(index + 2).write_bytes(b'0', -kk as usize);
+        write_mantissa_u128(coefficient, result.offset(index + length + offset));
 
         Formatted {
-            initialized: index as usize + length as usize + offset as usize,
+            initialized: (index + length + offset) as usize,
             meta: FormattedMeta::Decimal {
                 offset_decimal_point: (index + 1) as usize,
             },
         }
     } else if length == 1 {
-        // 1e30
-        *result.offset(index) = b'0' + v.mantissa as u8;
+        // 1e7
+        *result.offset(index) = b'0' + coefficient as u8;
         *result.offset(index + 1) = b'e';
 
         Formatted {
-            initialized: index as usize + 2 + write_exponent2(kk - 1, result.offset(index + 2)),
+            initialized: index as usize + 2 + write_exponent4(kk - 1, result.offset(index + 2)),
             meta: FormattedMeta::Exponent {
                 offset_decimal_point: None,
                 offset_exponent: (index + 1) as usize,
             },
         }
     } else {
-        // 1234e30 -> 1.234e33
-        write_mantissa(v.mantissa, result.offset(index + length + 1));
+        // 120e3 -> 1.20e5
+        write_mantissa_u128(coefficient, result.offset(index + length + 1));
         *result.offset(index) = *result.offset(index + 1);
         *result.offset(index + 1) = b'.';
         *result.offset(index + length + 1) = b'e';
@@ -307,7 +2279,7 @@ pub(crate) const unsafe fn format32_spec(f: f32, result: *mut u8) -> Formatted {
             initialized: index as usize
                 + length as usize
                 + 2
-                + write_exponent2(kk - 1, result.offset(index + length + 2)),
+                + write_exponent4(kk - 1, result.offset(index + length + 2)),
             meta: FormattedMeta::Exponent {
                 offset_decimal_point: Some((index + 1) as usize),
                 offset_exponent: (index + length + 1) as usize,
@@ -316,6 +2288,71 @@ pub(crate) const unsafe fn format32_spec(f: f32, result: *mut u8) -> Formatted {
     }
 }
 
+/// Print a binary fixed-point number to the given buffer and return number of
+/// bytes written.
+///
+/// The number is `raw * 2^-frac_bits`, as in the Q formats of DSPs: Q15.16
+/// has 16 fractional bits and Q1.31 has 31. This prints the shortest decimal
+/// that rounds back to `raw`, with ties to even, and of those, the one
+/// closest to the number. It is never in exponent form, and integers end in
+/// ".0" as in [`format64`]. At most 23 bytes will be written.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the representation of the number, and `frac_bits` must be at most
+/// 64.
+///
+/// ## Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::{slice, str};
+///
+/// // 1.1 in Q15.16 is 72090 * 2^-16.
+/// let raw = 72090i64;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 23];
+///     let len = ryuu::raw::format_fixed_point(raw, 16, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "1.1");
+/// }
+/// ```
+#[must_use]
+pub const unsafe fn format_fixed_point(raw: i64, frac_bits: u32, result: *mut u8) -> usize {
+    format_fixed_point_spec(raw, frac_bits, result).initialized
+}
+
+#[inline]
+#[must_use]
+/// Print a binary fixed-point number, returning the [`Formatted`] meta info.
+pub(crate) const unsafe fn format_fixed_point_spec(
+    raw: i64,
+    frac_bits: u32,
+    result: *mut u8,
+) -> Formatted {
+    debug_assert!(frac_bits <= 64);
+    let mut index = 0isize;
+    if raw < 0 {
+        *result = b'-';
+        index += 1;
+    }
+
+    if raw == 0 {
+        return write_fixed(index, 0, 0, 1, false, result);
+    }
+
+    let v = fixed2d(raw.unsigned_abs(), frac_bits);
+    let decimal_places = if v.exponent < 0 {
+        -v.exponent as usize
+    } else {
+        1
+    };
+    write_fixed(index, v.mantissa, v.exponent, decimal_places, false, result)
+}
+
 #[derive(Debug, Clone, Copy)]
 /// The formatted result.
 pub struct Formatted {
@@ -331,7 +2368,9 @@ pub struct Formatted {
 pub enum FormattedMeta {
     /// The offset of the decimal point in the string representation.
     Decimal {
-        /// The offset of the decimal point in the string representation.
+        /// The offset of the decimal point in the string representation, or
+        /// its length if there is no decimal point, as in the integers of
+        /// [`format64_radix`].
         offset_decimal_point: usize,
     },
 
@@ -47,3 +47,23 @@ pub const unsafe fn write_exponent2(mut k: isize, mut result: *mut u8) -> usize
         sign as usize + 1
     }
 }
+
+#[inline]
+pub const unsafe fn write_exponent4(mut k: isize, mut result: *mut u8) -> usize {
+    let sign = k < 0;
+    if sign {
+        *result = b'-';
+        result = result.add(1);
+        k = -k;
+    }
+
+    debug_assert!(k < 10000);
+    if k >= 100 {
+        let length = write_exponent3(k / 100, result);
+        let d = DIGIT_TABLE.as_ptr().add((k % 100 * 2) as usize);
+        ptr::copy_nonoverlapping(d, result.add(length), 2);
+        sign as usize + length + 2
+    } else {
+        sign as usize + write_exponent2(k, result)
+    }
+}
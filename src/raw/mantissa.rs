@@ -81,3 +81,27 @@ pub const unsafe fn write_mantissa(mut output: u32, mut result: *mut u8) {
         *result.sub(1) = b'0' + output as u8;
     }
 }
+
+#[inline]
+pub const unsafe fn write_mantissa_u128(mut output: u128, mut result: *mut u8) {
+    while output >= 10_000_000_000_000_000 {
+        // One expensive 128-bit division per 16 digits.
+        let quotient = output / 10_000_000_000_000_000;
+        let mut output2 = (output - 10_000_000_000_000_000 * quotient) as u64;
+        output = quotient;
+
+        let mut i = 0;
+        while i < 8 {
+            let c = (output2 % 100) << 1;
+            output2 /= 100;
+            ptr::copy_nonoverlapping(
+                DIGIT_TABLE.as_ptr().add(c as usize),
+                result.sub(2),
+                2,
+            );
+            result = result.sub(2);
+            i += 1;
+        }
+    }
+    write_mantissa_long(output as u64, result);
+}
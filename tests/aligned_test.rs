@@ -0,0 +1,147 @@
+use ryuu::format::RoundingMode::{self, HalfEven};
+use ryuu::Formatter;
+
+fn aligned(values: &[f64]) -> Vec<String> {
+    Formatter::format_aligned_f64(values)
+        .map(|cell| cell.to_string())
+        .collect()
+}
+
+fn aligned_dp(values: &[f64], decimal_places: usize, mode: RoundingMode) -> Vec<String> {
+    Formatter::format_aligned_dp_f64(values, decimal_places, mode)
+        .map(|cell| cell.to_string())
+        .collect()
+}
+
+#[test]
+fn test_decimal_points() {
+    assert_eq!(
+        aligned(&[1.25, 1.2, -20.5, 300.0]),
+        ["  1.25", "  1.2 ", "-20.5 ", "300.0 "]
+    );
+    assert_eq!(
+        aligned(&[1.0, 22.0, -333.0]),
+        ["   1.0", "  22.0", "-333.0"]
+    );
+    assert_eq!(aligned(&[0.0, -0.0, 0.5]), [" 0.0", "-0.0", " 0.5"]);
+    // The shortest digits are kept, so no value is lost.
+    assert_eq!(aligned(&[1.5, 3.0]), ["1.5", "3.0"]);
+    assert_eq!(aligned(&[7.0]), ["7.0"]);
+    assert!(aligned(&[]).is_empty());
+}
+
+#[test]
+fn test_exponent_form() {
+    // The exponents extend the fraction side of the column.
+    assert_eq!(
+        aligned(&[0.25, 0.5, 1.5e20, -2.5e-7]),
+        [" 0.25  ", " 0.5   ", " 1.5e20", "-2.5e-7"]
+    );
+    // A mantissa without a decimal point is aligned as an integer.
+    assert_eq!(aligned(&[10.0, 1e20]), ["10.0 ", " 1e20"]);
+}
+
+#[test]
+fn test_nonfinite() {
+    assert_eq!(
+        aligned(&[f64::NAN, 1.5, 2.0, f64::NEG_INFINITY, f64::INFINITY]),
+        [" NaN  ", "   1.5", "   2.0", "-inf  ", " inf  "]
+    );
+}
+
+#[test]
+fn test_decimal_places() {
+    let values = [1.25, 1.2, -20.5, 300.0];
+    let decimal_places = Formatter::shared_decimal_places_f64(&values, HalfEven);
+    assert_eq!(
+        aligned_dp(&values, decimal_places, HalfEven),
+        ["  1.25", "  1.20", "-20.50", "300.00"]
+    );
+    assert_eq!(aligned_dp(&[1.5, 3.0], 0, HalfEven), ["2", "3"]);
+    assert_eq!(
+        aligned_dp(&[0.25, 0.5, 1.5e20, -2.5e-7], 1, HalfEven),
+        [" 0.2   ", " 0.5   ", " 1.5e20", "-2.5e-7"]
+    );
+    assert_eq!(aligned_dp(&[10.0, 1e20], 0, HalfEven), ["10   ", " 1e20"]);
+}
+
+#[test]
+#[should_panic = "`decimal_places` must be at most 21"]
+fn test_too_many_decimal_places() {
+    let _ = Formatter::format_aligned_dp_f64(&[1.0], 22, HalfEven);
+}
+
+#[test]
+fn test_column() {
+    let values = [1.25, 1.2, -20.5, 300.0];
+    let column = Formatter::format_aligned_f64(&values);
+    assert_eq!(column.decimal_places(), None);
+    assert_eq!(column.integer_width(), 3);
+    assert_eq!(column.fraction_width(), 3);
+    assert_eq!(column.width(), 6);
+    assert_eq!(column.len(), 4);
+
+    let last = column.clone().next_back().unwrap();
+    assert_eq!(last.formatted().as_str(), "300.0");
+    assert_eq!(last.leading_spaces(), 0);
+    assert_eq!(last.trailing_spaces(), 1);
+
+    let first = column.clone().next().unwrap();
+    assert_eq!(first.leading_spaces(), 2);
+    assert_eq!(first.str_len(), 6);
+    let mut buffer = [0u8; 6];
+    assert_eq!(first.write_to(&mut buffer), Some("  1.25"));
+    assert_eq!(first.write_to(&mut buffer[..5]), None);
+
+    let mut column = Formatter::format_aligned_dp_f64(&values, 2, HalfEven);
+    assert_eq!(column.decimal_places(), Some(2));
+    assert_eq!(column.next_back().unwrap().formatted().as_str(), "300.00");
+}
+
+#[test]
+fn test_f32() {
+    let values = [0.1, 0.15, -12.25, 3e15];
+    let cells: Vec<String> = Formatter::format_aligned_f32(&values)
+        .map(|cell| cell.to_string())
+        .collect();
+    assert_eq!(cells, ["  0.1 ", "  0.15", "-12.25", "  3e15"]);
+    let cells: Vec<String> = Formatter::format_aligned_dp_f32(&values, 1, HalfEven)
+        .map(|cell| cell.to_string())
+        .collect();
+    assert_eq!(cells, ["  0.1   ", "  0.2   ", "-12.2   ", "  3.0e15"]);
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 10 } else { 1000 };
+    for _ in 0..n {
+        let len = rand::random_range(1..20);
+        let values: Vec<f64> = (0..len)
+            .map(|_| match rand::random_range(0..3) {
+                0 => f64::from_bits(rand::random::<u64>()),
+                1 => rand::random_range(-1e6..1e6),
+                _ => f64::from(rand::random_range(-1000..1000)) / 8.0,
+            })
+            .collect();
+        let decimal_places = Formatter::shared_decimal_places_f64(&values, HalfEven);
+        for column in [
+            Formatter::format_aligned_f64(&values),
+            Formatter::format_aligned_dp_f64(&values, decimal_places, HalfEven),
+        ] {
+            let width = column.width();
+            let integer_width = column.integer_width();
+            let shortest = column.decimal_places().is_none();
+            for (cell, d) in column.zip(&values) {
+                let text = cell.to_string();
+                assert_eq!(text.len(), width, "{values:?}");
+                assert_eq!(text.trim(), cell.formatted().as_str(), "{values:?}");
+                if shortest {
+                    assert_eq!(text.trim(), Formatter::format_f64(*d).as_str());
+                }
+                if d.is_finite() && text.contains('.') {
+                    assert_eq!(text.find('.'), Some(integer_width), "{values:?}");
+                }
+            }
+        }
+    }
+}
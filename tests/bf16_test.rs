@@ -0,0 +1,44 @@
+#![allow(clippy::unreadable_literal)]
+
+fn pretty(bits: u16) -> String {
+    ryuu::Formatter::format_bf16_bits(bits).as_str().to_owned()
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(pretty(0x0000), "0.0");
+    assert_eq!(pretty(0x8000), "-0.0");
+    assert_eq!(pretty(0x3f80), "1.0");
+    assert_eq!(pretty(0xbf80), "-1.0");
+    assert_eq!(pretty(0x7fc0), "NaN");
+    assert_eq!(pretty(0xffc0), "NaN");
+    assert_eq!(pretty(0x7f80), "inf");
+    assert_eq!(pretty(0xff80), "-inf");
+}
+
+#[test]
+fn test_ryu() {
+    assert_eq!(pretty(0x3dcd), "0.1");
+    assert_eq!(pretty(0x3eab), "0.334");
+    assert_eq!(pretty(0x4049), "3.14");
+    assert_eq!(pretty(0x402e), "2.72");
+    assert_eq!(pretty(0x4480), "1024.0");
+    assert_eq!(pretty(0x5368), "996000000000.0");
+}
+
+#[test]
+fn test_min_and_max() {
+    assert_eq!(pretty(0x7f7f), "3.39e38");
+    assert_eq!(pretty(0x0080), "1.18e-38");
+    assert_eq!(pretty(0x007f), "1.17e-38");
+    assert_eq!(pretty(0x0001), "1e-40");
+}
+
+#[test]
+fn test_non_finite() {
+    for i in 0u16..1 << 7 {
+        let bits = 0x7f80 + i;
+        ryuu::Formatter::format_finite_bf16_bits(bits);
+        ryuu::Formatter::format_finite_bf16_bits(bits | 0x8000);
+    }
+}
@@ -0,0 +1,103 @@
+#![allow(clippy::unreadable_literal)]
+
+use ryuu::Formatter;
+
+// Encodes a decimal64 whose coefficient fits in 53 bits.
+fn d64(sign: bool, coefficient: u64, exponent: i32) -> u64 {
+    ((sign as u64) << 63) | (((exponent + 398) as u64) << 53) | coefficient
+}
+
+fn d128(sign: bool, coefficient: u128, exponent: i32) -> u128 {
+    ((sign as u128) << 127) | (((exponent + 6176) as u128) << 113) | coefficient
+}
+
+fn both64(bits: u64) -> (String, String) {
+    (
+        Formatter::format_decimal64_bits(bits).as_str().to_owned(),
+        Formatter::format_decimal64_bits_cohort(bits)
+            .as_str()
+            .to_owned(),
+    )
+}
+
+fn both128(bits: u128) -> (String, String) {
+    (
+        Formatter::format_decimal128_bits(bits).as_str().to_owned(),
+        Formatter::format_decimal128_bits_cohort(bits)
+            .as_str()
+            .to_owned(),
+    )
+}
+
+#[test]
+fn test_decimal64() {
+    let pair = |a: &str, b: &str| (a.to_owned(), b.to_owned());
+    assert_eq!(both64(d64(false, 120, -2)), pair("1.2", "1.20"));
+    assert_eq!(both64(d64(false, 12, -1)), pair("1.2", "1.2"));
+    assert_eq!(both64(d64(true, 120, 0)), pair("-120.0", "-120"));
+    assert_eq!(both64(d64(false, 12, 1)), pair("120.0", "1.2e2"));
+    assert_eq!(both64(d64(false, 1, 20)), pair("1e20", "1e20"));
+    assert_eq!(both64(d64(false, 120, -6)), pair("0.00012", "0.000120"));
+    assert_eq!(both64(d64(false, 1, -7)), pair("1e-7", "1e-7"));
+    assert_eq!(both64(d64(false, 1234, -9)), pair("1.234e-6", "1.234e-6"));
+    assert_eq!(both64(d64(false, 0, 0)), pair("0.0", "0"));
+    assert_eq!(both64(d64(true, 0, -2)), pair("-0.0", "-0.00"));
+    assert_eq!(both64(d64(false, 0, 5)), pair("0.0", "0e5"));
+    assert_eq!(both64(d64(false, 1, -398)), pair("1e-398", "1e-398"));
+
+    // The largest decimal64, with the coefficient in the second form.
+    let max = 0x77fb86f26fc0ffff;
+    assert_eq!(
+        both64(max),
+        pair("9.999999999999999e384", "9.999999999999999e384")
+    );
+    // A noncanonical coefficient above 10^16 - 1 is zero.
+    assert_eq!(both64(0x6c7386f26fc10000), pair("0.0", "0"));
+}
+
+#[test]
+fn test_decimal128() {
+    let pair = |a: &str, b: &str| (a.to_owned(), b.to_owned());
+    assert_eq!(both128(d128(true, 1, -1)), pair("-0.1", "-0.1"));
+    let coefficient = 1234567890123456789012345678901234;
+    assert_eq!(
+        both128(d128(false, coefficient, -18)),
+        pair(
+            "1234567890123456.789012345678901234",
+            "1234567890123456.789012345678901234"
+        )
+    );
+    assert_eq!(
+        both128(d128(false, coefficient, -6176)),
+        pair(
+            "1.234567890123456789012345678901234e-6143",
+            "1.234567890123456789012345678901234e-6143"
+        )
+    );
+    assert_eq!(
+        both128(d128(true, 10u128.pow(33), -38)),
+        pair("-0.00001", "-0.00001000000000000000000000000000000000")
+    );
+    // The second form of the coefficient is never canonical.
+    assert_eq!(
+        both128(0x6000_0000_0000_0000_0000_0000_0000_0001),
+        pair("0.0", "0e-6176")
+    );
+}
+
+#[test]
+fn test_nonfinite() {
+    let expected = |s: &str| (s.to_owned(), s.to_owned());
+    assert_eq!(both64(0x7800000000000000), expected("inf"));
+    assert_eq!(both64(0xf800000000000000), expected("-inf"));
+    assert_eq!(both64(0x7c00000000000000), expected("NaN"));
+    assert_eq!(both64(0xfe00000000000001), expected("NaN"));
+    assert_eq!(
+        both128(0x7800_0000_0000_0000_0000_0000_0000_0000),
+        expected("inf")
+    );
+    assert_eq!(
+        both128(0xfc00_0000_0000_0000_0000_0000_0000_0000),
+        expected("NaN")
+    );
+}
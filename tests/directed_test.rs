@@ -0,0 +1,119 @@
+#![allow(clippy::float_cmp)]
+
+use ryuu::Formatter;
+
+fn down(d: f64) -> String {
+    Formatter::format_f64_round_down(d).as_str().to_owned()
+}
+
+fn up(d: f64) -> String {
+    Formatter::format_f64_round_up(d).as_str().to_owned()
+}
+
+// Digits and exponent of a decimal string with trailing zeros removed, so
+// that `digits * 10^exponent` is its value.
+fn decimal(s: &str) -> (bool, String, i32) {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().unwrap()),
+        None => (s, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let mut digits = format!("{int}{frac}");
+    let mut exponent = exponent - frac.len() as i32;
+    while digits.len() > 1 && digits.starts_with('0') {
+        digits.remove(0);
+    }
+    while digits.len() > 1 && digits.ends_with('0') {
+        digits.pop();
+        exponent += 1;
+    }
+    (negative, digits, exponent)
+}
+
+// Compares two decimal strings exactly.
+fn compare(a: &str, b: &str) -> core::cmp::Ordering {
+    let (a_negative, a_digits, a_exponent) = decimal(a);
+    let (b_negative, b_digits, b_exponent) = decimal(b);
+    assert!(a_digits != "0" && b_digits != "0" && a_negative == b_negative);
+    let a_kk = a_digits.len() as i32 + a_exponent;
+    let b_kk = b_digits.len() as i32 + b_exponent;
+    let magnitude = a_kk.cmp(&b_kk).then_with(|| {
+        let width = a_digits.len().max(b_digits.len());
+        format!("{a_digits:0<width$}").cmp(&format!("{b_digits:0<width$}"))
+    });
+    if a_negative {
+        magnitude.reverse()
+    } else {
+        magnitude
+    }
+}
+
+#[test]
+fn test_basic() {
+    // 0.1 is 0.1000000000000000055511151231257827 as an f64.
+    assert_eq!(down(0.1), "0.1");
+    assert_eq!(up(0.1), "0.10000000000000001");
+    assert_eq!(down(-0.1), "-0.10000000000000001");
+    assert_eq!(up(-0.1), "-0.1");
+    // 0.3 is 0.2999999999999999888977697537484346 as an f64.
+    assert_eq!(down(0.3), "0.29999999999999998");
+    assert_eq!(up(0.3), "0.3");
+    // Exact values are their own bounds.
+    assert_eq!(down(0.5), "0.5");
+    assert_eq!(up(0.5), "0.5");
+    assert_eq!(down(1e22), "1e22");
+    assert_eq!(up(1e22), "1e22");
+    assert_eq!(down(9007199254740993.0), "9007199254740992.0");
+}
+
+#[test]
+fn test_special() {
+    assert_eq!(down(0.0), "0.0");
+    assert_eq!(up(-0.0), "-0.0");
+    assert_eq!(down(f64::NAN), "NaN");
+    assert_eq!(up(f64::INFINITY), "inf");
+    assert_eq!(down(f64::NEG_INFINITY), "-inf");
+    // 5e-324 is 4.940656458412465441765687928682213723651e-324.
+    assert_eq!(down(5e-324), "4e-324");
+    assert_eq!(up(5e-324), "5e-324");
+    assert_eq!(down(f64::MAX), "1.7976931348623157e308");
+    assert_eq!(up(f64::MAX), "1.7976931348623158e308");
+    assert_eq!(f64::MAX, "1.7976931348623158e308".parse().unwrap());
+}
+
+#[test]
+fn test_eighteen_digits() {
+    // Half of the rounding interval is too narrow for 17 digits.
+    let d = 1.1568539726774643e-172;
+    assert_eq!(down(d), "1.1568539726774643e-172");
+    assert_eq!(up(d), "1.15685397267746432e-172");
+    assert_eq!(d, up(d).parse().unwrap());
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 100 } else { 10000 };
+    for _ in 0..n {
+        let d = f64::from_bits(rand::random::<u64>());
+        if !d.is_finite() || d == 0.0 {
+            continue;
+        }
+        let exact = format!("{d:.800e}");
+        let shortest = Formatter::format_f64(d);
+        for (formatted, side) in [
+            (down(d), core::cmp::Ordering::Less),
+            (up(d), core::cmp::Ordering::Greater),
+        ] {
+            assert_eq!(d, formatted.parse::<f64>().unwrap(), "{d:e}");
+            assert_ne!(compare(&formatted, &exact), side.reverse(), "{d:e}");
+            assert!(formatted.len() >= shortest.len(), "{d:e}");
+            if compare(shortest.as_str(), &exact) != side.reverse() {
+                assert_eq!(formatted, shortest.as_str(), "{d:e}");
+            }
+        }
+    }
+}
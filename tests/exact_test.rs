@@ -0,0 +1,124 @@
+#![allow(clippy::unreadable_literal)]
+
+use ryuu::format::{BUFFER_LEN_EXACT, BUFFER_LEN_EXACT_F32};
+use ryuu::{raw, Formatter};
+
+fn exact(d: f64) -> String {
+    let text = Formatter::exact_decimal_f64(d).to_string();
+    let mut buffer = [0u8; BUFFER_LEN_EXACT];
+    assert_eq!(
+        Formatter::exact_decimal_f64(d).write_to(&mut buffer),
+        Some(&*text),
+    );
+    assert_eq!(Formatter::exact_decimal_f64(d).str_len(), text.len());
+    text
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(exact(0.0), "0.0");
+    assert_eq!(exact(-0.0), "-0.0");
+    assert_eq!(exact(1.0), "1.0");
+    assert_eq!(exact(-1.5), "-1.5");
+    assert_eq!(exact(0.375), "0.375");
+    assert_eq!(exact(123456.0), "123456.0");
+    assert_eq!(
+        exact(0.1),
+        "0.1000000000000000055511151231257827021181583404541015625",
+    );
+    assert_eq!(
+        exact(0.3),
+        "0.299999999999999988897769753748434595763683319091796875",
+    );
+    assert_eq!(exact(1e23), "99999999999999991611392.0");
+    assert_eq!(exact(9007199254740993.0), "9007199254740992.0");
+}
+
+#[test]
+fn test_chunks() {
+    // Integer parts around the 19-digit chunks of the digit generation.
+    assert_eq!(exact(1e18), "1000000000000000000.0");
+    assert_eq!(exact(1e19), "10000000000000000000.0");
+    assert_eq!(exact(18446744073709551616.0), "18446744073709551616.0");
+    assert_eq!(exact(1e38), "99999999999999997748809823456034029568.0",);
+    // Fractions with 19 and 20 digits.
+    assert_eq!(exact(2f64.powi(-19)), "0.0000019073486328125");
+    assert_eq!(exact(2f64.powi(-20)), "0.00000095367431640625");
+    assert_eq!(exact(1.0 + 2f64.powi(-20)), "1.00000095367431640625");
+}
+
+#[test]
+fn test_extremes() {
+    let max = exact(f64::MAX);
+    assert!(max.starts_with("179769313486231570814527423731704356798070567525844996598917476803157260780028538760589558632766878171540458953514382464234321326889464182768467546703537516986049910576551282076245490090389328944075868508455133942304583236903222948165808559332123348274797826204144723168738177180919299881250404026184124858368"));
+    assert_eq!(max.len(), 311);
+    assert_eq!(exact(f64::MIN).len(), 312);
+
+    let min = exact(5e-324);
+    assert!(min.starts_with("0.000"));
+    assert!(min.ends_with("19718265533447265625"));
+    assert_eq!(min.len(), 2 + 1074);
+    assert_eq!(exact(-5e-324).len(), BUFFER_LEN_EXACT);
+
+    // The largest subnormal has the most significant digits.
+    let largest_subnormal = exact(f64::from_bits(0x000f_ffff_ffff_ffff));
+    let significant = largest_subnormal.trim_start_matches(['0', '.']);
+    assert_eq!(significant.len(), 767);
+}
+
+#[test]
+fn test_nonfinite() {
+    assert_eq!(exact(f64::NAN), "NaN");
+    assert_eq!(exact(f64::INFINITY), "inf");
+    assert_eq!(exact(f64::NEG_INFINITY), "-inf");
+}
+
+#[test]
+fn test_buffer() {
+    let mut buffer = [0u8; 8];
+    assert_eq!(
+        Formatter::exact_decimal_f64(0.375).write_to(&mut buffer),
+        Some("0.375")
+    );
+    assert_eq!(
+        Formatter::exact_decimal_f64(0.1).write_to(&mut buffer),
+        None
+    );
+    assert_eq!(
+        Formatter::exact_decimal_f64(f64::NAN).write_to(&mut buffer[..3]),
+        Some("NaN")
+    );
+    assert_eq!(
+        Formatter::exact_decimal_f64(-0.5).write_to(&mut buffer[..3]),
+        None
+    );
+
+    // Const evaluation.
+    const TEXT: [u8; 6] = {
+        let mut buffer = [0u8; 6];
+        match Formatter::exact_decimal_f64(-0.125).write_to(&mut buffer) {
+            Some(_) => {}
+            None => panic!(),
+        }
+        buffer
+    };
+    assert_eq!(&TEXT, b"-0.125");
+}
+
+#[test]
+fn test_f32() {
+    let exact = |f: f32| Formatter::exact_decimal_f32(f).to_string();
+    assert_eq!(exact(0.1), "0.100000001490116119384765625");
+    assert_eq!(exact(16777217.0), "16777216.0");
+    assert_eq!(exact(f32::MAX), "340282346638528859811704183484516925440.0",);
+    assert_eq!(exact(-f32::from_bits(1)).len(), BUFFER_LEN_EXACT_F32);
+}
+
+#[test]
+fn test_raw() {
+    let mut buffer = [0u8; BUFFER_LEN_EXACT_F32];
+    let len = unsafe { raw::format32_exact(0.1, buffer.as_mut_ptr()) };
+    assert_eq!(&buffer[..len], b"0.100000001490116119384765625");
+    let len = unsafe { raw::format64_exact(-2.5, buffer.as_mut_ptr()) };
+    assert_eq!(&buffer[..len], b"-2.5");
+}
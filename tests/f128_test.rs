@@ -0,0 +1,95 @@
+#![allow(clippy::unreadable_literal)]
+
+fn pretty(bits: u128) -> String {
+    ryuu::Formatter::format_f128_bits(bits).as_str().to_owned()
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(pretty(0), "0.0");
+    assert_eq!(pretty(1 << 127), "-0.0");
+    assert_eq!(pretty(0x3fff0000000000000000000000000000), "1.0");
+    assert_eq!(pretty(0xbfff0000000000000000000000000000), "-1.0");
+    assert_eq!(pretty(0x7fff8000000000000000000000000000), "NaN");
+    assert_eq!(pretty(0xffff8000000000000000000000000000), "NaN");
+    assert_eq!(pretty(0x7fff0000000000000000000000000000), "inf");
+    assert_eq!(pretty(0xffff0000000000000000000000000000), "-inf");
+}
+
+#[test]
+fn test_ryu() {
+    assert_eq!(pretty(0x3ffb999999999999999999999999999a), "0.1");
+    assert_eq!(
+        pretty(0x3ffd5555555555555555555555555555),
+        "0.3333333333333333333333333333333333"
+    );
+    assert_eq!(
+        pretty(0x4000921fb54442d18469898cc51701b8),
+        "3.1415926535897932384626433832795028"
+    );
+    assert_eq!(
+        pretty(0x40005bf0a8b1457695355fb8ac404e7a),
+        "2.7182818284590452353602874713526623"
+    );
+    assert_eq!(
+        pretty(0x403e0000000000000000000000000000),
+        "9.223372036854775808e18"
+    );
+    assert_eq!(
+        pretty(0x401f0000000000000000000000000001),
+        "4294967296.000000000000000000000001"
+    );
+}
+
+#[test]
+fn test_min_and_max() {
+    assert_eq!(
+        pretty(0x7ffeffffffffffffffffffffffffffff),
+        "1.189731495357231765085759326628007e4932"
+    );
+    assert_eq!(
+        pretty(0x00010000000000000000000000000000),
+        "3.3621031431120935062626778173217526e-4932"
+    );
+    assert_eq!(
+        pretty(0x0000ffffffffffffffffffffffffffff),
+        "3.362103143112093506262677817321752e-4932"
+    );
+    assert_eq!(pretty(0x00000000000000000000000000000001), "6e-4966");
+}
+
+#[test]
+fn test_large_integers() {
+    // Same as `f64`, integers from 10^16 on are printed in exponent form.
+    assert_eq!(
+        pretty(0x406f0000000000000000000000000000),
+        "5.192296858534827628530496329220096e33"
+    );
+    assert_eq!(
+        pretty(0x40700000000000000000000000000000),
+        "1.0384593717069655257060992658440192e34"
+    );
+}
+
+#[test]
+fn test_buffer_len() {
+    let longest = ryuu::Formatter::format_f128_bits(0x80010000000000000000000000000000);
+    assert_eq!(
+        longest.as_str(),
+        "-3.3621031431120935062626778173217526e-4932"
+    );
+    assert!(longest.len() <= ryuu::format::BUFFER_LEN_F128);
+}
+
+#[test]
+fn test_non_finite() {
+    for bits in [
+        1,
+        0x0000ffffffffffffffffffffffffffff,
+        0x8000000000000000000000000000,
+    ] {
+        let bits = 0x7fff0000000000000000000000000000 | bits;
+        ryuu::Formatter::format_finite_f128_bits(bits);
+        ryuu::Formatter::format_finite_f128_bits(bits | 1 << 127);
+    }
+}
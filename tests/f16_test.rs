@@ -0,0 +1,44 @@
+#![allow(clippy::unreadable_literal)]
+
+fn pretty(bits: u16) -> String {
+    ryuu::Formatter::format_f16_bits(bits).as_str().to_owned()
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(pretty(0x0000), "0.0");
+    assert_eq!(pretty(0x8000), "-0.0");
+    assert_eq!(pretty(0x3c00), "1.0");
+    assert_eq!(pretty(0xbc00), "-1.0");
+    assert_eq!(pretty(0x7e00), "NaN");
+    assert_eq!(pretty(0xfe00), "NaN");
+    assert_eq!(pretty(0x7c00), "inf");
+    assert_eq!(pretty(0xfc00), "-inf");
+}
+
+#[test]
+fn test_ryu() {
+    assert_eq!(pretty(0x2e66), "0.1");
+    assert_eq!(pretty(0x3555), "0.3333");
+    assert_eq!(pretty(0x4248), "3.14");
+    assert_eq!(pretty(0x4170), "2.719");
+    assert_eq!(pretty(0x6400), "1024.0");
+    assert_eq!(pretty(0x1400), "0.000977");
+}
+
+#[test]
+fn test_min_and_max() {
+    assert_eq!(pretty(0x7bff), "65500.0");
+    assert_eq!(pretty(0x0400), "0.00006104");
+    assert_eq!(pretty(0x03ff), "0.000061");
+    assert_eq!(pretty(0x0001), "6e-8");
+}
+
+#[test]
+fn test_non_finite() {
+    for i in 0u16..1 << 10 {
+        let bits = 0x7c00 + i;
+        ryuu::Formatter::format_finite_f16_bits(bits);
+        ryuu::Formatter::format_finite_f16_bits(bits | 0x8000);
+    }
+}
@@ -0,0 +1,88 @@
+#![allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+
+fn pretty(d: f64) -> String {
+    ryuu::Formatter::format_f64_as_f32(d).as_str().to_owned()
+}
+
+#[test]
+fn test_exact_f32() {
+    // An f64 that is exactly an f32 prints like the f32.
+    for f in [
+        1.0f32,
+        0.1,
+        0.3,
+        2.7182817,
+        1.1e32,
+        1.1e-32,
+        1e-45,
+        3.4028235e38,
+        -0.001234,
+        16777216.0,
+    ] {
+        assert_eq!(
+            pretty(f as f64),
+            ryuu::Formatter::format_f32(f).as_str(),
+            "{f}"
+        );
+    }
+}
+
+#[test]
+fn test_closest_candidate() {
+    assert_eq!(pretty(core::f64::consts::E), "2.7182818");
+    assert_eq!(
+        ryuu::Formatter::format_f32(core::f64::consts::E as f32).as_str(),
+        "2.7182817"
+    );
+    assert_eq!(pretty(1.0 / 3.0), "0.33333333");
+    assert_eq!(pretty(-1.0 / 3.0), "-0.33333333");
+    // 16777217 is halfway between two f32s and rounds to the even one, which
+    // it is still the shortest way to write.
+    assert_eq!(pretty(16777217.0), "16777217.0");
+    assert_eq!(16777217.0f32, 16777216.0);
+}
+
+#[test]
+fn test_cast_rules() {
+    assert_eq!(pretty(0.0), "0.0");
+    assert_eq!(pretty(-0.0), "-0.0");
+    assert_eq!(pretty(1e39), "inf");
+    assert_eq!(pretty(-1e39), "-inf");
+    assert_eq!(pretty(f64::MAX), "inf");
+    assert_eq!(pretty(f64::INFINITY), "inf");
+    assert_eq!(pretty(f64::NEG_INFINITY), "-inf");
+    assert_eq!(pretty(f64::NAN), "NaN");
+    assert_eq!(pretty(1e-46), "0.0");
+    assert_eq!(pretty(-1e-46), "-0.0");
+    assert_eq!(pretty(f64::MIN_POSITIVE), "0.0");
+    // Half an ulp past the largest f32 overflows, anything below rounds to
+    // it.
+    assert_eq!(pretty(3.4028235677973366e38), "inf");
+    assert_eq!(pretty(3.4028235677973362e38), "3.4028235e38");
+    // Just above half of the smallest subnormal rounds up to it.
+    assert_eq!(pretty(7.1e-46), "1e-45");
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 100 } else { 1000000 };
+    for _ in 0..n {
+        // Random f32 bit patterns, perturbed by up to half an f32 ulp.
+        let f = f32::from_bits(rand::random::<u32>() & 0x7fff_ffff);
+        if !f.is_finite() {
+            continue;
+        }
+        let d = f as f64 * (1.0 + (rand::random::<f64>() - 0.5) * 2f64.powi(-24));
+        let f = d as f32;
+
+        let formatted = pretty(d);
+        let shortest = ryuu::Formatter::format_f32(f);
+        assert_eq!(f, formatted.parse::<f32>().unwrap(), "{d:e}");
+        assert_eq!(formatted.len(), shortest.len(), "{d:e}");
+        if f.is_finite() && f != 0.0 {
+            let ours = (formatted.parse::<f64>().unwrap() - d).abs();
+            let theirs = (shortest.parse::<f64>().unwrap() - d).abs();
+            assert!(ours <= theirs * (1.0 + 1e-9), "{d:e}");
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use ryuu::{raw, Formatter};
+
+fn fixed(raw: i64, frac_bits: u32) -> String {
+    Formatter::format_fixed_point(raw, frac_bits)
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(fixed(0, 16), "0.0");
+    assert_eq!(fixed(1 << 16, 16), "1.0");
+    assert_eq!(fixed(-3 << 16, 16), "-3.0");
+    assert_eq!(fixed(72090, 16), "1.1");
+    assert_eq!(fixed(-72090, 16), "-1.1");
+    assert_eq!(fixed(3, 1), "1.5");
+    assert_eq!(fixed(1, 10), "0.001");
+    assert_eq!(fixed(1 << 62, 64), "0.25");
+}
+
+#[test]
+fn test_shortest() {
+    // 0.25 and 0.3125 are as far from 0.2 as from 0.3, and the even one wins.
+    assert_eq!(fixed(1, 2), "0.2");
+    assert_eq!(fixed(5, 4), "0.3");
+    assert_eq!(fixed(1, 16), "0.00002");
+    assert_eq!(fixed(i32::MAX as i64, 16), "32767.99998");
+    assert_eq!(fixed(214748365, 31), "0.1");
+    assert_eq!(fixed(i32::MAX as i64, 31), "0.9999999995");
+}
+
+#[test]
+fn test_limits() {
+    assert_eq!(fixed(i64::MAX, 0), "9223372036854775807.0");
+    assert_eq!(fixed(i64::MIN, 0), "-9223372036854775808.0");
+    assert_eq!(fixed(i64::MIN, 63), "-1.0");
+    assert_eq!(fixed(i64::MAX, 63), "0.9999999999999999999");
+    assert_eq!(fixed(i64::MAX, 64), "0.49999999999999999995");
+    assert_eq!(fixed(-1, 64), "-0.00000000000000000005");
+}
+
+#[test]
+#[should_panic = "`frac_bits` must be at most 64"]
+fn test_too_many_frac_bits() {
+    let _ = Formatter::format_fixed_point(1, 65);
+}
+
+#[test]
+fn test_raw() {
+    let mut buffer = [0u8; 23];
+    let len = unsafe { raw::format_fixed_point(-1, 64, buffer.as_mut_ptr()) };
+    assert_eq!(&buffer[..len], b"-0.00000000000000000005");
+    let len = unsafe { raw::format_fixed_point(i32::MIN as i64, 31, buffer.as_mut_ptr()) };
+    assert_eq!(&buffer[..len], b"-1.0");
+}
@@ -0,0 +1,122 @@
+use ryuu::format::RoundingMode::{
+    self, AwayFromZero, Ceiling, Floor, HalfAwayFromZero, HalfEven, HalfUp, TowardZero,
+};
+use ryuu::Formatter;
+
+fn fixed(d: f64, decimal_places: usize, mode: RoundingMode) -> String {
+    Formatter::format_f64_fixed(d, decimal_places, mode)
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_exact_value() {
+    // 2.675 is 2.67499999999999982236431605997495353221893310546875.
+    assert_eq!(fixed(2.675, 2, HalfEven), "2.67");
+    assert_eq!(fixed(2.675, 2, HalfUp), "2.67");
+    assert_eq!(fixed(2.675, 2, HalfAwayFromZero), "2.67");
+    assert_eq!(fixed(2.675, 2, Ceiling), "2.68");
+    // 0.125 is exact, and a tie.
+    assert_eq!(fixed(0.125, 2, HalfEven), "0.12");
+    assert_eq!(fixed(0.125, 2, HalfUp), "0.13");
+    assert_eq!(fixed(-0.125, 2, HalfUp), "-0.12");
+    assert_eq!(fixed(-0.125, 2, HalfAwayFromZero), "-0.13");
+    assert_eq!(fixed(0.125, 3, Floor), "0.125");
+    assert_eq!(fixed(0.125, 3, Ceiling), "0.125");
+}
+
+#[test]
+fn test_directed() {
+    // 0.1 is 0.1000000000000000055511151231257827...
+    assert_eq!(fixed(0.1, 14, Floor), "0.10000000000000");
+    assert_eq!(fixed(0.1, 14, Ceiling), "0.10000000000001");
+    assert_eq!(fixed(-0.1, 14, Floor), "-0.10000000000001");
+    assert_eq!(fixed(-0.1, 14, Ceiling), "-0.10000000000000");
+    assert_eq!(fixed(-0.1, 14, TowardZero), "-0.10000000000000");
+    assert_eq!(fixed(-0.1, 14, AwayFromZero), "-0.10000000000001");
+    assert_eq!(fixed(0.9, 0, Floor), "0");
+    assert_eq!(fixed(0.9, 0, Ceiling), "1");
+    assert_eq!(fixed(-0.9, 0, Floor), "-1");
+    assert_eq!(fixed(-0.9, 0, Ceiling), "-0");
+    assert_eq!(fixed(9.99, 1, Ceiling), "10.0");
+    assert_eq!(fixed(0.00001, 2, Ceiling), "0.01");
+    assert_eq!(fixed(0.00001, 2, Floor), "0.00");
+}
+
+#[test]
+fn test_layout() {
+    assert_eq!(fixed(0.0, 2, Floor), "0.00");
+    assert_eq!(fixed(-0.0, 0, Ceiling), "-0");
+    assert_eq!(fixed(1234.5, 0, HalfEven), "1234");
+    assert_eq!(
+        fixed(4503599627370495.5, 14, HalfEven),
+        "4503599627370495.50000000000000"
+    );
+    assert_eq!(fixed(1e15, 1, HalfEven), "1000000000000000.0");
+    // Exponent form rounds the mantissa.
+    assert_eq!(fixed(1.5e20, 2, HalfEven), "1.50e20");
+    assert_eq!(fixed(1e23, 14, HalfEven), "1.00000000000000e23");
+    assert_eq!(fixed(1e23, 2, Floor), "9.99e22");
+    assert_eq!(fixed(1e23, 2, Ceiling), "1.00e23");
+    assert_eq!(fixed(9.5e20, 0, HalfEven), "1e21");
+    assert_eq!(fixed(5e-324, 3, Floor), "4.940e-324");
+    assert_eq!(fixed(-5e-324, 3, Floor), "-4.941e-324");
+    assert_eq!(fixed(f64::MAX, 14, HalfEven), "1.79769313486232e308");
+    assert_eq!(fixed(f64::NAN, 2, HalfEven), "NaN");
+    assert_eq!(fixed(f64::NEG_INFINITY, 2, Floor), "-inf");
+}
+
+#[test]
+fn test_f32() {
+    let fixed = |f, decimal_places, mode| Formatter::format_f32_fixed(f, decimal_places, mode);
+    // 0.1f32 is 0.100000001490116119384765625.
+    assert_eq!(fixed(0.1f32, 9, Floor).as_str(), "0.100000001");
+    assert_eq!(fixed(0.1f32, 9, Ceiling).as_str(), "0.100000002");
+    assert_eq!(fixed(0.1f32, 14, HalfEven).as_str(), "0.10000000149012");
+    assert_eq!(fixed(1e13f32, 2, HalfEven).as_str(), "1.00e13");
+    assert_eq!(
+        fixed(1e-45f32, 14, HalfEven).as_str(),
+        "1.40129846432482e-45"
+    );
+}
+
+#[test]
+fn test_bounds() {
+    // The printed floor and ceiling bracket the value.
+    let modes = [Floor, Ceiling];
+    for d in [
+        0.1,
+        -0.1,
+        2.675,
+        1.0 / 3.0,
+        -2.0 / 3.0,
+        123.456,
+        1e-5,
+        4e15 + 0.5,
+    ] {
+        for decimal_places in 0..=14 {
+            let [lo, hi] = modes.map(|mode| fixed(d, decimal_places, mode).parse::<f64>().unwrap());
+            assert!(lo <= d && d <= hi, "{d} {decimal_places}");
+        }
+    }
+}
+
+#[test]
+fn test_significant_modes() {
+    use ryuu::format::TrailingZeros::Keep;
+
+    let sig = |d, digits, mode| {
+        Formatter::format_f64_significant(d, digits, Keep, mode)
+            .as_str()
+            .to_owned()
+    };
+    assert_eq!(sig(0.1, 17, Floor), "0.10000000000000000");
+    assert_eq!(sig(0.1, 17, Ceiling), "0.10000000000000001");
+    assert_eq!(sig(-2.0 / 3.0, 3, TowardZero), "-0.666");
+    assert_eq!(sig(-2.0 / 3.0, 3, Floor), "-0.667");
+    assert_eq!(sig(123456.0, 2, AwayFromZero), "130000.0");
+    assert_eq!(sig(999.1, 3, Ceiling), "1000.0");
+    assert_eq!(sig(2.5, 1, HalfEven), "2.0");
+    assert_eq!(sig(2.5, 1, HalfUp), "3.0");
+    assert_eq!(sig(-2.5, 1, HalfUp), "-2.0");
+}
@@ -0,0 +1,121 @@
+use ryuu::format::RoundingMode::{self, Ceiling, Floor, HalfEven, HalfUp};
+use ryuu::format::{FractionDigits, BUFFER_LEN_FRACTION_DIGITS};
+use ryuu::Formatter;
+
+fn digits(d: f64, min: usize, max: usize, mode: RoundingMode) -> String {
+    Formatter::format_f64_fraction_digits(d, FractionDigits::new(min, max), mode)
+        .as_str()
+        .to_owned()
+}
+
+fn padded(d: f64, min_integer_digits: usize, min: usize, max: usize) -> String {
+    let digits = FractionDigits::new(min, max).with_min_integer_digits(min_integer_digits);
+    Formatter::format_f64_fraction_digits(d, digits, HalfEven)
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_fraction_digits() {
+    assert_eq!(digits(2.0, 1, 3, HalfEven), "2.0");
+    assert_eq!(digits(2.12345, 1, 3, HalfEven), "2.123");
+    assert_eq!(digits(2.5, 1, 3, HalfEven), "2.5");
+    assert_eq!(digits(2.0, 0, 3, HalfEven), "2");
+    assert_eq!(digits(0.5, 0, 0, HalfEven), "0");
+    assert_eq!(digits(1.5, 0, 0, HalfEven), "2");
+    assert_eq!(digits(2.0, 2, 2, HalfEven), "2.00");
+    assert_eq!(digits(9.9996, 0, 3, HalfEven), "10");
+    assert_eq!(digits(0.0, 0, 5, HalfEven), "0");
+    assert_eq!(digits(0.0, 2, 5, HalfEven), "0.00");
+    assert_eq!(digits(-0.0, 1, 5, HalfEven), "-0.0");
+    assert_eq!(digits(0.1, 0, 14, HalfEven), "0.1");
+    assert_eq!(digits(0.1, 0, 14, Ceiling), "0.10000000000001");
+    assert_eq!(digits(0.1, 0, 20, HalfEven), "0.10000000000000000555");
+}
+
+#[test]
+fn test_exact_value() {
+    // 2.675 is 2.67499999999999982236431605997495353221893310546875.
+    assert_eq!(digits(2.675, 0, 2, HalfUp), "2.67");
+    assert_eq!(digits(0.125, 0, 2, HalfEven), "0.12");
+    assert_eq!(digits(0.125, 0, 2, HalfUp), "0.13");
+    assert_eq!(digits(-0.101, 1, 2, Floor), "-0.11");
+    assert_eq!(digits(-0.101, 1, 2, Ceiling), "-0.1");
+}
+
+#[test]
+fn test_min_integer_digits() {
+    assert_eq!(padded(7.0, 3, 0, 0), "007");
+    assert_eq!(padded(-0.125, 3, 0, 2), "-000.12");
+    assert_eq!(padded(0.0, 2, 0, 2), "00");
+    assert_eq!(padded(1234.5, 3, 0, 2), "1234.5");
+    assert_eq!(padded(999.96, 3, 0, 1), "1000");
+    assert_eq!(
+        padded(-1.0, 21, 20, 20),
+        "-000000000000000000001.00000000000000000000"
+    );
+    assert_eq!(
+        padded(4503599627370496.0, 21, 1, 3),
+        "000004503599627370496.0"
+    );
+}
+
+#[test]
+fn test_positional() {
+    // Numbers are never in exponent form.
+    assert_eq!(digits(1.5e20, 1, 3, HalfEven), "150000000000000000000.0");
+    assert_eq!(digits(1.5e20, 0, 0, HalfEven), "150000000000000000000");
+    assert_eq!(padded(-1.5e20, 5, 1, 3), "-150000000000000000000.0");
+    assert_eq!(digits(1.23456e-7, 0, 3, HalfEven), "0");
+    assert_eq!(digits(1.23456e-7, 0, 20, HalfEven), "0.000000123456");
+    assert_eq!(digits(1e-7, 2, 3, HalfEven), "0.00");
+    assert_eq!(digits(1e-7, 2, 3, Ceiling), "0.001");
+    assert_eq!(digits(-5e-324, 0, 2, HalfEven), "-0");
+    assert_eq!(
+        digits(1e300, 0, 5, HalfEven),
+        "1000000000000000052504760255204420248704468581108159154915854115511802457988908195786371375080447864043704443832883878176942523235360430575644792184786706982848387200926575803737830233794788090059368953234970799945081119038967640880074652742780142494579258788820056842838115669472196386865459400540160"
+    );
+    let max = digits(-f64::MAX, 20, 20, HalfEven);
+    assert!(max.starts_with("-17976931348623157"), "{max}");
+    assert!(max.ends_with(".00000000000000000000"), "{max}");
+    assert_eq!(max.len(), BUFFER_LEN_FRACTION_DIGITS);
+}
+
+#[test]
+fn test_f32() {
+    let digits = FractionDigits::new(1, 3).with_min_integer_digits(2);
+    let format = |f| Formatter::format_f32_fraction_digits(f, digits, HalfEven);
+    assert_eq!(format(2.0).as_str(), "02.0");
+    // 0.1f32 is 0.100000001490116119384765625.
+    assert_eq!(format(0.1).as_str(), "00.1");
+    assert_eq!(format(2.12345).as_str(), "02.123");
+    // 1.5e14f32 is 150000004759552.
+    assert_eq!(format(1.5e14).as_str(), "150000004759552.0");
+    assert_eq!(format(1e-7).as_str(), "00.0");
+}
+
+#[test]
+fn test_nonfinite() {
+    let digits = FractionDigits::new(2, 4).with_min_integer_digits(3);
+    let format = |d| Formatter::format_f64_fraction_digits(d, digits, HalfEven);
+    assert_eq!(format(f64::NAN).as_str(), "NaN");
+    assert_eq!(format(f64::NEG_INFINITY).as_str(), "-inf");
+}
+
+#[test]
+#[should_panic = "`min_fraction_digits` must be at most `max_fraction_digits`"]
+fn test_min_above_max() {
+    let _ = FractionDigits::new(3, 2);
+}
+
+#[test]
+#[should_panic = "`max_fraction_digits` must be at most 20"]
+fn test_too_many_fraction_digits() {
+    let _ = FractionDigits::new(0, 21);
+}
+
+#[test]
+#[should_panic = "`min_integer_digits` must be between 1 and 21"]
+fn test_too_many_integer_digits() {
+    let _ = FractionDigits::new(0, 2).with_min_integer_digits(22);
+}
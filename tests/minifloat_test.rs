@@ -0,0 +1,45 @@
+#![allow(clippy::unreadable_literal)]
+
+use ryuu::minifloat::Layout;
+
+fn pretty(bits: u16, layout: Layout) -> String {
+    ryuu::Formatter::format_minifloat_bits(bits, layout)
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_e4m3() {
+    assert_eq!(pretty(0x00, Layout::E4M3), "0.0");
+    assert_eq!(pretty(0x80, Layout::E4M3), "-0.0");
+    assert_eq!(pretty(0x38, Layout::E4M3), "1.0");
+    assert_eq!(pretty(0x3a, Layout::E4M3), "1.2");
+    assert_eq!(pretty(0x1d, Layout::E4M3), "0.1");
+    assert_eq!(pretty(0x7e, Layout::E4M3), "450.0");
+    assert_eq!(pretty(0x01, Layout::E4M3), "0.002");
+    assert_eq!(pretty(0x7f, Layout::E4M3), "NaN");
+    assert_eq!(pretty(0xff, Layout::E4M3), "NaN");
+    assert_eq!(pretty(0x78, Layout::E4M3), "260.0");
+}
+
+#[test]
+fn test_e5m2() {
+    assert_eq!(pretty(0x3c, Layout::E5M2), "1.0");
+    assert_eq!(pretty(0x3e, Layout::E5M2), "1.5");
+    assert_eq!(pretty(0x7b, Layout::E5M2), "60000.0");
+    assert_eq!(pretty(0x01, Layout::E5M2), "0.00002");
+    assert_eq!(pretty(0x7c, Layout::E5M2), "inf");
+    assert_eq!(pretty(0xfc, Layout::E5M2), "-inf");
+    assert_eq!(pretty(0x7d, Layout::E5M2), "NaN");
+}
+
+#[test]
+fn test_fp6_and_fp4() {
+    assert_eq!(pretty(0x1f, Layout::E2M3), "7.5");
+    assert_eq!(pretty(0x3f, Layout::E2M3), "-7.5");
+    assert_eq!(pretty(0x1f, Layout::E3M2), "28.0");
+    assert_eq!(pretty(0x01, Layout::E2M1), "0.5");
+    assert_eq!(pretty(0x06, Layout::E2M1), "4.0");
+    assert_eq!(pretty(0x07, Layout::E2M1), "6.0");
+    assert_eq!(pretty(0x0f, Layout::E2M1), "-6.0");
+}
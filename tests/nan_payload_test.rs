@@ -0,0 +1,61 @@
+use ryuu::Formatter;
+
+fn nan64(bits: u64) -> String {
+    Formatter::format_f64_nan_payload(f64::from_bits(bits))
+        .as_str()
+        .to_owned()
+}
+
+fn nan32(bits: u32) -> String {
+    Formatter::format_f32_nan_payload(f32::from_bits(bits))
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_f64() {
+    assert_eq!(nan64(0x7ff8000000000000), "nan");
+    assert_eq!(nan64(0xfff8000000000000), "-nan");
+    assert_eq!(nan64(0x7ff8000000000001), "nan(0x8000000000001)");
+    assert_eq!(nan64(0x7ffc000000000000), "nan(0xc000000000000)");
+    assert_eq!(nan64(0x7fffffffffffffff), "nan(0xfffffffffffff)");
+    assert_eq!(nan64(0x7ff0000000000001), "snan(0x1)");
+    assert_eq!(nan64(0xfff4000000000000), "-snan(0x4000000000000)");
+    assert_eq!(nan64(0xfff7ffffffffffff), "-snan(0x7ffffffffffff)");
+}
+
+#[test]
+fn test_f32() {
+    assert_eq!(nan32(0x7fc00000), "nan");
+    assert_eq!(nan32(0xffc00000), "-nan");
+    assert_eq!(nan32(0x7fc00001), "nan(0x400001)");
+    assert_eq!(nan32(0xff800abc), "-snan(0xabc)");
+    assert_eq!(nan32(0x7fbfffff), "snan(0x3fffff)");
+}
+
+#[test]
+fn test_unchanged() {
+    // Other values, and NaN in the default mode, print as before.
+    for d in [
+        0.0,
+        -0.0,
+        1.5,
+        -1e300,
+        5e-324,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ] {
+        assert_eq!(
+            Formatter::format_f64_nan_payload(d).as_str(),
+            Formatter::format_f64(d).as_str()
+        );
+    }
+    assert_eq!(
+        Formatter::format_f32_nan_payload(f32::NEG_INFINITY).as_str(),
+        "-inf"
+    );
+    assert_eq!(
+        Formatter::format_f64(f64::from_bits(0xfff0000000000001)).as_str(),
+        "NaN"
+    );
+}
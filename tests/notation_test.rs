@@ -0,0 +1,79 @@
+use ryuu::format::{Notation, BUFFER_LEN_F128, NOTATION_EXTRA_LEN};
+use ryuu::Formatter;
+
+fn typographic(d: f64) -> String {
+    let mut buffer = [0u8; 64];
+    let formatted = Formatter::format_f64(d);
+    let text = formatted
+        .write_notation(Notation::Typographic, &mut buffer)
+        .unwrap();
+    assert_eq!(text.len(), formatted.notation_len(Notation::Typographic));
+    text.to_owned()
+}
+
+fn latex(d: f64) -> String {
+    let mut buffer = [0u8; 64];
+    let formatted = Formatter::format_f64(d);
+    let text = formatted
+        .write_notation(Notation::Latex, &mut buffer)
+        .unwrap();
+    assert_eq!(text.len(), formatted.notation_len(Notation::Latex));
+    text.to_owned()
+}
+
+#[test]
+fn test_typographic() {
+    assert_eq!(
+        typographic(-1.23e-7),
+        "\u{2212}1.23 \u{d7} 10\u{207b}\u{2077}"
+    );
+    assert_eq!(typographic(1e20), "1 \u{d7} 10\u{b2}\u{2070}");
+    assert_eq!(
+        typographic(1.5e-308),
+        "1.5 \u{d7} 10\u{207b}\u{b3}\u{2070}\u{2078}"
+    );
+    assert_eq!(typographic(6.02e23), "6.02 \u{d7} 10\u{b2}\u{b3}");
+    assert_eq!(typographic(-0.5), "\u{2212}0.5");
+    assert_eq!(typographic(42.0), "42.0");
+    assert_eq!(typographic(f64::INFINITY), "\u{221e}");
+    assert_eq!(typographic(f64::NEG_INFINITY), "\u{2212}\u{221e}");
+    assert_eq!(typographic(f64::NAN), "NaN");
+}
+
+#[test]
+fn test_latex() {
+    assert_eq!(latex(-1.23e-7), "-1.23 \\times 10^{-7}");
+    assert_eq!(latex(1e20), "1 \\times 10^{20}");
+    assert_eq!(latex(-0.5), "-0.5");
+    assert_eq!(latex(f64::INFINITY), "\\infty");
+    assert_eq!(latex(f64::NEG_INFINITY), "-\\infty");
+    assert_eq!(latex(f64::NAN), "\\mathrm{NaN}");
+}
+
+#[test]
+fn test_buffer_too_short() {
+    let formatted = Formatter::format_f64(-1.23e-7);
+    let len = formatted.notation_len(Notation::Typographic);
+    assert_eq!(len, 19);
+    let mut buffer = [0u8; 19];
+    assert_eq!(
+        formatted.write_notation(Notation::Typographic, &mut buffer[..len - 1]),
+        None
+    );
+    assert!(formatted
+        .write_notation(Notation::Typographic, &mut buffer)
+        .is_some());
+}
+
+#[test]
+fn test_max_len() {
+    // A negative number with a four-digit negative exponent.
+    let bits = (1u128 << 127) | 0x0001_8000_0000_0000_0000_0000_0000_0000;
+    let formatted = Formatter::format_f128_bits(bits);
+    let ascii_len = formatted.as_str().len();
+    let mut buffer = [0u8; BUFFER_LEN_F128 + NOTATION_EXTRA_LEN];
+    let text = formatted
+        .write_notation(Notation::Typographic, &mut buffer)
+        .unwrap();
+    assert!(text.len() <= ascii_len + NOTATION_EXTRA_LEN);
+}
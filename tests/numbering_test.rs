@@ -0,0 +1,92 @@
+use ryuu::format::NumberingSystem;
+use ryuu::Formatter;
+
+fn numbering(d: f64, system: NumberingSystem) -> String {
+    let mut buffer = [0u8; 4 * 32];
+    let formatted = Formatter::format_f64(d);
+    let text = formatted.write_numbering(system, &mut buffer).unwrap();
+    assert_eq!(text.len(), formatted.numbering_len(system));
+    text.to_owned()
+}
+
+#[test]
+fn test_systems() {
+    assert_eq!(numbering(123.45, NumberingSystem::ASCII), "123.45");
+    assert_eq!(
+        numbering(123.45, NumberingSystem::ARABIC_INDIC),
+        "\u{661}\u{662}\u{663}\u{66b}\u{664}\u{665}"
+    );
+    assert_eq!(
+        numbering(-0.5, NumberingSystem::EXTENDED_ARABIC_INDIC),
+        "\u{2212}\u{6f0}\u{66b}\u{6f5}"
+    );
+    assert_eq!(
+        numbering(-1e-7, NumberingSystem::DEVANAGARI),
+        "-\u{967}e-\u{96d}"
+    );
+    assert_eq!(
+        numbering(2.5e21, NumberingSystem::BENGALI),
+        "\u{9e8}.\u{9eb}e\u{9e8}\u{9e7}"
+    );
+    assert_eq!(
+        numbering(90.0, NumberingSystem::THAI),
+        "\u{e59}\u{e50}.\u{e50}"
+    );
+    assert_eq!(
+        numbering(-123.45, NumberingSystem::FULLWIDTH),
+        "\u{ff0d}\u{ff11}\u{ff12}\u{ff13}\u{ff0e}\u{ff14}\u{ff15}"
+    );
+}
+
+#[test]
+fn test_nonfinite() {
+    assert_eq!(numbering(f64::NAN, NumberingSystem::FULLWIDTH), "NaN");
+    assert_eq!(numbering(f64::INFINITY, NumberingSystem::THAI), "inf");
+    assert_eq!(
+        numbering(f64::NEG_INFINITY, NumberingSystem::FULLWIDTH),
+        "\u{ff0d}inf"
+    );
+    // The digits of a NaN payload are kept as they are.
+    let formatted = Formatter::format_f64_nan_payload(f64::from_bits(0x7ff8000000000012));
+    let mut buffer = [0u8; 4 * 32];
+    assert_eq!(
+        formatted.write_numbering(NumberingSystem::ARABIC_INDIC, &mut buffer),
+        Some(formatted.as_str())
+    );
+}
+
+#[test]
+fn test_custom() {
+    // The mathematical bold digits, which take four bytes each.
+    let system = NumberingSystem::new('\u{1d7ce}', ',', '\u{2212}');
+    assert_eq!(system.digit(7), '\u{1d7d5}');
+    assert_eq!(system.digit_value('\u{1d7d7}'), Some(9));
+    assert_eq!(system.digit_value('9'), None);
+    assert_eq!(numbering(-1.5, system), "\u{2212}\u{1d7cf},\u{1d7d3}");
+}
+
+#[test]
+#[should_panic = "the decimal separator and minus sign must be distinct symbols"]
+fn test_digit_as_separator() {
+    let _ = NumberingSystem::new('\u{660}', '\u{661}', '-');
+}
+
+#[test]
+#[should_panic = "the digits must all be characters"]
+fn test_surrogate_digits() {
+    let _ = NumberingSystem::new('\u{d7ff}', '.', '-');
+}
+
+#[test]
+fn test_buffer_too_short() {
+    let formatted = Formatter::format_f64(123.45);
+    let system = NumberingSystem::FULLWIDTH;
+    let len = formatted.numbering_len(system);
+    assert_eq!(len, 18);
+    let mut buffer = [0u8; 18];
+    assert_eq!(
+        formatted.write_numbering(system, &mut buffer[..len - 1]),
+        None
+    );
+    assert!(formatted.write_numbering(system, &mut buffer).is_some());
+}
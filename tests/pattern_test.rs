@@ -0,0 +1,149 @@
+use ryuu::format::RoundingMode;
+use ryuu::pattern::{NumberPattern, PatternError};
+
+fn pattern(pattern: &str, d: f64) -> String {
+    let pattern = NumberPattern::parse(pattern).unwrap();
+    let mut buffer = [0u8; 512];
+    let text = pattern.write_to(d, &mut buffer).unwrap();
+    assert_eq!(text.len(), pattern.str_len(d));
+    text.to_owned()
+}
+
+#[test]
+fn test_fixed() {
+    assert_eq!(pattern("0.00", 1.0), "1.00");
+    // The exact value is rounded: 12.345 is 12.3450000000000006394884621840…
+    // and 0.35 is 0.34999999999999997779553950749686919152736663818359375.
+    assert_eq!(pattern("0.00", 12.345), "12.35");
+    assert_eq!(pattern("0.00", 0.125), "0.12");
+    assert_eq!(pattern("0.0", 0.35), "0.3");
+    assert_eq!(pattern("0.##", 0.5), "0.5");
+    assert_eq!(pattern("0.##", 2.0), "2");
+    assert_eq!(pattern("#.##", 0.25), ".25");
+    assert_eq!(pattern("#.##", 0.0), "0");
+    assert_eq!(pattern("000", 7.0), "007");
+    assert_eq!(pattern("0.", 3.0), "3.");
+    assert_eq!(pattern("0.00#", 1.0 / 3.0), "0.333");
+    assert_eq!(pattern("0.00", -0.001), "0.00");
+    assert_eq!(pattern("0", 1e21), "1000000000000000000000");
+    assert_eq!(
+        pattern("0.##############################", 0.1),
+        "0.100000000000000005551115123126"
+    );
+    assert!(pattern("0.0", 2.5e300).starts_with("25000000000000001312619006380110506"));
+}
+
+#[test]
+fn test_grouping() {
+    assert_eq!(pattern("#,##0.00", 1234567.891), "1,234,567.89");
+    assert_eq!(pattern("#,##0", 999.0), "999");
+    assert_eq!(pattern("#,##,##0", 123456789.0), "12,34,56,789");
+    assert_eq!(pattern("#,####", 123456789.0), "1,2345,6789");
+    assert_eq!(pattern("0,000,000", 42.0), "0,000,042");
+}
+
+#[test]
+fn test_affixes() {
+    assert_eq!(pattern("#,##0.00;(#,##0.00)", -1234.5), "(1,234.50)");
+    assert_eq!(pattern("#,##0.00;(#,##0.00)", -0.001), "0.00");
+    assert_eq!(pattern("\"$\"#,##0.00", -5.0), "-$5.00");
+    assert_eq!(pattern("'#'0 'items'''", 5.0), "#5 items'");
+    assert_eq!(pattern("\\#0", 5.0), "#5");
+    assert_eq!(
+        pattern("0.0 \u{20ac};0.0- \u{20ac}", -2.25),
+        "2.2- \u{20ac}"
+    );
+    assert_eq!(pattern("0.#%", 0.1234), "12.3%");
+    assert_eq!(pattern("0.000\u{2030}", 0.0123456), "12.346\u{2030}");
+    assert_eq!(pattern("'%'0", 0.5), "%0");
+}
+
+#[test]
+fn test_scientific() {
+    assert_eq!(pattern("0.00E+00", -12345.0), "-1.23E+04");
+    assert_eq!(pattern("0.00E+00", 5e-324), "4.94E-324");
+    assert_eq!(pattern("0.00E0", 9.999), "1.00E1");
+    assert_eq!(pattern("0.00E00", 0.00125), "1.25E-03");
+    assert_eq!(pattern("0.00E00", 0.0), "0.00E00");
+    assert_eq!(pattern("0.###E0", 1.0), "1E0");
+    assert_eq!(pattern("00.###E0", 0.00012345), "12.345E-5");
+    assert_eq!(pattern("0.0E0%", 0.5), "5.0E1%");
+    // The exponent is a multiple of three, and the mantissa has as many
+    // significant digits as the minimum integer digits plus the maximum
+    // fraction digits.
+    assert_eq!(pattern("##0.##E0", 12345.0), "12.3E3");
+    assert_eq!(pattern("##0.##E0", 0.00012345), "123E-6");
+}
+
+#[test]
+fn test_rounding_mode() {
+    let half_away = NumberPattern::parse("0")
+        .unwrap()
+        .with_rounding_mode(RoundingMode::HalfAwayFromZero);
+    let mut buffer = [0u8; 8];
+    assert_eq!(half_away.write_to(2.5, &mut buffer), Some("3"));
+    assert_eq!(half_away.write_to(-2.5, &mut buffer), Some("-3"));
+    let half_up = NumberPattern::parse("0")
+        .unwrap()
+        .with_rounding_mode(RoundingMode::HalfUp);
+    assert_eq!(half_up.write_to(2.5, &mut buffer), Some("3"));
+    assert_eq!(half_up.write_to(-2.5, &mut buffer), Some("-2"));
+    assert_eq!(pattern("0", 2.5), "2");
+    let excel = NumberPattern::parse("0.00")
+        .unwrap()
+        .with_rounding_mode(RoundingMode::HalfAwayFromZero);
+    assert_eq!(excel.write_to(12.345, &mut buffer), Some("12.35"));
+
+    let floor = NumberPattern::parse("0.00E0")
+        .unwrap()
+        .with_rounding_mode(RoundingMode::Floor);
+    assert_eq!(floor.write_to(-1.001, &mut buffer), Some("-1.01E0"));
+    // Directed rounding never crosses the exact value.
+    let floor = NumberPattern::parse("0.0000000000000000")
+        .unwrap()
+        .with_rounding_mode(RoundingMode::Floor);
+    let mut buffer = [0u8; 32];
+    assert_eq!(
+        floor.write_to(0.35, &mut buffer),
+        Some("0.3499999999999999")
+    );
+}
+
+#[test]
+fn test_nonfinite() {
+    assert_eq!(pattern("0.00", f64::NAN), "NaN");
+    assert_eq!(pattern("0.00;(0.00)", f64::NEG_INFINITY), "(inf)");
+    assert_eq!(pattern("0.00%", f64::INFINITY), "inf%");
+}
+
+#[test]
+fn test_buffer_too_short() {
+    let pattern = NumberPattern::parse("#,##0.00").unwrap();
+    let mut buffer = [0u8; 8];
+    assert_eq!(pattern.write_to(1234.5, &mut buffer), Some("1,234.50"));
+    assert_eq!(pattern.write_to(12345.0, &mut buffer), None);
+}
+
+#[test]
+fn test_errors() {
+    for (text, error) in [
+        ("", PatternError::MissingDigits),
+        ("abc", PatternError::MissingDigits),
+        ("0#", PatternError::MisplacedDigit),
+        ("#.#0", PatternError::MisplacedDigit),
+        ("0 0", PatternError::MisplacedDigit),
+        ("#,", PatternError::MisplacedSymbol),
+        ("0.0.0", PatternError::MisplacedSymbol),
+        ("0.0,0", PatternError::MisplacedSymbol),
+        ("0E", PatternError::MisplacedSymbol),
+        ("'abc", PatternError::UnterminatedQuote),
+        ("0;0;0", PatternError::TooManySubpatterns),
+        (
+            "0.0000000000000000000000000000000",
+            PatternError::TooManyDigits,
+        ),
+        ("0.00000000000000000E0", PatternError::TooManyDigits),
+    ] {
+        assert_eq!(NumberPattern::parse(text), Err(error), "{text}");
+    }
+}
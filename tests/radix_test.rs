@@ -0,0 +1,84 @@
+#![allow(clippy::float_cmp)]
+
+use ryuu::{raw, Formatter};
+
+fn radix(d: f64, radix: u32) -> String {
+    Formatter::format_f64_radix(d, radix).as_str().to_owned()
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(radix(0.0, 2), "0");
+    assert_eq!(radix(-0.0, 36), "-0");
+    assert_eq!(radix(255.0, 16), "ff");
+    assert_eq!(radix(-0.5, 2), "-0.1");
+    assert_eq!(radix(0.1, 3), "0.0022002200220022002200220022002201");
+    assert_eq!(radix(1e21, 36), "5v1j4f4ds7a000");
+    assert_eq!(radix(35.0, 36), "z");
+    assert_eq!(radix(1.0 / 3.0, 3), "0.1");
+    assert_eq!(radix(0.1, 16), "0.1999999999999a");
+    assert_eq!(Formatter::format_f32_radix(0.1, 16).as_str(), "0.199999a");
+}
+
+#[test]
+fn test_nonfinite() {
+    assert_eq!(radix(f64::NAN, 16), "NaN");
+    assert_eq!(radix(f64::INFINITY, 2), "inf");
+    assert_eq!(radix(f64::NEG_INFINITY, 36), "-inf");
+}
+
+#[test]
+fn test_decimal_places() {
+    // Integers have no decimal point, which the decimal places add.
+    let mut formatted = Formatter::format_f64_radix(255.0, 16);
+    assert_eq!(formatted.as_str_fixed_dp::<2>(), "ff");
+    let mut buffer = [0u8; 8];
+    let written = formatted.copy_to_bytes::<2>(&mut buffer).unwrap();
+    assert_eq!(&buffer[..written], b"ff.00");
+    let written = formatted.copy_to_bytes::<0>(&mut buffer).unwrap();
+    assert_eq!(&buffer[..written], b"ff");
+    assert_eq!(formatted.as_str_adjusting_dp::<0>(), "ff");
+    assert_eq!(formatted.as_str_adjusting_dp::<2>(), "ff.00");
+
+    let mut formatted = Formatter::format_f64_radix(-0.0, 2);
+    assert_eq!(formatted.as_str_adjusting_dp::<1>(), "-0.0");
+    let mut formatted = Formatter::format_f64_radix(-0.5, 2);
+    assert_eq!(formatted.as_str_adjusting_dp::<3>(), "-0.100");
+}
+
+#[test]
+fn test_radix_10() {
+    // Radix 10 is the usual shortest representation, exponent and all.
+    for d in [0.1, 1e21, 1.5e-7, 123.456, f64::MAX, 5e-324] {
+        assert_eq!(radix(d, 10), Formatter::format_f64(d).as_str());
+    }
+    let f = 1.0e-7f32;
+    assert_eq!(
+        Formatter::format_f32_radix(f, 10).as_str(),
+        Formatter::format_f32(f).as_str()
+    );
+}
+
+#[test]
+fn test_power_of_two() {
+    // In a power-of-two radix, the shortest digits are the exact value.
+    assert_eq!(radix(5e-324, 2).len(), 2 + 1074);
+    assert!(radix(5e-324, 2).ends_with("0001"));
+    assert_eq!(radix(f64::MAX, 2), "1".repeat(53) + &"0".repeat(971));
+    assert_eq!(radix(f64::MAX, 32).len(), 205);
+}
+
+#[test]
+fn test_raw() {
+    let mut buffer = [0u8; 64];
+    let len = unsafe { raw::format64_radix(-3.75, 4, buffer.as_mut_ptr()) };
+    assert_eq!(&buffer[..len], b"-3.3");
+    let len = unsafe { raw::format32_radix(1.0e9, 36, buffer.as_mut_ptr()) };
+    assert_eq!(&buffer[..len], b"gjdgy0"); // 1e9 + 8, within half an ulp.
+}
+
+#[test]
+#[should_panic = "`radix` must be from 2 to 36"]
+fn test_invalid_radix() {
+    let _ = Formatter::format_f64_radix(1.0, 1);
+}
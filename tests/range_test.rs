@@ -0,0 +1,101 @@
+#![allow(clippy::float_cmp)]
+
+use ryuu::Formatter;
+
+fn shortest(lo: f64, hi: f64, inclusive: bool) -> Option<String> {
+    Formatter::shortest_in_range_f64(lo, hi, inclusive).map(|f| f.as_str().to_owned())
+}
+
+fn next_up(d: f64) -> f64 {
+    f64::from_bits(d.to_bits() + 1)
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(shortest(0.12345, 0.12399, true).unwrap(), "0.1237");
+    assert_eq!(shortest(1.0, 2.0, true).unwrap(), "2.0");
+    assert_eq!(shortest(1.0, 2.0, false).unwrap(), "1.5");
+    assert_eq!(shortest(1.0, 9.0, false).unwrap(), "5.0");
+    assert_eq!(shortest(17.0, 42.0, true).unwrap(), "30.0");
+    assert_eq!(shortest(95.0, 1050.0, true).unwrap(), "1000.0");
+    assert_eq!(shortest(1e-300, 1e300, true).unwrap(), "1e300");
+    assert_eq!(shortest(1.5e20, 3.7e20, true).unwrap(), "2e20");
+    assert_eq!(shortest(-0.3, -0.25, false).unwrap(), "-0.28");
+}
+
+#[test]
+fn test_endpoints() {
+    // Exact endpoints are only included in the closed interval.
+    assert_eq!(shortest(0.5, 0.5, true).unwrap(), "0.5");
+    assert_eq!(shortest(0.5, 0.5, false), None);
+    assert_eq!(shortest(0.5, next_up(0.5), true).unwrap(), "0.5");
+    assert_eq!(
+        shortest(0.5, next_up(0.5), false).unwrap(),
+        "0.5000000000000001"
+    );
+    assert_eq!(shortest(0.25, 0.5, true).unwrap(), "0.4");
+    assert_eq!(shortest(0.45, 0.5, true).unwrap(), "0.5");
+    assert_eq!(shortest(0.45, 0.5, false).unwrap(), "0.48");
+    // The exact values of 0.1 and 0.3 are slightly above and below.
+    assert_eq!(shortest(0.1, 0.2, true).unwrap(), "0.2");
+    assert_eq!(shortest(0.1, 0.3, true).unwrap(), "0.2");
+    assert_eq!(shortest(0.3, 0.35, false).unwrap(), "0.3");
+    // Too many digits for the exact value of a single f64.
+    assert_eq!(shortest(0.1, 0.1, true), None);
+    assert_eq!(shortest(5e-324, 5e-324, true), None);
+    assert_eq!(shortest(5e-324, 1e-323, false).unwrap(), "7e-324");
+}
+
+#[test]
+fn test_zero() {
+    assert_eq!(shortest(-1.0, 1.0, false).unwrap(), "0.0");
+    assert_eq!(shortest(0.0, 1.0, true).unwrap(), "0.0");
+    assert_eq!(shortest(-0.0, 0.0, true).unwrap(), "0.0");
+    assert_eq!(shortest(0.0, 0.0, false), None);
+    assert_eq!(shortest(0.0, 1.0, false).unwrap(), "0.5");
+    assert_eq!(shortest(-1.0, -0.0, false).unwrap(), "-0.5");
+    assert_eq!(shortest(0.0, 5e-324, false).unwrap(), "2e-324");
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(shortest(2.0, 1.0, true), None);
+    assert_eq!(shortest(f64::NAN, 1.0, true), None);
+    assert_eq!(shortest(0.0, f64::INFINITY, true), None);
+    assert_eq!(shortest(f64::NEG_INFINITY, 0.0, true), None);
+}
+
+#[test]
+fn test_adjacent() {
+    // Between two adjacent f64s there is always a decimal with at most 17
+    // digits, and it parses to one of them.
+    let n = if cfg!(miri) { 100 } else { 10000 };
+    for _ in 0..n {
+        let lo = f64::from_bits(rand::random::<u64>() & 0x7fef_ffff_ffff_ffff);
+        let hi = next_up(lo);
+        for inclusive in [false, true] {
+            let formatted = shortest(lo, hi, inclusive).unwrap();
+            let parsed = formatted.parse::<f64>().unwrap();
+            assert!(parsed == lo || parsed == hi, "{lo:e}");
+        }
+    }
+}
+
+#[test]
+fn test_round_trip_interval() {
+    // The shortest representation of an f64 is in the interval of values
+    // that parse to it.
+    for d in [
+        0.1f64,
+        0.3,
+        1.0 / 3.0,
+        123.456,
+        1e22,
+        5e-324,
+        1.7976931348623155e308,
+    ] {
+        let prev = f64::from_bits(d.to_bits() - 1);
+        let formatted = shortest(prev, next_up(d), false).unwrap();
+        assert!(formatted.len() <= Formatter::format_f64(d).len(), "{d:e}");
+    }
+}
@@ -0,0 +1,104 @@
+use ryuu::format::RoundingMode;
+use ryuu::Formatter;
+
+fn dp(d: f64, decimal_places: usize, mode: RoundingMode) -> String {
+    Formatter::format_f64_rounding_dp(d, decimal_places, mode)
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_ties() {
+    use RoundingMode::{HalfAwayFromZero, HalfEven, HalfUp};
+
+    // The shortest digits are "2.675", although the f64 is slightly below.
+    assert_eq!(dp(2.675, 2, HalfEven), "2.68");
+    assert_eq!(dp(2.675, 2, HalfUp), "2.68");
+    assert_eq!(dp(2.675, 2, HalfAwayFromZero), "2.68");
+    assert_eq!(dp(-2.675, 2, HalfEven), "-2.68");
+    assert_eq!(dp(-2.675, 2, HalfUp), "-2.67");
+    assert_eq!(dp(-2.675, 2, HalfAwayFromZero), "-2.68");
+
+    assert_eq!(dp(2.665, 2, HalfEven), "2.66");
+    assert_eq!(dp(2.665, 2, HalfUp), "2.67");
+    assert_eq!(dp(-2.665, 2, HalfUp), "-2.66");
+
+    assert_eq!(dp(0.5, 0, HalfEven), "0");
+    assert_eq!(dp(1.5, 0, HalfEven), "2");
+    assert_eq!(dp(-0.5, 0, HalfUp), "-0");
+    assert_eq!(dp(-0.5, 0, HalfAwayFromZero), "-1");
+
+    // Not a tie: only the first dropped digit is a 5.
+    assert_eq!(dp(2.6651, 2, HalfEven), "2.67");
+    assert_eq!(dp(-2.6651, 2, HalfUp), "-2.67");
+}
+
+#[test]
+fn test_carry() {
+    let mode = RoundingMode::HalfUp;
+    assert_eq!(dp(9.995, 2, mode), "10.00");
+    assert_eq!(dp(-9.995, 2, RoundingMode::HalfAwayFromZero), "-10.00");
+    assert_eq!(dp(99.95, 1, mode), "100.0");
+    assert_eq!(dp(999.5, 0, mode), "1000");
+    assert_eq!(dp(0.995, 2, mode), "1.00");
+    assert_eq!(dp(0.0995, 2, mode), "0.10");
+    assert_eq!(dp(0.00005, 4, mode), "0.0001");
+    assert_eq!(dp(0.00004, 4, mode), "0.0000");
+    assert_eq!(dp(0.00004, 0, mode), "0");
+}
+
+#[test]
+fn test_padding() {
+    let mode = RoundingMode::HalfEven;
+    assert_eq!(dp(3.1, 3, mode), "3.100");
+    assert_eq!(dp(3.0, 0, mode), "3");
+    assert_eq!(dp(0.0, 2, mode), "0.00");
+    assert_eq!(dp(-0.0, 2, mode), "-0.00");
+    assert_eq!(dp(1234000.0, 1, mode), "1234000.0");
+    assert_eq!(dp(1e15, 14, mode), "1000000000000000.00000000000000");
+    assert_eq!(
+        dp(-4503599627370495.5, 14, mode),
+        "-4503599627370495.50000000000000"
+    );
+    assert_eq!(dp(0.0001234, 14, mode), "0.00012340000000");
+}
+
+#[test]
+fn test_exponent_form() {
+    let mode = RoundingMode::HalfUp;
+    assert_eq!(dp(1.5e20, 2, mode), "1.50e20");
+    assert_eq!(dp(1.2345e20, 2, mode), "1.23e20");
+    assert_eq!(dp(1.2355e20, 2, mode), "1.24e20");
+    assert_eq!(dp(9.995e20, 2, mode), "1.00e21");
+    assert_eq!(dp(9.5e20, 0, mode), "1e21");
+    assert_eq!(dp(9.995e-10, 2, mode), "1.00e-9");
+    assert_eq!(dp(1e-7, 3, mode), "1.000e-7");
+    assert_eq!(dp(-5e-324, 14, mode), "-5.00000000000000e-324");
+    assert_eq!(dp(f64::MAX, 1, mode), "1.8e308");
+}
+
+#[test]
+fn test_nonfinite() {
+    let mode = RoundingMode::HalfEven;
+    assert_eq!(dp(f64::NAN, 2, mode), "NaN");
+    assert_eq!(dp(f64::INFINITY, 2, mode), "inf");
+    assert_eq!(dp(f64::NEG_INFINITY, 0, mode), "-inf");
+}
+
+#[test]
+fn test_f32() {
+    let dp = |f, decimal_places| {
+        Formatter::format_f32_rounding_dp(f, decimal_places, RoundingMode::HalfUp)
+    };
+    assert_eq!(dp(2.675f32, 2).as_str(), "2.68");
+    assert_eq!(dp(9.995f32, 2).as_str(), "10.00");
+    assert_eq!(dp(1e13f32, 1).as_str(), "1.0e13");
+    assert_eq!(dp(0.000001f32, 2).as_str(), "0.00");
+    assert_eq!(dp(1e-7f32, 2).as_str(), "1.00e-7");
+}
+
+#[test]
+#[should_panic = "`decimal_places` must be at most 14"]
+fn test_too_many_decimal_places() {
+    dp(1.0, 15, RoundingMode::HalfEven);
+}
@@ -0,0 +1,171 @@
+use ryuu::format::{RoundingMode, BUFFER_LEN_COLUMN, MAX_SHARED_VALUES};
+use ryuu::Formatter;
+
+fn dp(values: &[f64]) -> usize {
+    Formatter::shared_decimal_places_f64(values, RoundingMode::HalfEven)
+}
+
+fn labels(values: &[f64], mode: RoundingMode) -> Vec<String> {
+    Formatter::format_shared_dp_f64(values, mode)
+        .map(|formatted| formatted.as_str().to_owned())
+        .collect()
+}
+
+#[test]
+fn test_axis() {
+    let mode = RoundingMode::HalfEven;
+    assert_eq!(labels(&[0.1, 0.15, 0.2], mode), ["0.10", "0.15", "0.20"]);
+    assert_eq!(
+        labels(&[0.0, 0.5, 1.0, 1.5], mode),
+        ["0.0", "0.5", "1.0", "1.5"]
+    );
+    assert_eq!(labels(&[0.0, 20.0, 40.0], mode), ["0", "20", "40"]);
+    assert_eq!(
+        labels(&[-1.0, -0.8, -0.75], mode),
+        ["-1.00", "-0.80", "-0.75"]
+    );
+
+    // The sums of steps of 0.1 are not exactly 0.3 and 0.6, but round to them.
+    let steps = [0.0, 0.1, 0.1 + 0.1, 0.1 + 0.1 + 0.1, 0.6, 0.1 * 6.0];
+    assert_eq!(dp(&steps[..5]), 1);
+    assert_eq!(
+        labels(&steps[..5], mode),
+        ["0.0", "0.1", "0.2", "0.3", "0.6"]
+    );
+    // But 0.6 and 0.6000000000000001 are different values.
+    assert_eq!(dp(&steps), 16);
+}
+
+#[test]
+fn test_minimal() {
+    assert_eq!(dp(&[]), 0);
+    assert_eq!(dp(&[3.25]), 0);
+    assert_eq!(dp(&[1.04, 1.06]), 1);
+    assert_eq!(dp(&[1.04, 1.05]), 2);
+    assert_eq!(dp(&[0.001, -0.001]), 3);
+    assert_eq!(dp(&[1.0, 1.0, 1.0]), 0);
+    assert_eq!(dp(&[0.0, -0.0]), 0);
+
+    // One decimal place tells 0.149 and 0.15 apart, but two do not.
+    assert_eq!(dp(&[0.149, 0.15]), 1);
+    assert_eq!(
+        labels(&[0.149, 0.15], RoundingMode::HalfEven),
+        ["0.1", "0.2"]
+    );
+    assert_eq!(dp(&[0.149, 0.15, 0.35]), 1);
+    assert_eq!(dp(&[0.149, 0.15, 0.25]), 3);
+}
+
+#[test]
+fn test_mode() {
+    let values = [2.5, 3.5];
+    assert_eq!(
+        Formatter::shared_decimal_places_f64(&values, RoundingMode::HalfUp),
+        0
+    );
+    assert_eq!(labels(&values, RoundingMode::HalfUp), ["3", "4"]);
+    assert_eq!(
+        Formatter::shared_decimal_places_f64(&values, RoundingMode::Floor),
+        0
+    );
+    let values = [3.5, 4.5];
+    assert_eq!(
+        Formatter::shared_decimal_places_f64(&values, RoundingMode::HalfEven),
+        1
+    );
+    assert_eq!(labels(&values, RoundingMode::HalfEven), ["3.5", "4.5"]);
+}
+
+#[test]
+fn test_exponent_form() {
+    let mode = RoundingMode::HalfEven;
+    // Decimal places of numbers in exponent form count those of the mantissa.
+    assert_eq!(labels(&[1.5e20, 2e20], mode), ["1.5e20", "2.0e20"]);
+    assert_eq!(labels(&[1e20, 2e21], mode), ["1e20", "2e21"]);
+    assert_eq!(labels(&[1.2e-7, 1.25e-7], mode), ["1.20e-7", "1.25e-7"]);
+    assert_eq!(labels(&[1.2e-7, 0.5], mode), ["1e-7", "0"]);
+}
+
+#[test]
+fn test_nonfinite() {
+    let values = [f64::NAN, 0.25, f64::INFINITY, 0.5, f64::NEG_INFINITY];
+    assert_eq!(dp(&values), 1);
+    assert_eq!(
+        labels(&values, RoundingMode::HalfEven),
+        ["NaN", "0.2", "inf", "0.5", "-inf"],
+    );
+    assert_eq!(dp(&[f64::NAN, f64::NAN]), 0);
+}
+
+#[test]
+fn test_widest() {
+    // More decimal places than `MAX_DECIMAL_PLACES`, after a 16-digit integer
+    // part.
+    let values = [
+        -1234567890123456.0,
+        0.000012345678901234571,
+        0.000012345678901234573,
+    ];
+    assert_eq!(dp(&values), 21);
+    let labels = labels(&values, RoundingMode::HalfEven);
+    assert_eq!(labels[0], "-1234567890123456.000000000000000000000");
+    assert_eq!(labels[0].len(), BUFFER_LEN_COLUMN);
+    assert_eq!(labels[1], "0.000012345678901234571");
+    assert_eq!(labels[2], "0.000012345678901234573");
+}
+
+#[test]
+fn test_iterator() {
+    let values = [0.1, 0.15, 0.2];
+    let mut iter = Formatter::format_shared_dp_f64(&values, RoundingMode::HalfEven);
+    assert_eq!(iter.decimal_places(), 2);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back().unwrap().as_str(), "0.20");
+    assert_eq!(iter.next().unwrap().as_str(), "0.10");
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next().unwrap().as_str(), "0.15");
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_f32() {
+    let mode = RoundingMode::HalfEven;
+    assert_eq!(
+        Formatter::shared_decimal_places_f32(&[0.1, 0.15, 0.2], mode),
+        2
+    );
+    let labels: Vec<String> = Formatter::format_shared_dp_f32(&[0.1, 0.15, 0.2], mode)
+        .map(|formatted| formatted.as_str().to_owned())
+        .collect();
+    assert_eq!(labels, ["0.10", "0.15", "0.20"]);
+
+    // The f32 layout switches to exponent form above 1e13 and below 1e-5.
+    assert_eq!(
+        Formatter::shared_decimal_places_f32(&[1.5e13, 2e13], mode),
+        1
+    );
+    assert_eq!(
+        Formatter::shared_decimal_places_f32(&[1e-6, 1.2e-6], mode),
+        7
+    );
+    assert_eq!(
+        Formatter::shared_decimal_places_f32(&[1e-7, 1.2e-7], mode),
+        1
+    );
+    assert_eq!(
+        Formatter::shared_decimal_places_f32(&[1e-5, 1.01e-5], mode),
+        7
+    );
+}
+
+#[test]
+fn test_max_values() {
+    let values: Vec<f64> = (0..MAX_SHARED_VALUES).map(|i| i as f64 / 1000.0).collect();
+    assert_eq!(dp(&values), 3);
+}
+
+#[test]
+#[should_panic = "`values` must have at most 256 values"]
+fn test_too_many_values() {
+    dp(&[0.0; MAX_SHARED_VALUES + 1]);
+}
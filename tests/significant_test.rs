@@ -0,0 +1,107 @@
+#![allow(clippy::unreadable_literal)]
+
+use ryuu::format::{RoundingMode, TrailingZeros};
+use ryuu::Formatter;
+
+fn keep(d: f64, digits: u32) -> String {
+    Formatter::format_f64_significant(d, digits, TrailingZeros::Keep, RoundingMode::HalfEven)
+        .as_str()
+        .to_owned()
+}
+
+fn strip(d: f64, digits: u32) -> String {
+    Formatter::format_f64_significant(d, digits, TrailingZeros::Strip, RoundingMode::HalfEven)
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_layout() {
+    assert_eq!(keep(0.0012345, 3), "0.00123");
+    assert_eq!(keep(123456.0, 3), "123000.0");
+    assert_eq!(keep(-123456.0, 3), "-123000.0");
+    assert_eq!(keep(1.2, 3), "1.20");
+    assert_eq!(strip(1.2, 3), "1.2");
+    assert_eq!(keep(1.0, 4), "1.000");
+    assert_eq!(strip(1.0, 4), "1.0");
+    assert_eq!(keep(100.0, 3), "100.0");
+    assert_eq!(keep(1e15, 17), "1000000000000000.0");
+    assert_eq!(keep(1e16, 2), "1.0e16");
+    assert_eq!(strip(1e16, 2), "1e16");
+    assert_eq!(keep(1.2e-5, 3), "0.0000120");
+    assert_eq!(keep(1.2e-6, 3), "1.20e-6");
+    assert_eq!(keep(1.2e-4, 3), "0.000120");
+    assert_eq!(strip(1.2e-4, 3), "0.00012");
+    assert_eq!(keep(f64::MAX, 17), "1.7976931348623157e308");
+    assert_eq!(keep(f64::MAX, 3), "1.80e308");
+    assert_eq!(keep(5e-324, 17), "4.9406564584124654e-324");
+    assert_eq!(strip(5e-324, 3), "4.94e-324");
+}
+
+#[test]
+fn test_rounding() {
+    assert_eq!(keep(0.125, 2), "0.12");
+    assert_eq!(keep(0.375, 2), "0.38");
+    assert_eq!(keep(2.675, 3), "2.67");
+    assert_eq!(keep(9.996, 3), "10.0");
+    assert_eq!(keep(9.996, 4), "9.996");
+    assert_eq!(keep(99999.5, 5), "100000.0");
+    assert_eq!(keep(9.5e-6, 1), "0.00001");
+    assert_eq!(keep(9.5e-7, 1), "1e-6");
+    assert_eq!(keep(1e23, 17), "9.9999999999999992e22");
+    assert_eq!(keep(0.1, 17), "0.10000000000000001");
+}
+
+#[test]
+fn test_zero_and_nonfinite() {
+    assert_eq!(keep(0.0, 1), "0.0");
+    assert_eq!(keep(0.0, 3), "0.00");
+    assert_eq!(keep(-0.0, 3), "-0.00");
+    assert_eq!(strip(0.0, 3), "0.0");
+    assert_eq!(keep(f64::NAN, 3), "NaN");
+    assert_eq!(keep(f64::INFINITY, 3), "inf");
+    assert_eq!(strip(f64::NEG_INFINITY, 3), "-inf");
+}
+
+#[test]
+fn test_f32() {
+    let keep = |f, digits| {
+        Formatter::format_f32_significant(f, digits, TrailingZeros::Keep, RoundingMode::HalfEven)
+    };
+    assert_eq!(keep(0.1f32, 9).as_str(), "0.100000001");
+    assert_eq!(keep(1.5f32, 3).as_str(), "1.50");
+    assert_eq!(keep(3.4028235e38f32, 9).as_str(), "3.40282347e38");
+    assert_eq!(keep(1e-45f32, 9).as_str(), "1.40129846e-45");
+    assert_eq!(keep(1e12f32, 2).as_str(), "1000000000000.0");
+    assert_eq!(keep(1e13f32, 2).as_str(), "1.0e13");
+}
+
+#[test]
+fn test_shortest_digits() {
+    // Rounding to the number of digits of the shortest representation gives
+    // the same digits, unless the shortest one is not the closest.
+    for d in [1.0, 0.3, 2.5, 1.1e32, 1.1e-32, 123456.789, -0.001234] {
+        let shortest = Formatter::format_f64(d);
+        let digits = shortest
+            .trim_start_matches('-')
+            .split('e')
+            .next()
+            .unwrap()
+            .replace('.', "")
+            .trim_matches('0')
+            .len();
+        assert_eq!(strip(d, digits as u32), shortest.as_str());
+    }
+}
+
+#[test]
+#[should_panic = "`digits` must be between 1 and 17"]
+fn test_too_many_digits() {
+    keep(1.0, 18);
+}
+
+#[test]
+#[should_panic = "`digits` must be between 1 and 17"]
+fn test_no_digits() {
+    keep(1.0, 0);
+}
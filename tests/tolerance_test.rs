@@ -0,0 +1,114 @@
+#![allow(clippy::approx_constant, clippy::float_cmp)]
+
+use ryuu::format::Tolerance::{self, Absolute, Relative};
+use ryuu::Formatter;
+
+fn within(d: f64, tolerance: Tolerance) -> String {
+    Formatter::format_within_tolerance(d, tolerance)
+        .as_str()
+        .to_owned()
+}
+
+fn significant_digits(s: &str) -> usize {
+    let mantissa = s.split('e').next().unwrap();
+    let digits = mantissa.replace(['-', '.'], "");
+    digits.trim_matches('0').len()
+}
+
+#[test]
+fn test_relative() {
+    assert_eq!(within(0.30000000000000004, Relative(1e-6)), "0.3");
+    assert_eq!(within(3.14159265, Relative(1e-6)), "3.14159");
+    assert_eq!(within(3.14159265, Relative(1e-3)), "3.14");
+    assert_eq!(within(3.14159265, Relative(0.1)), "3.0");
+    assert_eq!(within(3.14159265, Relative(0.5)), "3.0");
+    assert_eq!(within(1.9, Relative(0.5)), "2.0");
+    assert_eq!(within(123456789.0, Relative(1e-3)), "123500000.0");
+    assert_eq!(within(1.23456789e-200, Relative(1e-4)), "1.2346e-200");
+    assert_eq!(within(-1.23456789e200, Relative(1e-4)), "-1.2346e200");
+    assert_eq!(within(f64::MAX, Relative(1e-3)), "1.798e308");
+    assert_eq!(within(f64::MAX, Relative(1e-2)), "1.8e308");
+    assert_eq!(within(5e-324, Relative(0.5)), "5e-324");
+}
+
+#[test]
+fn test_absolute() {
+    assert_eq!(within(3.14159265, Absolute(0.005)), "3.14");
+    assert_eq!(within(3.14159265, Absolute(0.5)), "3.0");
+    assert_eq!(within(1234.5678, Absolute(1.0)), "1235.0");
+    assert_eq!(within(1234.5678, Absolute(50.0)), "1200.0");
+    assert_eq!(within(-0.000123, Absolute(1e-5)), "-0.00012");
+    assert_eq!(within(-0.000123, Absolute(3e-5)), "-0.0001");
+    assert_eq!(within(1e-320, Absolute(1e-321)), "1e-320");
+}
+
+#[test]
+fn test_endpoints() {
+    // The tolerance is inclusive.
+    assert_eq!(within(0.75, Absolute(0.25)), "1.0");
+    assert_eq!(within(0.75, Absolute(0.2)), "0.8");
+    assert_eq!(within(1.5, Relative(0.5)), "2.0");
+    // 1/3 as an f64 is slightly below 1/3.
+    assert_eq!(within(1.5, Relative(1.0 / 3.0)), "1.5");
+    // Of the shortest candidates, the closest one is chosen.
+    assert_eq!(within(1.4, Absolute(0.5)), "1.0");
+    assert_eq!(within(1.6, Absolute(0.5)), "2.0");
+}
+
+#[test]
+fn test_zero() {
+    assert_eq!(within(0.0, Relative(0.1)), "0.0");
+    assert_eq!(within(-0.0, Absolute(0.1)), "-0.0");
+    assert_eq!(within(0.004, Absolute(0.005)), "0.0");
+    assert_eq!(within(-0.004, Absolute(0.005)), "-0.0");
+    assert_eq!(within(0.005, Absolute(0.005)), "0.0");
+    assert_eq!(within(12.0, Relative(1.0)), "0.0");
+    assert_eq!(within(12.0, Absolute(f64::INFINITY)), "0.0");
+    assert_eq!(within(f64::NAN, Relative(0.1)), "NaN");
+    assert_eq!(within(f64::NEG_INFINITY, Absolute(1.0)), "-inf");
+}
+
+#[test]
+#[should_panic = "`tolerance` must not be negative or NaN"]
+fn test_negative_tolerance() {
+    let _ = Formatter::format_within_tolerance(1.0, Absolute(-1.0));
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 100 } else { 20000 };
+    for _ in 0..n {
+        let d = f64::from_bits(rand::random::<u64>());
+        if !d.is_finite() {
+            continue;
+        }
+        let shortest = Formatter::format_f64(d);
+
+        // Without a tolerance, the output is the shortest representation.
+        assert_eq!(within(d, Relative(0.0)), shortest.as_str(), "{d:e}");
+        assert_eq!(within(d, Absolute(0.0)), shortest.as_str(), "{d:e}");
+
+        let tolerance = 10f64.powi(-rand::random_range(1..20));
+        let formatted = within(d, Relative(tolerance));
+        let parsed = formatted.parse::<f64>().unwrap();
+        assert!(
+            significant_digits(&formatted) <= significant_digits(&shortest),
+            "{d:e}"
+        );
+        // Parsing may add up to half an ulp of error.
+        if parsed.is_finite() {
+            let error = (parsed - d).abs();
+            assert!(error <= d.abs() * (tolerance + f64::EPSILON), "{d:e}");
+        } else {
+            // A decimal within the tolerance of a number near `f64::MAX` may
+            // be beyond it, so compare a tenth of both, with the error of the
+            // division.
+            let (mantissa, exponent) = formatted.split_once('e').unwrap();
+            let exponent = exponent.parse::<i32>().unwrap() - 1;
+            let parsed = format!("{mantissa}e{exponent}").parse::<f64>().unwrap();
+            let error = (parsed - d / 10.0).abs();
+            let tenth = (d / 10.0).abs();
+            assert!(error <= tenth * (tolerance + 2.0 * f64::EPSILON), "{d:e}");
+        }
+    }
+}
@@ -0,0 +1,96 @@
+#![allow(clippy::unreadable_literal)]
+
+fn pretty(sign_exponent: u16, mantissa: u64) -> String {
+    ryuu::Formatter::format_x87_bits(sign_exponent, mantissa)
+        .as_str()
+        .to_owned()
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(pretty(0x0000, 0), "0.0");
+    assert_eq!(pretty(0x8000, 0), "-0.0");
+    assert_eq!(pretty(0x3fff, 0x8000000000000000), "1.0");
+    assert_eq!(pretty(0xbfff, 0x8000000000000000), "-1.0");
+    assert_eq!(pretty(0x7fff, 0xc000000000000000), "NaN");
+    assert_eq!(pretty(0xffff, 0xc000000000000000), "NaN");
+    assert_eq!(pretty(0x7fff, 0x8000000000000001), "NaN");
+    assert_eq!(pretty(0x7fff, 0x8000000000000000), "inf");
+    assert_eq!(pretty(0xffff, 0x8000000000000000), "-inf");
+}
+
+#[test]
+fn test_ryu() {
+    assert_eq!(pretty(0x3ffb, 0xcccccccccccccccd), "0.1");
+    assert_eq!(pretty(0x3ffd, 0xaaaaaaaaaaaaaaab), "0.33333333333333333334");
+    assert_eq!(pretty(0x4000, 0xc90fdaa22168c235), "3.1415926535897932385");
+    assert_eq!(pretty(0xc000, 0xadf85458a2bb4a9b), "-2.7182818284590452354");
+    assert_eq!(
+        pretty(0x403e, 0xffffffffffffffff),
+        "1.8446744073709551615e19"
+    );
+    assert_eq!(
+        pretty(0x403f, 0x8000000000000000),
+        "1.8446744073709551616e19"
+    );
+}
+
+#[test]
+fn test_min_and_max() {
+    assert_eq!(
+        pretty(0x7ffe, 0xffffffffffffffff),
+        "1.189731495357231765e4932"
+    );
+    assert_eq!(
+        pretty(0x0001, 0x8000000000000000),
+        "3.3621031431120935063e-4932"
+    );
+    assert_eq!(
+        pretty(0x0000, 0x7fffffffffffffff),
+        "3.362103143112093506e-4932"
+    );
+    assert_eq!(pretty(0x0000, 0x0000000000000001), "4e-4951");
+}
+
+#[test]
+fn test_pseudo_denormal() {
+    // A zero exponent with the integer bit set has the value of the normal
+    // number with a biased exponent of 1.
+    assert_eq!(
+        pretty(0x0000, 0x8000000000000000),
+        "3.3621031431120935063e-4932"
+    );
+    assert_eq!(
+        pretty(0x8000, 0xc000000000000000),
+        "-5.0431547146681402594e-4932"
+    );
+    for mantissa in [0x8000000000000001, 0xc90fdaa22168c235, 0xffffffffffffffff] {
+        assert_eq!(pretty(0x0000, mantissa), pretty(0x0001, mantissa));
+        assert_eq!(pretty(0x8000, mantissa), pretty(0x8001, mantissa));
+    }
+}
+
+#[test]
+fn test_unnormal() {
+    // A nonzero exponent with the integer bit clear is an invalid operand.
+    assert_eq!(pretty(0x3fff, 0x4000000000000000), "NaN");
+    assert_eq!(pretty(0xbfff, 0x0000000000000001), "NaN");
+    assert_eq!(pretty(0x0001, 0x7fffffffffffffff), "NaN");
+    // Pseudo-zero, pseudo-infinity and pseudo-NaN.
+    assert_eq!(pretty(0x3fff, 0), "NaN");
+    assert_eq!(pretty(0x7fff, 0), "NaN");
+    assert_eq!(pretty(0xffff, 0x4000000000000000), "NaN");
+
+    // Unless asked to print the value of the encoding.
+    let unnormal = ryuu::Formatter::format_finite_x87_bits(0x3fff, 0x4000000000000000);
+    assert_eq!(unnormal.as_str(), "0.5");
+    let pseudo_zero = ryuu::Formatter::format_finite_x87_bits(0xbfff, 0);
+    assert_eq!(pseudo_zero.as_str(), "-0.0");
+}
+
+#[test]
+fn test_buffer_len() {
+    let longest = ryuu::Formatter::format_x87_bits(0x8000, 0xc000000000000000);
+    assert_eq!(longest.as_str(), "-5.0431547146681402594e-4932");
+    assert!(longest.len() <= ryuu::format::BUFFER_LEN);
+}
@@ -0,0 +1,357 @@
+extern crate std;
+
+use std::format;
+use std::vec::Vec;
+
+use rand::Rng;
+
+use super::*;
+
+// The digits and exponent of `x` rounded to `digits` significant digits by
+// the standard library, which rounds the exact value half to even.
+fn reference(x: f64, digits: u32) -> (u64, i32) {
+    let precision = digits as usize - 1;
+    let formatted = format!("{x:.precision$e}");
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    (
+        mantissa.replace('.', "").parse().unwrap(),
+        exponent.parse::<i32>().unwrap() - precision as i32,
+    )
+}
+
+fn significant_f64_mode(x: f64, digits: u32, mode: RoundingMode) -> (u64, i32) {
+    let bits = x.to_bits();
+    let v = d2fixed_significant(
+        bits & ((1 << 52) - 1),
+        (bits >> 52) as u32 & 0x7ff,
+        digits,
+        x < 0.0,
+        mode,
+    );
+    (v.mantissa, v.exponent)
+}
+
+fn significant_f32_mode(x: f32, digits: u32, mode: RoundingMode) -> (u64, i32) {
+    let bits = x.to_bits();
+    let v = f2fixed_significant(
+        bits & ((1 << 23) - 1),
+        (bits >> 23) & 0xff,
+        digits,
+        x < 0.0,
+        mode,
+    );
+    (v.mantissa, v.exponent)
+}
+
+fn significant_f64(x: f64, digits: u32) -> (u64, i32) {
+    significant_f64_mode(x, digits, RoundingMode::HalfEven)
+}
+
+fn significant_f32(x: f32, digits: u32) -> (u64, i32) {
+    significant_f32_mode(x, digits, RoundingMode::HalfEven)
+}
+
+fn fixed_f64(x: f64, decimal_places: u32, mode: RoundingMode) -> u128 {
+    let bits = x.to_bits();
+    d2fixed(
+        bits & ((1 << 52) - 1),
+        (bits >> 52) as u32 & 0x7ff,
+        decimal_places,
+        x < 0.0,
+        mode,
+    )
+}
+
+fn fixed_f32(x: f32, decimal_places: u32, mode: RoundingMode) -> u128 {
+    let bits = x.to_bits();
+    f2fixed(
+        bits & ((1 << 23) - 1),
+        (bits >> 23) & 0xff,
+        decimal_places,
+        x < 0.0,
+        mode,
+    )
+}
+
+const MODES: [RoundingMode; 7] = [
+    RoundingMode::HalfEven,
+    RoundingMode::HalfUp,
+    RoundingMode::HalfAwayFromZero,
+    RoundingMode::Floor,
+    RoundingMode::Ceiling,
+    RoundingMode::TowardZero,
+    RoundingMode::AwayFromZero,
+];
+
+// The exact decimal digits of a nonzero f64, and the power of ten of the
+// first one. The standard library prints exact digits when asked for more
+// than the at most 767 significant digits of an f64.
+fn exact_digits(x: f64) -> (Vec<u8>, i32) {
+    let formatted = format!("{:.800e}", x.abs());
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let mut digits: Vec<u8> = mantissa.bytes().filter(u8::is_ascii_digit).collect();
+    while digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    (digits, exponent.parse().unwrap())
+}
+
+// The exact value of the nonzero `x` rounded to a multiple of 10^target with
+// `mode`, by rounding its exact digits as a string.
+fn reference_round(x: f64, target: i32, mode: RoundingMode) -> u128 {
+    let (digits, first) = exact_digits(x);
+    let negative = x < 0.0;
+    // Digit i has the power of ten first - i.
+    let digit = |p: i32| -> u8 {
+        let i = first - p;
+        if 0 <= i && (i as usize) < digits.len() {
+            digits[i as usize] - b'0'
+        } else {
+            0
+        }
+    };
+    let mut q = 0u128;
+    let mut p = first;
+    while p >= target {
+        q = q * 10 + digit(p) as u128;
+        p -= 1;
+    }
+    let last = first - digits.len() as i32 + 1;
+    let rest_nonzero = (last..target - 1).any(|p| digit(p) != 0);
+    let remainder = match (digit(target - 1), rest_nonzero) {
+        (0, false) => Remainder::Zero,
+        (d, _) if d < 5 => Remainder::BelowHalf,
+        (5, false) => Remainder::Half,
+        _ => Remainder::AboveHalf,
+    };
+    let up = match (remainder, mode) {
+        (Remainder::Zero, _) => false,
+        (_, RoundingMode::Floor) => negative,
+        (_, RoundingMode::Ceiling) => !negative,
+        (_, RoundingMode::TowardZero) => false,
+        (_, RoundingMode::AwayFromZero) => true,
+        (Remainder::BelowHalf, _) => false,
+        (Remainder::AboveHalf, _) => true,
+        (Remainder::Half, RoundingMode::HalfEven) => q % 2 == 1,
+        (Remainder::Half, RoundingMode::HalfUp) => !negative,
+        (Remainder::Half, RoundingMode::HalfAwayFromZero) => true,
+    };
+    q + up as u128
+}
+
+// The reference for `significant_f64_mode`.
+fn reference_significant(x: f64, digits: u32, mode: RoundingMode) -> (u64, i32) {
+    let (_, first) = exact_digits(x);
+    let mut target = first - digits as i32 + 1;
+    let mut q = reference_round(x, target, mode);
+    if q == 10u128.pow(digits) {
+        q /= 10;
+        target += 1;
+    }
+    (q as u64, target)
+}
+
+#[test]
+fn test_div_pow10() {
+    assert_eq!((1, Remainder::Zero), div_pow10(1, 0, 0));
+    // 0.125 = 1 * 2^-3
+    assert_eq!((12, Remainder::Half), div_pow10(1, -3, -2));
+    assert_eq!((1, Remainder::BelowHalf), div_pow10(1, -3, -1));
+    assert_eq!((0, Remainder::BelowHalf), div_pow10(1, -3, 0));
+    assert_eq!((0, Remainder::BelowHalf), div_pow10(1, -1074, 0));
+    // 3 * 2^10 = 3072
+    assert_eq!((3, Remainder::BelowHalf), div_pow10(3, 10, 3));
+    assert_eq!((307, Remainder::BelowHalf), div_pow10(3, 10, 1));
+    assert_eq!((3072, Remainder::Zero), div_pow10(3, 10, 0));
+    assert_eq!((30, Remainder::AboveHalf), div_pow10(3, 10, 2));
+    // The largest f64.
+    assert_eq!(
+        (17976931348623157, Remainder::BelowHalf),
+        div_pow10((1 << 53) - 1, 971, 292)
+    );
+}
+
+#[test]
+fn test_rounding() {
+    // 0.125 is exactly halfway and 0.375 as well, both round to even.
+    assert_eq!((12, -2), significant_f64(0.125, 2));
+    assert_eq!((38, -2), significant_f64(0.375, 2));
+    // 2.675 is a bit below halfway in binary.
+    assert_eq!((267, -2), significant_f64(2.675, 3));
+    // Carry into a new digit.
+    assert_eq!((100, -1), significant_f64(9.996, 3));
+    assert_eq!((1, 1), significant_f64(9.5, 1));
+    assert_eq!((8, 0), significant_f64(8.5, 1));
+    // The shortest representation is "1e23", but the value is below it.
+    // 99999999999999991611392.
+    assert_eq!((99999999999999992, 6), significant_f64(1e23, 17));
+    assert_eq!((10, 22), significant_f64(1e23, 2));
+    assert_eq!((49406564584124654, -340), significant_f64(5e-324, 17));
+    assert_eq!((5, -324), significant_f64(5e-324, 1));
+    assert_eq!((140129846, -53), significant_f32(1e-45, 9));
+    assert_eq!((34028235, 31), significant_f32(f32::MAX, 8));
+}
+
+#[test]
+fn test_random() {
+    let mut rng = rand::rng();
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    for _ in 0..n {
+        let x = f64::from_bits(rng.random::<u64>() & !(1 << 63));
+        if !x.is_finite() || x == 0.0 {
+            continue;
+        }
+        let digits = rng.random_range(1..=MAX_SIGNIFICANT_DIGITS_F64);
+        assert_eq!(reference(x, digits), significant_f64(x, digits), "{x:e}");
+
+        let f = f32::from_bits(rng.random::<u32>() & !(1 << 31));
+        if !f.is_finite() || f == 0.0 {
+            continue;
+        }
+        let digits = rng.random_range(1..=MAX_SIGNIFICANT_DIGITS_F32);
+        assert_eq!(
+            reference(f as f64, digits),
+            significant_f32(f, digits),
+            "{f:e}"
+        );
+    }
+}
+
+#[test]
+fn test_halfway() {
+    // Values with few significant bits are often exactly halfway.
+    let mut rng = rand::rng();
+    for _ in 0..100000 {
+        let x = rng.random_range(1..1u64 << 20) as f64 * 2f64.powi(rng.random_range(-40..40));
+        let digits = rng.random_range(1..=7);
+        assert_eq!(reference(x, digits), significant_f64(x, digits), "{x:e}");
+    }
+}
+
+#[test]
+fn test_round_decimal() {
+    use RoundingMode::{HalfAwayFromZero, HalfEven, HalfUp};
+
+    assert_eq!((268, -2), round_decimal(2675, -3, -2, false, HalfEven));
+    assert_eq!((266, -2), round_decimal(2665, -3, -2, false, HalfEven));
+    assert_eq!((267, -2), round_decimal(2665, -3, -2, false, HalfUp));
+    assert_eq!((266, -2), round_decimal(2665, -3, -2, true, HalfUp));
+    assert_eq!(
+        (267, -2),
+        round_decimal(2665, -3, -2, true, HalfAwayFromZero)
+    );
+    assert_eq!((267, -2), round_decimal(26651, -4, -2, true, HalfUp));
+    assert_eq!((1000, -2), round_decimal(9995, -3, -2, false, HalfUp));
+    // Nothing to round.
+    assert_eq!((31, -1), round_decimal(31, -1, -3, false, HalfEven));
+    assert_eq!((5, 3), round_decimal(5, 3, 0, false, HalfEven));
+    // More digits dropped than a u64 has.
+    assert_eq!((0, 2), round_decimal(u64::MAX, -18, 2, false, HalfUp));
+    assert_eq!((1, 2), round_decimal(5, 1, 2, false, HalfUp));
+    assert_eq!((0, 2), round_decimal(5, 1, 2, false, HalfEven));
+}
+
+#[test]
+fn test_directed() {
+    use RoundingMode::{AwayFromZero, Ceiling, Floor, TowardZero};
+
+    // 0.1 = 0.1000000000000000055511151231257827...
+    assert_eq!((100, -3), significant_f64_mode(0.1, 3, Floor));
+    assert_eq!((101, -3), significant_f64_mode(0.1, 3, Ceiling));
+    assert_eq!((101, -3), significant_f64_mode(-0.1, 3, Floor));
+    assert_eq!((100, -3), significant_f64_mode(-0.1, 3, Ceiling));
+    assert_eq!((100, -3), significant_f64_mode(-0.1, 3, TowardZero));
+    assert_eq!((101, -3), significant_f64_mode(-0.1, 3, AwayFromZero));
+    // Exact values never move.
+    assert_eq!((125, -3), significant_f64_mode(0.125, 3, AwayFromZero));
+    assert_eq!((125, -3), significant_f64_mode(-0.125, 3, Floor));
+    // Carry into a new digit.
+    assert_eq!((1, 1), significant_f64_mode(9.01, 1, Ceiling));
+    assert_eq!((1, 1), significant_f64_mode(-9.01, 1, Floor));
+    assert_eq!((4, -324), significant_f64_mode(5e-324, 1, Floor));
+    assert_eq!((5, -324), significant_f64_mode(5e-324, 1, Ceiling));
+    assert_eq!((49, -325), significant_f64_mode(5e-324, 2, Floor));
+    assert_eq!((50, -325), significant_f64_mode(5e-324, 2, Ceiling));
+
+    assert_eq!(10, fixed_f64(0.1, 2, Floor));
+    assert_eq!(11, fixed_f64(0.1, 2, Ceiling));
+    assert_eq!(11, fixed_f64(-0.1, 2, Floor));
+    assert_eq!(0, fixed_f64(5e-324, 14, Floor));
+    assert_eq!(1, fixed_f64(5e-324, 14, Ceiling));
+    assert_eq!(1, fixed_f64(-5e-324, 14, Floor));
+    assert_eq!(0, fixed_f64(-5e-324, 14, Ceiling));
+    assert_eq!(999999999999999800000000000000, fixed_f64(9999999999999998.0, 14, Floor));
+    assert_eq!(999999999999999800000000000000, fixed_f64(9999999999999998.0, 14, Ceiling));
+    assert_eq!(1, fixed_f64(1.5, 0, Floor));
+    assert_eq!(2, fixed_f64(1.5, 0, Ceiling));
+}
+
+#[test]
+fn test_random_modes() {
+    let mut rng = rand::rng();
+    let n = if cfg!(miri) { 10 } else { 5000 };
+    for _ in 0..n {
+        let x = f64::from_bits(rng.random::<u64>());
+        if !x.is_finite() || x == 0.0 {
+            continue;
+        }
+        let mode = MODES[rng.random_range(0..MODES.len())];
+        let digits = rng.random_range(1..=MAX_SIGNIFICANT_DIGITS_F64);
+        assert_eq!(
+            reference_significant(x, digits, mode),
+            significant_f64_mode(x, digits, mode),
+            "{x:e} {mode:?}"
+        );
+
+        // Fixed decimal places, for values that are printed positionally.
+        let x = (rng.random::<f64>() - 0.5) * 10f64.powi(rng.random_range(-6..16));
+        if x == 0.0 {
+            continue;
+        }
+        let decimal_places = rng.random_range(0..=14);
+        assert_eq!(
+            reference_round(x, -(decimal_places as i32), mode),
+            fixed_f64(x, decimal_places, mode),
+            "{x:e} {mode:?}"
+        );
+
+        let f = x as f32;
+        if f != 0.0 {
+            assert_eq!(
+                reference_round(f as f64, -(decimal_places as i32), mode),
+                fixed_f32(f, decimal_places, mode),
+                "{f:e} {mode:?}"
+            );
+            let digits = rng.random_range(1..=MAX_SIGNIFICANT_DIGITS_F32);
+            assert_eq!(
+                reference_significant(f as f64, digits, mode),
+                significant_f32_mode(f, digits, mode),
+                "{f:e} {mode:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_halfway_modes() {
+    // Values with few significant bits are often exactly halfway, and the
+    // directed modes differ on the sign.
+    let mut rng = rand::rng();
+    for _ in 0..20000 {
+        let sign = if rng.random() { -1.0 } else { 1.0 };
+        let x = sign * rng.random_range(1..1u64 << 12) as f64 * 2f64.powi(rng.random_range(-12..4));
+        let mode = MODES[rng.random_range(0..MODES.len())];
+        let decimal_places = rng.random_range(0..=6);
+        assert_eq!(
+            reference_round(x, -(decimal_places as i32), mode),
+            fixed_f64(x, decimal_places, mode),
+            "{x:e} {mode:?}"
+        );
+        let digits = rng.random_range(1..=5);
+        assert_eq!(
+            reference_significant(x, digits, mode),
+            significant_f64_mode(x, digits, mode),
+            "{x:e} {mode:?}"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+extern crate std;
+
+use rand::Rng;
+
+use super::*;
+use crate::d2fixed::{f32_parts, f64_parts};
+use crate::d2s::d2d;
+use crate::f2s::f2d;
+
+fn radix_f64(x: f64, radix: u32) -> FloatingRadix {
+    let bits = x.to_bits();
+    let ieee_mantissa = bits & ((1 << 52) - 1);
+    let ieee_exponent = (bits >> 52) as u32 & 0x7ff;
+    let (m2, e2) = f64_parts(ieee_mantissa, ieee_exponent);
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    d2radix(m2, e2, mm_shift, radix)
+}
+
+fn radix_f32(x: f32, radix: u32) -> FloatingRadix {
+    let bits = x.to_bits();
+    let ieee_mantissa = bits & ((1 << 23) - 1);
+    let ieee_exponent = (bits >> 23) & 0xff;
+    let (m2, e2) = f32_parts(ieee_mantissa, ieee_exponent);
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    d2radix(m2, e2, mm_shift, radix)
+}
+
+#[test]
+fn test_floor_log_radix_pow2() {
+    assert_eq!(floor_log_radix_pow2(0, 10), 0);
+    assert_eq!(floor_log_radix_pow2(3, 10), 0);
+    assert_eq!(floor_log_radix_pow2(4, 10), 1);
+    assert_eq!(floor_log_radix_pow2(-1, 10), -1);
+    assert_eq!(floor_log_radix_pow2(-4, 10), -2);
+    assert_eq!(floor_log_radix_pow2(-1074, 2), -1074);
+    assert_eq!(floor_log_radix_pow2(10, 32), 2);
+    assert_eq!(floor_log_radix_pow2(-10, 32), -2);
+    assert_eq!(floor_log_radix_pow2(1024, 36), 198);
+}
+
+#[test]
+fn test_powers_of_two_radix() {
+    // Every float is exact in these radices, with digits of the mantissa.
+    assert_eq!(
+        radix_f64(0.1, 16),
+        FloatingRadix {
+            mantissa: 0x1999999999999a,
+            exponent: -14,
+        },
+    );
+    assert_eq!(
+        radix_f64(5e-324, 2),
+        FloatingRadix {
+            mantissa: 1,
+            exponent: -1074,
+        },
+    );
+    assert_eq!(
+        radix_f64(f64::MAX, 2),
+        FloatingRadix {
+            mantissa: (1 << 53) - 1,
+            exponent: 971,
+        },
+    );
+}
+
+#[test]
+fn test_radix_10() {
+    // Radix 10 gives Ryū's digits, including at the powers of two, whose
+    // rounding interval is asymmetric.
+    let mut ieee_exponent = 0;
+    while ieee_exponent < 0x7ff {
+        for ieee_mantissa in [0, 1, (1 << 52) - 1] {
+            let x = f64::from_bits(ieee_exponent << 52 | ieee_mantissa);
+            if x == 0.0 {
+                continue;
+            }
+            let v = d2d(ieee_mantissa, ieee_exponent as u32);
+            let r = radix_f64(x, 10);
+            assert_eq!((r.mantissa, r.exponent), (v.mantissa, v.exponent), "{x:?}");
+        }
+        ieee_exponent += 1;
+    }
+
+    let mut rng = rand::rng();
+    for _ in 0..20000 {
+        let x = f64::from_bits(rng.random::<u64>() & !(1 << 63));
+        if !x.is_finite() || x == 0.0 {
+            continue;
+        }
+        let bits = x.to_bits();
+        let v = d2d(bits & ((1 << 52) - 1), (bits >> 52) as u32 & 0x7ff);
+        let r = radix_f64(x, 10);
+        assert_eq!((r.mantissa, r.exponent), (v.mantissa, v.exponent), "{x:?}");
+
+        let f = f32::from_bits(rng.random::<u32>() & !(1 << 31));
+        if !f.is_finite() || f == 0.0 {
+            continue;
+        }
+        let bits = f.to_bits();
+        let v = f2d(bits & ((1 << 23) - 1), (bits >> 23) & 0xff);
+        let r = radix_f32(f, 10);
+        assert_eq!(
+            (r.mantissa, r.exponent),
+            (v.mantissa as u64, v.exponent),
+            "{f:?}"
+        );
+    }
+}
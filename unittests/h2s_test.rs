@@ -0,0 +1,167 @@
+extern crate std;
+
+use std::format;
+
+use crate::parse::{s2bf16_bits, s2f16_bits, Error};
+use crate::{Formatted, Formatter};
+
+struct Layout {
+    format: fn(u16) -> Formatted,
+    parse: fn(&[u8]) -> Result<u16, Error>,
+    to_f64: fn(u16) -> f64,
+    exponent_mask: u16,
+    max_len: usize,
+}
+
+const HALF: Layout = Layout {
+    format: Formatter::format_finite_f16_bits,
+    parse: s2f16_bits,
+    to_f64: f16_bits_to_f64,
+    exponent_mask: 0x7c00,
+    max_len: 13,
+};
+
+const BFLOAT: Layout = Layout {
+    format: Formatter::format_finite_bf16_bits,
+    parse: s2bf16_bits,
+    to_f64: |bits| f32::from_bits((bits as u32) << 16) as f64,
+    exponent_mask: 0x7f80,
+    max_len: 16,
+};
+
+// The exact value of a finite binary16 number.
+fn f16_bits_to_f64(bits: u16) -> f64 {
+    let sign = if bits >> 15 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    if exponent == 0 {
+        sign * mantissa * 2f64.powi(-24)
+    } else {
+        sign * (1024.0 + mantissa) * 2f64.powi(exponent - 25)
+    }
+}
+
+// The number of significant digits in a formatted number.
+fn significant_digits(s: &str) -> usize {
+    let mantissa = s.split('e').next().unwrap();
+    let digits = mantissa.trim_start_matches('-').replace('.', "");
+    digits.trim_start_matches('0').trim_end_matches('0').len()
+}
+
+fn check_exhaustive_round_trip(layout: &Layout) {
+    for bits in 0..=u16::MAX {
+        if bits & layout.exponent_mask == layout.exponent_mask {
+            continue;
+        }
+        let formatted = (layout.format)(bits);
+        assert!(formatted.len() <= layout.max_len, "{formatted:?}");
+        assert_eq!(
+            Ok(bits),
+            (layout.parse)(formatted.as_bytes()),
+            "{formatted:?}"
+        );
+    }
+}
+
+fn check_exhaustive_shortest(layout: &Layout) {
+    for bits in 1..layout.exponent_mask {
+        let formatted = (layout.format)(bits);
+        let digits = significant_digits(&formatted);
+        if digits == 1 {
+            continue;
+        }
+
+        // No decimal with one digit less may round-trip: it would be one of
+        // the two neighbors of the correctly rounded one, or itself.
+        let exact = (layout.to_f64)(bits);
+        let precision = digits - 2;
+        let nearest = format!("{exact:.precision$e}");
+        let (mantissa, exponent) = nearest.split_once('e').unwrap();
+        let mantissa: i64 = mantissa.replace('.', "").parse().unwrap();
+        let exponent: i32 = exponent.parse::<i32>().unwrap() - precision as i32;
+        for candidate in [mantissa - 1, mantissa, mantissa + 1] {
+            let candidate = format!("{candidate}e{exponent}");
+            assert_ne!(
+                Ok(bits),
+                (layout.parse)(candidate.as_bytes()),
+                "{formatted:?}"
+            );
+        }
+    }
+}
+
+fn check_exhaustive_parse(layout: &Layout) {
+    // Every value parses to itself, and every midpoint between two adjacent
+    // positive values rounds to the one with an even mantissa.
+    for bits in 0..layout.exponent_mask {
+        let exact = (layout.to_f64)(bits);
+        let formatted = format!("{exact:e}");
+        assert_eq!(
+            Ok(bits),
+            (layout.parse)(formatted.as_bytes()),
+            "{formatted}"
+        );
+
+        let midpoint = (exact + (layout.to_f64)(bits + 1)) / 2.0;
+        if midpoint.is_infinite() {
+            continue;
+        }
+        let formatted = format!("{midpoint:.200e}");
+        let even = if bits & 1 == 0 { bits } else { bits + 1 };
+        assert_eq!(
+            Ok(even),
+            (layout.parse)(formatted.as_bytes()),
+            "{formatted}"
+        );
+    }
+}
+
+#[test]
+fn test_exhaustive_round_trip() {
+    check_exhaustive_round_trip(&HALF);
+    check_exhaustive_round_trip(&BFLOAT);
+}
+
+#[test]
+fn test_exhaustive_shortest() {
+    check_exhaustive_shortest(&HALF);
+    check_exhaustive_shortest(&BFLOAT);
+}
+
+#[test]
+fn test_exhaustive_parse() {
+    check_exhaustive_parse(&HALF);
+    check_exhaustive_parse(&BFLOAT);
+}
+
+#[test]
+fn test_parse_rounding() {
+    assert_eq!(Ok(0x0000), s2f16_bits(b"0"));
+    assert_eq!(Ok(0x8000), s2f16_bits(b"-0.0"));
+    assert_eq!(Ok(0x3c00), s2f16_bits(b"1"));
+    assert_eq!(Ok(0x7bff), s2f16_bits(b"65504"));
+    assert_eq!(Ok(0x7bff), s2f16_bits(b"65519.999999999999999999"));
+    assert_eq!(Ok(0x7c00), s2f16_bits(b"65520"));
+    assert_eq!(Ok(0x7c00), s2f16_bits(b"1e99999999"));
+    // Smallest subnormal is 2^-24, halfway to zero rounds to even.
+    assert_eq!(Ok(0x0001), s2f16_bits(b"5.9604644775390625e-8"));
+    assert_eq!(Ok(0x0000), s2f16_bits(b"2.98023223876953125e-8"));
+    assert_eq!(
+        Ok(0x0001),
+        s2f16_bits(b"2.98023223876953125000000000001e-8")
+    );
+    assert_eq!(Ok(0x0000), s2f16_bits(b"1e-99999999"));
+    // 2049 is halfway between 2048 and 2050.
+    assert_eq!(Ok(0x6800), s2f16_bits(b"2049"));
+    assert_eq!(Ok(0x6801), s2f16_bits(b"2049.0000000000000000000000001"));
+    assert_eq!(Ok(0x6802), s2f16_bits(b"2051"));
+
+    assert_eq!(Ok(0x3f80), s2bf16_bits(b"1"));
+    assert_eq!(Ok(0xbf80), s2bf16_bits(b"-1"));
+    // 257 is halfway between 256 and 258.
+    assert_eq!(Ok(0x4380), s2bf16_bits(b"257"));
+    assert_eq!(Ok(0x4381), s2bf16_bits(b"257.000000000000000000000001"));
+    assert_eq!(Ok(0x7f80), s2bf16_bits(b"3.4e38"));
+    assert_eq!(Ok(0x0001), s2bf16_bits(b"9.2e-41"));
+    assert_eq!(Ok(0x0000), s2bf16_bits(b"4e-41"));
+}
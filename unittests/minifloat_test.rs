@@ -0,0 +1,201 @@
+extern crate std;
+
+use std::format;
+
+use crate::minifloat::{Layout, Special};
+use crate::parse::{s2f16_bits, s2minifloat_bits};
+use crate::Formatter;
+
+const LAYOUTS: [Layout; 5] = [
+    Layout::E4M3,
+    Layout::E5M2,
+    Layout::E2M3,
+    Layout::E3M2,
+    Layout::E2M1,
+];
+
+const HALF: Layout = Layout::new(5, 10, 15, Special::Ieee);
+
+// The exact value of a finite minifloat.
+fn to_f64(bits: u16, layout: Layout) -> f64 {
+    let sign = if layout.is_negative(bits) { -1.0 } else { 1.0 };
+    let mantissa = layout.ieee_mantissa(bits);
+    let exponent = layout.ieee_exponent(bits);
+    let (m2, e2) = if exponent == 0 {
+        (mantissa, 1)
+    } else {
+        (mantissa | 1 << layout.mantissa_bits(), exponent as i32)
+    };
+    sign * m2 as f64 * 2f64.powi(e2 - layout.bias() - layout.mantissa_bits() as i32)
+}
+
+// The number of significant digits in a formatted number.
+fn significant_digits(s: &str) -> usize {
+    let mantissa = s.split('e').next().unwrap();
+    let digits = mantissa.trim_start_matches('-').replace('.', "");
+    digits.trim_start_matches('0').trim_end_matches('0').len()
+}
+
+#[test]
+fn test_exhaustive_round_trip() {
+    for layout in LAYOUTS {
+        for bits in 0..1u16 << layout.width() {
+            let formatted = Formatter::format_minifloat_bits(bits, layout);
+            if layout.is_nan(bits) {
+                assert_eq!(formatted.as_str(), "NaN");
+                continue;
+            } else if layout.is_infinite(bits) {
+                let expected = if layout.is_negative(bits) {
+                    "-inf"
+                } else {
+                    "inf"
+                };
+                assert_eq!(formatted.as_str(), expected);
+                continue;
+            }
+
+            assert!(formatted.len() <= 16, "{layout:?} {formatted:?}");
+            assert_eq!(
+                Ok(bits),
+                s2minifloat_bits(formatted.as_bytes(), layout),
+                "{layout:?} {formatted:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_exhaustive_shortest() {
+    for layout in LAYOUTS {
+        for bits in 1..=layout.max_finite() {
+            let formatted = Formatter::format_minifloat_bits(bits, layout);
+            let digits = significant_digits(&formatted);
+            if digits == 1 {
+                continue;
+            }
+            if bits == layout.max_finite() && layout.special() == Special::FiniteOnly {
+                // Anything larger saturates, which does not count as rounding.
+                continue;
+            }
+
+            // No decimal with one digit less may round-trip: it would be one
+            // of the two neighbors of the correctly rounded one, or itself.
+            let exact = to_f64(bits, layout);
+            let precision = digits - 2;
+            let nearest = format!("{exact:.precision$e}");
+            let (mantissa, exponent) = nearest.split_once('e').unwrap();
+            let mantissa: i64 = mantissa.replace('.', "").parse().unwrap();
+            let exponent: i32 = exponent.parse::<i32>().unwrap() - precision as i32;
+            for candidate in [mantissa - 1, mantissa, mantissa + 1] {
+                let candidate = format!("{candidate}e{exponent}");
+                assert_ne!(
+                    Ok(bits),
+                    s2minifloat_bits(candidate.as_bytes(), layout),
+                    "{layout:?} {formatted:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_exhaustive_parse() {
+    // Every value parses to itself, and every midpoint between two adjacent
+    // positive values rounds to the one with an even mantissa.
+    for layout in LAYOUTS {
+        for bits in 0..layout.max_finite() {
+            let exact = to_f64(bits, layout);
+            let formatted = format!("{exact:e}");
+            assert_eq!(
+                Ok(bits),
+                s2minifloat_bits(formatted.as_bytes(), layout),
+                "{layout:?} {formatted}"
+            );
+
+            let midpoint = (exact + to_f64(bits + 1, layout)) / 2.0;
+            let formatted = format!("{midpoint:.200e}");
+            let even = if bits & 1 == 0 { bits } else { bits + 1 };
+            assert_eq!(
+                Ok(even),
+                s2minifloat_bits(formatted.as_bytes(), layout),
+                "{layout:?} {formatted}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_overflow() {
+    let e4m3 = Layout::E4M3;
+    assert_eq!(Ok(0x7e), s2minifloat_bits(b"448", e4m3));
+    // Halfway to 480, which would have an odd mantissa.
+    assert_eq!(Ok(0x7e), s2minifloat_bits(b"464", e4m3));
+    assert_eq!(
+        Ok(0x7f),
+        s2minifloat_bits(b"464.00000000000000000001", e4m3)
+    );
+    assert_eq!(Ok(0xff), s2minifloat_bits(b"-1e99999999", e4m3));
+
+    let e5m2 = Layout::E5M2;
+    assert_eq!(Ok(0x7b), s2minifloat_bits(b"57344", e5m2));
+    assert_eq!(
+        Ok(0x7b),
+        s2minifloat_bits(b"61439.9999999999999999999", e5m2)
+    );
+    assert_eq!(Ok(0x7c), s2minifloat_bits(b"61440", e5m2));
+    assert_eq!(Ok(0xfc), s2minifloat_bits(b"-1e99999999", e5m2));
+
+    let e2m1 = Layout::E2M1;
+    assert_eq!(Ok(0x7), s2minifloat_bits(b"7", e2m1));
+    assert_eq!(Ok(0x7), s2minifloat_bits(b"1e99999999", e2m1));
+    assert_eq!(Ok(0xf), s2minifloat_bits(b"-8", e2m1));
+
+    // Underflow keeps the sign.
+    assert_eq!(Ok(0x80), s2minifloat_bits(b"-1e-99999999", e4m3));
+    assert_eq!(Ok(0x8), s2minifloat_bits(b"-0.25", e2m1));
+    assert_eq!(
+        Ok(0x9),
+        s2minifloat_bits(b"-0.2500000000000000000001", e2m1)
+    );
+}
+
+#[test]
+fn test_same_as_f16() {
+    for bits in 0..=u16::MAX {
+        let formatted = Formatter::format_minifloat_bits(bits, HALF);
+        assert_eq!(
+            formatted.as_str(),
+            Formatter::format_f16_bits(bits).as_str()
+        );
+        if HALF.is_finite(bits) {
+            let exact = format!("{:.200e}", to_f64(bits, HALF) * 1.000244140625);
+            assert_eq!(
+                s2f16_bits(exact.as_bytes()),
+                s2minifloat_bits(exact.as_bytes(), HALF)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_bits_above_width() {
+    let e2m1 = Layout::E2M1;
+    for bits in 0..16 {
+        assert_eq!(
+            Formatter::format_minifloat_bits(bits, e2m1).as_str(),
+            Formatter::format_minifloat_bits(bits | 0xfff0, e2m1).as_str()
+        );
+    }
+}
+
+#[test]
+#[should_panic = "a minifloat is at most 16 bits wide"]
+fn test_layout_too_wide() {
+    let _ = Layout::new(5, 11, 15, Special::Ieee);
+}
+
+#[test]
+#[should_panic = "the minifloat exceeds the range of f32"]
+fn test_layout_out_of_range() {
+    let _ = Layout::new(8, 7, 100, Special::Ieee);
+}
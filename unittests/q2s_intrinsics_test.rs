@@ -0,0 +1,131 @@
+use crate::q2s_intrinsics::{
+    compute_inv_pow5_128, compute_pow5_128, mul_shift_128, pow5bits_128, Q_POW5_BITCOUNT,
+    Q_POW5_INV_BITCOUNT,
+};
+
+// Little-endian multi-word integer, large enough for 5^4969 times a 256-bit
+// table entry.
+struct Big {
+    limbs: [u64; 190],
+}
+
+impl Big {
+    fn one() -> Self {
+        let mut limbs = [0; 190];
+        limbs[0] = 1;
+        Big { limbs }
+    }
+
+    fn mul_small(&mut self, factor: u64) {
+        let mut carry = 0u128;
+        for limb in &mut self.limbs {
+            let t = *limb as u128 * factor as u128 + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+        assert_eq!(carry, 0);
+    }
+
+    fn mul_256(&self, other: &[u64; 4]) -> Self {
+        let mut limbs = [0; 190];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.iter().enumerate() {
+                if i + j >= limbs.len() {
+                    assert!(a == 0 || b == 0);
+                    continue;
+                }
+                let t = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            if i + 4 < limbs.len() {
+                limbs[i + 4] = carry as u64;
+            } else {
+                assert_eq!(carry, 0);
+            }
+        }
+        Big { limbs }
+    }
+
+    fn bit_length(&self) -> u32 {
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            if limb != 0 {
+                return 64 * i as u32 + 64 - limb.leading_zeros();
+            }
+        }
+        0
+    }
+
+    // Returns the low 256 bits of self >> shift, where a negative shift
+    // shifts to the left.
+    fn shr_256(&self, shift: i32) -> [u64; 4] {
+        let mut result = [0; 4];
+        for (k, word) in result.iter_mut().enumerate() {
+            for bit in 0..64 {
+                let index = shift + 64 * k as i32 + bit;
+                if index < 0 {
+                    continue;
+                }
+                let limb = self.limbs.get(index as usize / 64).copied().unwrap_or(0);
+                *word |= ((limb >> (index % 64)) & 1) << bit;
+            }
+        }
+        result
+    }
+
+    // Returns whether self is at most 2^exponent.
+    fn at_most_pow2(&self, exponent: u32) -> bool {
+        let bits = self.bit_length();
+        bits <= exponent
+            || (bits == exponent + 1
+                && self.limbs.iter().enumerate().all(|(i, &limb)| {
+                    limb == if i == (exponent / 64) as usize {
+                        1 << (exponent % 64)
+                    } else {
+                        0
+                    }
+                }))
+    }
+}
+
+#[test]
+fn test_compute_pow5_128() {
+    let mut pow5 = Big::one();
+    for i in 0..4970u32 {
+        // 5^i scaled to exactly Q_POW5_BITCOUNT bits, rounded down.
+        let shift = pow5.bit_length() as i32 - Q_POW5_BITCOUNT;
+        assert_eq!(compute_pow5_128(i), pow5.shr_256(shift), "5^{i}");
+        pow5.mul_small(5);
+    }
+}
+
+#[test]
+fn test_compute_inv_pow5_128() {
+    let mut pow5 = Big::one();
+    for i in 0..4970u32 {
+        // floor(2^k / 5^i) + 1 for k = pow5bits(i) - 1 + Q_POW5_INV_BITCOUNT,
+        // i.e. (inv - 1) * 5^i <= 2^k < inv * 5^i.
+        let k = (pow5bits_128(i as i32) - 1 + Q_POW5_INV_BITCOUNT) as u32;
+        let mut inv = compute_inv_pow5_128(i);
+        let product = pow5.mul_256(&inv);
+        assert!(!product.at_most_pow2(k), "5^-{i}");
+        let borrow = inv[0] == 0;
+        inv[0] = inv[0].wrapping_sub(1);
+        if borrow {
+            inv[1] -= 1;
+        }
+        let product = pow5.mul_256(&inv);
+        assert!(product.at_most_pow2(k), "5^-{i}");
+        pow5.mul_small(5);
+    }
+}
+
+#[test]
+fn test_mul_shift_128() {
+    let mul = [0, 0, 0, 1 << 63];
+    assert_eq!(mul_shift_128(1, &mul, 255), 1);
+    assert_eq!(mul_shift_128(u128::MAX, &mul, 255), u128::MAX);
+    let mul = [u64::MAX; 4];
+    assert_eq!(mul_shift_128(1 << 100, &mul, 256), (1 << 100) - 1);
+}
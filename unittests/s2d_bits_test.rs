@@ -0,0 +1,102 @@
+extern crate std;
+
+use std::string::String;
+
+use rand::Rng;
+
+use crate::parse::{s2d_bits, s2f16_bits, s2f_bits, Error};
+use crate::Formatter;
+
+#[test]
+fn test_nan() {
+    assert_eq!(Ok(0x7ff8000000000000), s2d_bits(b"nan"));
+    assert_eq!(Ok(0x7ff8000000000000), s2d_bits(b"NaN"));
+    assert_eq!(Ok(0xfff8000000000000), s2d_bits(b"-nan"));
+    assert_eq!(Ok(0x7ff8000000000001), s2d_bits(b"nan(0x8000000000001)"));
+    assert_eq!(Ok(0x7ff8000000000001), s2d_bits(b"nan(0x1)"));
+    assert_eq!(Ok(0x7ff8000000000000), s2d_bits(b"nan(0x0)"));
+    assert_eq!(Ok(0x7fffffffffffffff), s2d_bits(b"NAN(0XFFFFFFFFFFFFF)"));
+    assert_eq!(Ok(0x7ff0000000000001), s2d_bits(b"snan(0x1)"));
+    assert_eq!(
+        Ok(0xfff7ffffffffffff),
+        s2d_bits(b"-snan(0x0007ffffffffffff)")
+    );
+    assert_eq!(Ok(0x7fc00000), s2f_bits(b"nan"));
+    assert_eq!(Ok(0xff800abc), s2f_bits(b"-snan(0xabc)"));
+    assert_eq!(Ok(0x7fffffff), s2f_bits(b"nan(0x7fffff)"));
+}
+
+#[test]
+fn test_other() {
+    assert_eq!(Ok(0x7ff0000000000000), s2d_bits(b"inf"));
+    assert_eq!(Ok(0xfff0000000000000), s2d_bits(b"-INF"));
+    assert_eq!(Ok(0x8000000000000000), s2d_bits(b"-0.0"));
+    assert_eq!(Ok(1e21f64.to_bits()), s2d_bits(b"1e21"));
+    assert_eq!(Ok(0xff800000), s2f_bits(b"-inf"));
+    assert_eq!(
+        Ok(4335233100000.0f32.to_bits()),
+        s2f_bits(b"4335233100000.0")
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(Err(Error::InputTooShort), s2d_bits(b""));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"nan("));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"nan()"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"nan(0x)"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"nan(1)"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"nan(0x1"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"nan(0xg)"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"nanx"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"infinity"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"+nan"));
+    // A signaling NaN needs a payload, without the quiet bit.
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"snan"));
+    assert_eq!(Err(Error::MalformedInput), s2d_bits(b"snan(0x0)"));
+    assert_eq!(
+        Err(Error::MalformedInput),
+        s2d_bits(b"snan(0x8000000000001)")
+    );
+    assert_eq!(Err(Error::MalformedInput), s2f_bits(b"snan(0x400000)"));
+    // The payload must fit in the trailing significand.
+    assert_eq!(Err(Error::InputTooLong), s2d_bits(b"nan(0x10000000000000)"));
+    assert_eq!(Err(Error::InputTooLong), s2f_bits(b"nan(0x800000)"));
+}
+
+#[test]
+fn test_long_exponent() {
+    // The exponent is clamped only after the digits make up for it.
+    let mut text = String::from("0.");
+    text.push_str(&"0".repeat(1_999_999));
+    text.push_str("1e2000300");
+    assert_eq!(Ok(1e300f64.to_bits()), s2d_bits(text.as_bytes()));
+
+    let mut text = String::from("1");
+    text.push_str(&"0".repeat(2_000_000));
+    text.push_str("e-2000300");
+    assert_eq!(Ok(1e-300f64.to_bits()), s2d_bits(text.as_bytes()));
+    text.truncate(text.len() - 7);
+    text.push_str("2000000");
+    assert_eq!(Ok(0x3c00), s2f16_bits(text.as_bytes()));
+}
+
+#[test]
+fn test_round_trip() {
+    let mut rng = rand::rng();
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    for _ in 0..n {
+        // Half of the values are NaN.
+        let mut bits = rng.random::<u64>();
+        if bits & 1 != 0 {
+            bits |= 0x7ff0000000000000;
+        }
+        let d = f64::from_bits(bits);
+        let formatted = Formatter::format_f64_nan_payload(d);
+        assert_eq!(Ok(bits), s2d_bits(formatted.as_bytes()), "{formatted}");
+
+        let bits = bits as u32 | if bits & 2 != 0 { 0x7f800000 } else { 0 };
+        let formatted = Formatter::format_f32_nan_payload(f32::from_bits(bits));
+        assert_eq!(Ok(bits), s2f_bits(formatted.as_bytes()), "{formatted}");
+    }
+}
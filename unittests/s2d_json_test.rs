@@ -0,0 +1,195 @@
+extern crate std;
+
+use core::str;
+use std::string::String;
+
+use crate::parse::{s2d_json, JsonError, JsonErrorKind};
+
+fn err(kind: JsonErrorKind, index: usize) -> Result<(f64, usize), JsonError> {
+    Err(JsonError { kind, index })
+}
+
+#[test]
+fn test_grammar() {
+    assert_eq!(err(JsonErrorKind::UnexpectedEnd, 0), s2d_json(b""));
+    assert_eq!(err(JsonErrorKind::UnexpectedEnd, 1), s2d_json(b"-"));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 0), s2d_json(b".5"));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 0), s2d_json(b"+1"));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 1), s2d_json(b"-.5"));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 0), s2d_json(b"NaN"));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 1), s2d_json(b"-Infinity"));
+    assert_eq!(err(JsonErrorKind::LeadingZero, 0), s2d_json(b"01"));
+    assert_eq!(err(JsonErrorKind::LeadingZero, 1), s2d_json(b"-00.5"));
+    assert_eq!(err(JsonErrorKind::UnexpectedEnd, 2), s2d_json(b"1."));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 2), s2d_json(b"1.e5"));
+    assert_eq!(err(JsonErrorKind::UnexpectedEnd, 2), s2d_json(b"1e"));
+    assert_eq!(err(JsonErrorKind::UnexpectedEnd, 3), s2d_json(b"1e+"));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 3), s2d_json(b"1E-x"));
+}
+
+#[test]
+fn test_consumed() {
+    assert_eq!(Ok((0.0, 1)), s2d_json(b"0"));
+    assert_eq!(Ok((0.0, 1)), s2d_json(b"0x"));
+    assert_eq!(Ok((1.0, 1)), s2d_json(b"1,"));
+    assert_eq!(Ok((-12.5, 5)), s2d_json(b"-12.5]"));
+    assert_eq!(err(JsonErrorKind::ExpectedDigit, 2), s2d_json(b"1.."));
+    assert_eq!(Ok((1e5, 3)), s2d_json(b"1e5.0"));
+    assert_eq!(Ok((120.0, 7)), s2d_json(b"1.20E+2 "));
+    assert_eq!(Ok((0.01, 4)), s2d_json(b"1e-2}"));
+}
+
+#[test]
+fn test_basic() {
+    assert!(s2d_json(b"-0").unwrap().0.is_sign_negative());
+    assert!(s2d_json(b"-0.000e99").unwrap().0.is_sign_negative());
+    assert_eq!(0.1, s2d_json(b"0.1").unwrap().0);
+    assert_eq!(
+        0.1,
+        s2d_json(b"0.1000000000000000000000000000000000").unwrap().0
+    );
+    assert_eq!(123.456, s2d_json(b"123.456").unwrap().0);
+    assert_eq!(5e-324, s2d_json(b"5e-324").unwrap().0);
+    assert_eq!(f64::MAX, s2d_json(b"1.7976931348623157e308").unwrap().0);
+    assert_eq!(0.0, s2d_json(b"1e-99999999999999999999").unwrap().0);
+    assert_eq!(0.0, s2d_json(b"0e99999999999999999999").unwrap().0);
+    assert_eq!(
+        1.0,
+        s2d_json(b"0.00000000000000000000000000000001e32")
+            .unwrap()
+            .0
+    );
+}
+
+#[test]
+fn test_out_of_range() {
+    assert_eq!(err(JsonErrorKind::OutOfRange, 0), s2d_json(b"1e309"));
+    assert_eq!(
+        err(JsonErrorKind::OutOfRange, 0),
+        s2d_json(b"-1e99999999999999999999")
+    );
+    assert_eq!(
+        err(JsonErrorKind::OutOfRange, 0),
+        s2d_json(b"179769313486231580793728971405303415079934132710037826936173778980444968292764750946649017977587207096330286416692887910946555547851940402630657488671505820681908902000708383676273854845817711531764475730270069855571366959622842914819860834936475292719074168444365510704342711559699508093042880177904174497792")
+    );
+    assert_eq!(
+        f64::MAX,
+        s2d_json(b"179769313486231580793728971405303415079934132710037826936173778980444968292764750946649017977587207096330286416692887910946555547851940402630657488671505820681908902000708383676273854845817711531764475730270069855571366959622842914819860834936475292719074168444365510704342711559699508093042880177904174497791")
+            .unwrap()
+            .0
+    );
+}
+
+#[test]
+fn test_long_mantissa() {
+    // 2^53 + 1 is halfway between two doubles and rounds to even, any nonzero
+    // digit after it rounds up.
+    assert_eq!(9007199254740992.0, s2d_json(b"9007199254740993").unwrap().0);
+    assert_eq!(
+        9007199254740992.0,
+        s2d_json(b"9007199254740993.000000000000000000000")
+            .unwrap()
+            .0
+    );
+    assert_eq!(
+        9007199254740994.0,
+        s2d_json(b"9007199254740993.000000000000000000001")
+            .unwrap()
+            .0
+    );
+    assert_eq!(9007199254740996.0, s2d_json(b"9007199254740995").unwrap().0);
+    assert_eq!(
+        9007199254740994.0,
+        s2d_json(b"9007199254740994.999999999999999999999")
+            .unwrap()
+            .0
+    );
+    // Halfway between 0 and the smallest subnormal.
+    assert_eq!(0.0, s2d_json(b"2.4703282292062327208828439643411068618252990130716238221279284125033775363510437593264991818081799618989828234772285886546332835517796989819938739800539093906315035659515570226392290858392449105184435931802849936536152500319370457678249219365623669863658480757001585769269903706311928279558551332927834338409351978015531246597263579574622766465272827220056374006485499977096599470454020828166226237857393450736339007967761930577506740176324673600968951340535537458516661134223766678604162159680461914467291840300530057530849048765391711386591646239524912623653881879636239373280423891018672348497668235089863388587925628302755995657524455507255189313690836254779186948667994968324049705821028513185451396213837722826145437693412532098591327667236328125e-324").unwrap().0);
+    assert_eq!(5e-324, s2d_json(b"2.4703282292062327208828439643411068618252990130716238221279284125033775363510437593264991818081799618989828234772285886546332835517796989819938739800539093906315035659515570226392290858392449105184435931802849936536152500319370457678249219365623669863658480757001585769269903706311928279558551332927834338409351978015531246597263579574622766465272827220056374006485499977096599470454020828166226237857393450736339007967761930577506740176324673600968951340535537458516661134223766678604162159680461914467291840300530057530849048765391711386591646239524912623653881879636239373280423891018672348497668235089863388587925628302755995657524455507255189313690836254779186948667994968324049705821028513185451396213837722826145437693412532098591327667236328125000000000000000000000000000000001e-324").unwrap().0);
+}
+
+#[test]
+fn test_long_exponent() {
+    // The exponent is clamped only after the digits make up for it.
+    let mut text = String::from("0.");
+    text.push_str(&"0".repeat(1_999_999));
+    text.push_str("1e2000300");
+    assert_eq!(Ok((1e300, text.len())), s2d_json(text.as_bytes()));
+
+    let mut text = String::from("1");
+    text.push_str(&"0".repeat(2_000_000));
+    text.push_str("e-2000300");
+    assert_eq!(Ok((1e-300, text.len())), s2d_json(text.as_bytes()));
+
+    let mut text = String::from("1");
+    text.push_str(&"0".repeat(2_000_000));
+    text.push_str("e-99999999999999999999");
+    assert_eq!(Ok((0.0, text.len())), s2d_json(text.as_bytes()));
+}
+
+#[test]
+fn test_random_against_core() {
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::seed_from_u64(0x5eed_0027);
+    let n = if cfg!(miri) { 100 } else { 100_000 };
+    let mut buffer = [0u8; 128];
+    for _ in 0..n {
+        let mut len = 0;
+        let int_digits = rng.random_range(1..30);
+        for k in 0..int_digits {
+            buffer[len] = if k == 0 {
+                rng.random_range(b'1'..=b'9')
+            } else {
+                rng.random_range(b'0'..=b'9')
+            };
+            len += 1;
+        }
+        if rng.random_bool(0.5) {
+            buffer[len] = b'.';
+            len += 1;
+            for _ in 0..rng.random_range(1..40) {
+                buffer[len] = rng.random_range(b'0'..=b'9');
+                len += 1;
+            }
+        }
+        let e10: i32 = rng.random_range(-360..330);
+        buffer[len] = b'e';
+        len += 1;
+        let mut e = e10.unsigned_abs();
+        if e10 < 0 {
+            buffer[len] = b'-';
+            len += 1;
+        }
+        let start = len;
+        loop {
+            buffer[len] = b'0' + (e % 10) as u8;
+            len += 1;
+            e /= 10;
+            if e == 0 {
+                break;
+            }
+        }
+        buffer[start..len].reverse();
+
+        let input = &buffer[..len];
+        let expected: f64 = str::from_utf8(input).unwrap().parse().unwrap();
+        match s2d_json(input) {
+            Ok((value, consumed)) => {
+                assert_eq!(
+                    expected.to_bits(),
+                    value.to_bits(),
+                    "{}",
+                    str::from_utf8(input).unwrap()
+                );
+                assert_eq!(len, consumed);
+            }
+            Err(e) => {
+                assert!(expected.is_infinite(), "{}", str::from_utf8(input).unwrap());
+                assert_eq!(JsonErrorKind::OutOfRange, e.kind);
+            }
+        }
+    }
+}
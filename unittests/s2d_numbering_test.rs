@@ -0,0 +1,112 @@
+extern crate std;
+
+use std::string::String;
+
+use rand::Rng;
+
+use crate::format::NumberingSystem;
+use crate::parse::{s2d_numbering, s2f_numbering, Error};
+use crate::Formatter;
+
+const SYSTEMS: [NumberingSystem; 7] = [
+    NumberingSystem::ASCII,
+    NumberingSystem::ARABIC_INDIC,
+    NumberingSystem::EXTENDED_ARABIC_INDIC,
+    NumberingSystem::DEVANAGARI,
+    NumberingSystem::BENGALI,
+    NumberingSystem::THAI,
+    NumberingSystem::FULLWIDTH,
+];
+
+#[test]
+fn test_basic() {
+    let arabic = NumberingSystem::ARABIC_INDIC;
+    assert_eq!(Ok(123.45), s2d_numbering("١٢٣٫٤٥".as_bytes(), arabic));
+    assert_eq!(Ok(-1e-7), s2d_numbering("-١e-٧".as_bytes(), arabic));
+    assert_eq!(Ok(1e7), s2d_numbering("١E+٧".as_bytes(), arabic));
+    assert_eq!(
+        Ok(-0.5),
+        s2d_numbering(
+            "\u{2212}\u{6f0}\u{66b}\u{6f5}".as_bytes(),
+            NumberingSystem::EXTENDED_ARABIC_INDIC
+        )
+    );
+    assert_eq!(
+        Ok(0.1),
+        s2f_numbering("０．１".as_bytes(), NumberingSystem::FULLWIDTH)
+    );
+}
+
+#[test]
+fn test_long() {
+    // Any number of digits, correctly rounded.
+    let mut text = String::from("٠٫١");
+    for _ in 0..30 {
+        text.push('٠');
+    }
+    text.push('١');
+    assert_eq!(
+        Ok(0.1),
+        s2d_numbering(text.as_bytes(), NumberingSystem::ARABIC_INDIC)
+    );
+}
+
+#[test]
+fn test_errors() {
+    let arabic = NumberingSystem::ARABIC_INDIC;
+    assert_eq!(Err(Error::InputTooShort), s2d_numbering(b"", arabic));
+    // The digits, decimal point and minus sign of other systems.
+    assert_eq!(Err(Error::MalformedInput), s2d_numbering(b"12", arabic));
+    assert_eq!(
+        Err(Error::MalformedInput),
+        s2d_numbering("١.٢".as_bytes(), arabic)
+    );
+    assert_eq!(
+        Err(Error::MalformedInput),
+        s2d_numbering("١٢۳".as_bytes(), arabic)
+    );
+    assert_eq!(
+        Err(Error::MalformedInput),
+        s2d_numbering("-０".as_bytes(), NumberingSystem::FULLWIDTH)
+    );
+    assert_eq!(
+        Err(Error::MalformedInput),
+        s2d_numbering("१e-२".as_bytes(), NumberingSystem::ASCII)
+    );
+    // Invalid UTF-8: a truncated sequence, a lone continuation byte and an
+    // overlong encoding of "0".
+    assert_eq!(Err(Error::MalformedInput), s2d_numbering(b"\xd9", arabic));
+    assert_eq!(
+        Err(Error::MalformedInput),
+        s2d_numbering(b"1\xa0", NumberingSystem::ASCII)
+    );
+    assert_eq!(
+        Err(Error::MalformedInput),
+        s2d_numbering(b"\xe0\x80\xb0", NumberingSystem::ASCII)
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let mut rng = rand::rng();
+    let mut buffer = [0u8; 4 * 32];
+    let n = if cfg!(miri) { 100 } else { 10000 };
+    for _ in 0..n {
+        let d = f64::from_bits(rng.random::<u64>());
+        if !d.is_finite() {
+            continue;
+        }
+        let system = SYSTEMS[rng.random_range(0..SYSTEMS.len())];
+        let formatted = Formatter::format_f64(d);
+        let text = formatted.write_numbering(system, &mut buffer).unwrap();
+        assert_eq!(Ok(d), s2d_numbering(text.as_bytes(), system), "{text}");
+
+        let f = f32::from_bits(rng.random::<u32>());
+        if !f.is_finite() {
+            continue;
+        }
+        let formatted = Formatter::format_f32(f);
+        let text = formatted.write_numbering(system, &mut buffer).unwrap();
+        assert_eq!(Ok(f), s2f_numbering(text.as_bytes(), system), "{text}");
+    }
+}
@@ -0,0 +1,108 @@
+extern crate std;
+
+use std::format;
+
+use rand::Rng;
+
+use crate::parse::{s2d_radix, s2f_radix, Error};
+use crate::Formatter;
+
+#[test]
+fn test_basic() {
+    assert_eq!(Ok(255.5), s2d_radix(b"ff.8", 16));
+    assert_eq!(Ok(255.5), s2d_radix(b"FF.8", 16));
+    assert_eq!(Ok(-0.5), s2d_radix(b"-0.1", 2));
+    assert_eq!(Ok(-0.5), s2d_radix(b"-.1", 2));
+    assert_eq!(Ok(1.0), s2d_radix(b"1.", 36));
+    assert_eq!(Ok(1296.0), s2d_radix(b"100", 36));
+    assert_eq!(Ok(1296.0), s2d_radix(b"000100.000", 36));
+    assert_eq!(Ok(1.0 / 3.0), s2d_radix(b"0.1", 3));
+    assert!(s2d_radix(b"-0", 7).unwrap().is_sign_negative());
+    assert_eq!(Ok(1e21), s2d_radix(b"5v1j4f4ds7a000", 36));
+    assert_eq!(Ok(0.1f32), s2f_radix(b"0.199999a", 16));
+}
+
+#[test]
+fn test_radix_10() {
+    // Radix 10 accepts an exponent and any number of digits.
+    assert_eq!(Ok(1e21), s2d_radix(b"1e21", 10));
+    assert_eq!(Ok(0.1), s2d_radix(b"0.1000000000000000000000000001", 10));
+    assert_eq!(Ok(4335233100000.0f32), s2f_radix(b"4335233100000.0", 10));
+}
+
+#[test]
+fn test_rounding() {
+    // 2^53 + 1 is halfway between two doubles, and rounds to the even one.
+    assert_eq!(Ok(9007199254740992.0), s2d_radix(b"20000000000001", 16));
+    assert_eq!(Ok(9007199254740996.0), s2d_radix(b"20000000000003", 16));
+    // Just above the halfway point.
+    assert_eq!(Ok(9007199254740994.0), s2d_radix(b"20000000000001.1", 16));
+
+    // Half of the smallest subnormal, 2^-1075, rounds to zero, but not
+    // anything above it.
+    let half = format!("0.{}2", "0".repeat(268));
+    assert_eq!(Ok(0.0), s2d_radix(half.as_bytes(), 16));
+    assert_eq!(Ok(5e-324), s2d_radix(format!("{half}01").as_bytes(), 16));
+}
+
+#[test]
+fn test_overflow() {
+    let ones = "1".repeat(53);
+    let max = format!("{ones}{}", "0".repeat(971));
+    assert_eq!(Ok(f64::MAX), s2d_radix(max.as_bytes(), 2));
+    // Halfway between f64::MAX and 2^1024 rounds to even, which is infinite.
+    let halfway = format!("{ones}1{}", "0".repeat(970));
+    assert_eq!(Ok(f64::INFINITY), s2d_radix(halfway.as_bytes(), 2));
+    assert_eq!(
+        Ok(f64::NEG_INFINITY),
+        s2d_radix(format!("-1{}", "0".repeat(200)).as_bytes(), 36)
+    );
+    assert_eq!(
+        Ok(f32::INFINITY),
+        s2f_radix(format!("1{}", "0".repeat(32)).as_bytes(), 16)
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(Err(Error::InputTooShort), s2d_radix(b"", 16));
+    assert_eq!(Err(Error::MalformedInput), s2d_radix(b"-", 16));
+    assert_eq!(Err(Error::MalformedInput), s2d_radix(b".", 16));
+    assert_eq!(Err(Error::MalformedInput), s2d_radix(b"1.2.3", 16));
+    assert_eq!(Err(Error::MalformedInput), s2d_radix(b"2", 2));
+    assert_eq!(Err(Error::MalformedInput), s2d_radix(b"g", 16));
+    assert_eq!(Err(Error::MalformedInput), s2d_radix(b"+1", 16));
+    assert_eq!(Err(Error::MalformedInput), s2d_radix(b"1p4", 16));
+    assert_eq!(
+        Err(Error::InputTooLong),
+        s2d_radix(b"10000000000000001", 16)
+    );
+    assert_eq!(Err(Error::InputTooLong), s2f_radix(b"zzzzzzzzzzzzz", 36));
+}
+
+#[test]
+#[should_panic = "`radix` must be from 2 to 36"]
+fn test_invalid_radix() {
+    let _ = s2d_radix(b"1", 37);
+}
+
+#[test]
+fn test_round_trip() {
+    let mut rng = rand::rng();
+    let n = if cfg!(miri) { 100 } else { 20000 };
+    for i in 0..n {
+        let radix = 2 + i % 35;
+        let d = f64::from_bits(rng.random::<u64>());
+        if d.is_finite() {
+            let formatted = Formatter::format_f64_radix(d, radix);
+            let parsed = s2d_radix(formatted.as_str().as_bytes(), radix).unwrap();
+            assert_eq!(d.to_bits(), parsed.to_bits(), "{d:e} {radix}");
+        }
+        let f = f32::from_bits(rng.random::<u32>());
+        if f.is_finite() {
+            let formatted = Formatter::format_f32_radix(f, radix);
+            let parsed = s2f_radix(formatted.as_str().as_bytes(), radix).unwrap();
+            assert_eq!(f.to_bits(), parsed.to_bits(), "{f:e} {radix}");
+        }
+    }
+}
@@ -18,7 +18,7 @@
 // is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
 // KIND, either express or implied.
 
-use crate::parse::{s2d, Error};
+use crate::parse::{s2d, s2d_const, Error};
 
 #[test]
 fn test_bad_input() {
@@ -103,3 +103,16 @@ fn test_issue173() {
     assert_eq!(2.2250738585072013e-308, s2d(b"2.2250738585072013e-308").unwrap());
     assert_eq!(2.2250738585072014e-308, s2d(b"2.2250738585072014e-308").unwrap());
 }
+
+#[test]
+fn test_const() {
+    const ONE_TENTH: f64 = s2d_const(b"0.1");
+    const MAX: f64 = s2d_const(b"1.7976931348623157e308");
+    const NEG_SUBNORMAL: f64 = s2d_const(b"-5e-324");
+    assert_eq!(0.1, ONE_TENTH);
+    assert_eq!(f64::MAX, MAX);
+    assert_eq!(-5e-324, NEG_SUBNORMAL);
+
+    const MALFORMED: Result<f64, Error> = s2d(b"1e.1");
+    assert_eq!(Error::MalformedInput, MALFORMED.unwrap_err());
+}
@@ -0,0 +1,134 @@
+extern crate std;
+
+use std::string::String;
+
+use crate::parse::{s2decimal128_bits, s2decimal64_bits, Error};
+use crate::Formatter;
+
+fn d64(sign: bool, coefficient: u64, exponent: i32) -> u64 {
+    ((sign as u64) << 63) | (((exponent + 398) as u64) << 53) | coefficient
+}
+
+fn d128(sign: bool, coefficient: u128, exponent: i32) -> u128 {
+    ((sign as u128) << 127) | (((exponent + 6176) as u128) << 113) | coefficient
+}
+
+#[test]
+fn test_cohort() {
+    assert_eq!(Ok(d64(false, 120, -2)), s2decimal64_bits(b"1.20"));
+    assert_eq!(Ok(d64(false, 120, 0)), s2decimal64_bits(b"120"));
+    assert_eq!(Ok(d64(false, 12, 1)), s2decimal64_bits(b"1.2e2"));
+    assert_eq!(Ok(d64(true, 0, -2)), s2decimal64_bits(b"-0.00"));
+    assert_eq!(Ok(d64(false, 0, 5)), s2decimal64_bits(b"0e5"));
+    assert_eq!(Ok(d64(false, 120, -6)), s2decimal64_bits(b"0.000120"));
+    assert_eq!(Ok(d64(false, 5, -1)), s2decimal64_bits(b".5"));
+    assert_eq!(Ok(d128(false, 10, -1)), s2decimal128_bits(b"001.0"));
+}
+
+#[test]
+fn test_rounding() {
+    // 17 digits round to 16, ties to even.
+    assert_eq!(
+        Ok(d64(false, 1234567890123456, 1)),
+        s2decimal64_bits(b"12345678901234565")
+    );
+    assert_eq!(
+        Ok(d64(false, 1234567890123457, 1)),
+        s2decimal64_bits(b"12345678901234565.000001")
+    );
+    assert_eq!(
+        Ok(d64(false, 1000000000000000, 1)),
+        s2decimal64_bits(b"9999999999999999.5")
+    );
+    // Below the smallest exponent.
+    assert_eq!(Ok(d64(false, 2, -398)), s2decimal64_bits(b"15e-399"));
+    assert_eq!(Ok(d64(false, 0, -398)), s2decimal64_bits(b"5e-399"));
+    assert_eq!(Ok(d64(false, 1, -398)), s2decimal64_bits(b"5.1e-399"));
+    assert_eq!(Ok(d64(true, 0, -398)), s2decimal64_bits(b"-1e-99999"));
+}
+
+#[test]
+fn test_range() {
+    // Above the largest exponent, the coefficient is padded with zeros.
+    assert_eq!(Ok(d64(false, 1000, 369)), s2decimal64_bits(b"1e372"));
+    assert_eq!(Ok(d64(false, 0, 369)), s2decimal64_bits(b"0e400"));
+    assert_eq!(
+        Ok(0x77fb86f26fc0ffff),
+        s2decimal64_bits(b"9.999999999999999e384")
+    );
+    assert_eq!(Ok(0x7800000000000000), s2decimal64_bits(b"1e385"));
+    assert_eq!(
+        Ok(0x7800000000000000),
+        s2decimal64_bits(b"9.9999999999999995e384")
+    );
+    assert_eq!(
+        Ok(0xf800_0000_0000_0000_0000_0000_0000_0000),
+        s2decimal128_bits(b"-1e6145")
+    );
+    assert_eq!(
+        Ok(d128(false, 10u128.pow(33), 6111)),
+        s2decimal128_bits(b"1e6144")
+    );
+}
+
+#[test]
+fn test_long_exponent() {
+    // The exponent is clamped only after the digits make up for it.
+    let mut text = String::from("0.");
+    text.push_str(&"0".repeat(1_999_999));
+    text.push_str("1e2000300");
+    assert_eq!(Ok(d64(false, 1, 300)), s2decimal64_bits(text.as_bytes()));
+
+    let mut text = String::from("1");
+    text.push_str(&"0".repeat(2_000_000));
+    text.push_str("e-2000300");
+    assert_eq!(
+        Ok(d64(false, 10u64.pow(15), -315)),
+        s2decimal64_bits(text.as_bytes())
+    );
+}
+
+#[test]
+fn test_special() {
+    assert_eq!(Ok(0x7800000000000000), s2decimal64_bits(b"inf"));
+    assert_eq!(Ok(0xf800000000000000), s2decimal64_bits(b"-INF"));
+    assert_eq!(Ok(0x7c00000000000000), s2decimal64_bits(b"NaN"));
+    assert_eq!(Ok(0xfc00000000000000), s2decimal64_bits(b"-nan"));
+    assert_eq!(
+        Ok(0x7c00_0000_0000_0000_0000_0000_0000_0000),
+        s2decimal128_bits(b"NaN")
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(Err(Error::InputTooShort), s2decimal64_bits(b""));
+    assert_eq!(Err(Error::MalformedInput), s2decimal64_bits(b"-"));
+    assert_eq!(Err(Error::MalformedInput), s2decimal64_bits(b"."));
+    assert_eq!(Err(Error::MalformedInput), s2decimal64_bits(b"1.2.3"));
+    assert_eq!(Err(Error::MalformedInput), s2decimal64_bits(b"1e"));
+    assert_eq!(Err(Error::MalformedInput), s2decimal64_bits(b"1e+"));
+    assert_eq!(Err(Error::MalformedInput), s2decimal64_bits(b"1x"));
+    assert_eq!(Err(Error::MalformedInput), s2decimal128_bits(b"infinity"));
+}
+
+#[test]
+fn test_round_trip() {
+    // The cohort output gives back the same bits, and the shortest output the
+    // same value.
+    for (coefficient, exponent) in [(120, -2), (1, 20), (0, -7), (9999999999999999, 369)] {
+        for sign in [false, true] {
+            let bits = d64(sign, coefficient, exponent);
+            let formatted = Formatter::format_decimal64_bits_cohort(bits);
+            assert_eq!(Ok(bits), s2decimal64_bits(formatted.as_bytes()));
+        }
+    }
+    let bits = d128(false, 1234567890123456789012345678901234, -6176);
+    let formatted = Formatter::format_decimal128_bits_cohort(bits);
+    assert_eq!(Ok(bits), s2decimal128_bits(formatted.as_bytes()));
+    let formatted = Formatter::format_decimal64_bits(d64(false, 120, -2));
+    assert_eq!(
+        Ok(d64(false, 12, -1)),
+        s2decimal64_bits(formatted.as_bytes())
+    );
+}
@@ -55,3 +55,11 @@ fn test_trailing_zeros() {
     assert_eq!(50000004.0, s2f(b"50000002.5").unwrap());
     assert_eq!(99999992.0, s2f(b"99999989.5").unwrap());
 }
+
+#[test]
+fn test_const() {
+    const ONE_TENTH: f32 = s2f_const(b"0.1");
+    const MAX: f32 = s2f_const(b"3.4028235e+38");
+    assert_eq!(0.1, ONE_TENTH);
+    assert_eq!(f32::MAX, MAX);
+}
@@ -0,0 +1,79 @@
+use rand::Rng;
+
+use crate::parse::{s2fixed_point, Error};
+use crate::Formatter;
+
+#[test]
+fn test_basic() {
+    assert_eq!(Ok(0), s2fixed_point(b"0", 16));
+    assert_eq!(Ok(0), s2fixed_point(b"-0.0", 16));
+    assert_eq!(Ok(72090), s2fixed_point(b"1.1", 16));
+    assert_eq!(Ok(-3 << 16), s2fixed_point(b"-3", 16));
+    assert_eq!(Ok(214748365), s2fixed_point(b"0.1", 31));
+    assert_eq!(Ok(123), s2fixed_point(b"1.23e2", 0));
+    assert_eq!(Ok(1), s2fixed_point(b"5e-20", 64));
+}
+
+#[test]
+fn test_rounding() {
+    // Ties go to the even neighbour.
+    assert_eq!(Ok(0), s2fixed_point(b"0.5", 0));
+    assert_eq!(Ok(2), s2fixed_point(b"1.5", 0));
+    assert_eq!(Ok(-2), s2fixed_point(b"-2.5", 0));
+    assert_eq!(Ok(2), s2fixed_point(b"0.625", 2));
+    assert_eq!(Ok(3), s2fixed_point(b"0.6250000000000000000000001", 2));
+    assert_eq!(Ok(0), s2fixed_point(b"0.00000762939453125", 16));
+    assert_eq!(Ok(1), s2fixed_point(b"0.00000762939453126", 16));
+    // A tie decided by a digit beyond the ones that are kept.
+    let mut long = b"0.5".to_vec();
+    long.resize(2000, b'0');
+    assert_eq!(Ok(0), s2fixed_point(&long, 0));
+    long.push(b'1');
+    assert_eq!(Ok(1), s2fixed_point(&long, 0));
+}
+
+#[test]
+fn test_saturation() {
+    assert_eq!(Ok(i64::MAX), s2fixed_point(b"9223372036854775807", 0));
+    assert_eq!(Ok(i64::MAX), s2fixed_point(b"9223372036854775807.5", 0));
+    assert_eq!(Ok(i64::MIN), s2fixed_point(b"-9223372036854775808", 0));
+    assert_eq!(Ok(i64::MIN), s2fixed_point(b"-9223372036854775809", 0));
+    assert_eq!(Ok(i64::MAX), s2fixed_point(b"1", 63));
+    assert_eq!(Ok(i64::MIN), s2fixed_point(b"-1", 63));
+    assert_eq!(Ok(i64::MAX), s2fixed_point(b"1e10", 31));
+    assert_eq!(Ok(i64::MIN), s2fixed_point(b"-1e99999", 0));
+    assert_eq!(Ok(0), s2fixed_point(b"1e-99999", 64));
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(Err(Error::InputTooShort), s2fixed_point(b"", 16));
+    assert_eq!(Err(Error::MalformedInput), s2fixed_point(b"1.2.3", 16));
+    assert_eq!(Err(Error::MalformedInput), s2fixed_point(b"1e+x", 16));
+    assert_eq!(Err(Error::MalformedInput), s2fixed_point(b"0x10", 16));
+    assert_eq!(Err(Error::MalformedInput), s2fixed_point(b"inf", 16));
+}
+
+#[test]
+#[should_panic = "`frac_bits` must be at most 64"]
+fn test_too_many_frac_bits() {
+    let _ = s2fixed_point(b"1", 65);
+}
+
+#[test]
+fn test_round_trip() {
+    let mut rng = rand::rng();
+    let n = if cfg!(miri) { 100 } else { 10000 };
+    for _ in 0..n {
+        let raw = rng.random::<i64>() >> rng.random_range(0..64);
+        let frac_bits = rng.random_range(0..=64);
+        let formatted = Formatter::format_fixed_point(raw, frac_bits);
+        assert_eq!(
+            Ok(raw),
+            s2fixed_point(formatted.as_str().as_bytes(), frac_bits),
+            "{} with {} fractional bits",
+            formatted.as_str(),
+            frac_bits,
+        );
+    }
+}